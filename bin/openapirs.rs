@@ -0,0 +1,520 @@
+// SPDX-License-Identifier: MIT
+//
+// Command line interface of the openapi compiler
+//
+extern crate openapirs;
+
+use openapirs::check;
+use openapirs::codegen;
+use openapirs::compile;
+use openapirs::compile::operation::Operation;
+use openapirs::compile::router::Router;
+use openapirs::model;
+use openapirs::schema::load;
+use openapirs::schema::span::Spans;
+use openapirs::schema::Description;
+use openapirs::transform;
+use openapirs::transform::bundle;
+use openapirs::transform::deref;
+use serde_json::json;
+use std::io::Read;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage: openapirs <command> [options] [<file>]
+
+Commands:
+  parse      Parse description and print it back
+  validate   Check description (references, path templates, examples)
+  compile    Compile operations and list them
+  model      Build model of types to be generated
+  generate   Generate Rust types, client and server into output directory
+  bundle     Bundle multi-file description into single document
+  deref      Bundle description and replace references by copies
+             of referenced objects
+
+Options:
+  -o, --output <path>     Output directory (generate) or file (bundle
+                          and deref)
+  -f, --format <format>   Output format: human (default) or json
+  --operation <id>        Select operation by operationId (compile
+                          and generate, repeatable)
+  --client                Generate client (default unless --server)
+  --server                Generate server traits and axum routers
+  --split-read-write      Generate request and response variants of
+                          schemas with readOnly or writeOnly properties
+  --recursion-limit <n>   Expand recursive schema references n times
+                          and replace them by any value then (deref)
+  -h, --help              Print this help
+
+Description is read from <file> (YAML or JSON) or from standard
+input if <file> is omitted or -.
+
+Exit status is 0 on success, 1 if the description has problems
+and 2 on other errors.
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Parse,
+    Validate,
+    Compile,
+    Model,
+    Generate,
+    Bundle,
+    Deref,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Debug)]
+struct Args {
+    command: Command,
+    input: Option<String>,
+    output: Option<String>,
+    format: Format,
+    operations: Vec<String>,
+    // Parts of the generated code (both false if not given)
+    client: bool,
+    server: bool,
+    // Split models with read-only or write-only properties (model and
+    // generate)
+    split: bool,
+    // Expansions of recursive references (deref)
+    recursion_limit: Option<u32>,
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Io(String, std::io::Error),
+    Load(String, load::Error),
+    Compile(String, String),
+    Model(String, String),
+    Bundle(String, String),
+    Deref(String, String),
+    Serialize(String),
+    OperationNotFound(String),
+    // Number of problems found by validate
+    Invalid(usize),
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|v| v == "-h" || v == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+    match parse_args(&args).and_then(|args| run(&args)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Error::Invalid(_)) => ExitCode::from(1),
+        Err(err @ Error::Usage(_)) => {
+            eprintln!("{err}");
+            eprint!("\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Args, Error> {
+    let mut args = args.iter();
+    let command = match args.next().map(String::as_str) {
+        Some("parse") => Command::Parse,
+        Some("validate") => Command::Validate,
+        Some("compile") => Command::Compile,
+        Some("model") => Command::Model,
+        Some("generate") => Command::Generate,
+        Some("bundle") => Command::Bundle,
+        Some("deref") => Command::Deref,
+        Some(other) => return Err(Error::Usage(format!("unknown command: {other}"))),
+        None => return Err(Error::Usage("command is needed".into())),
+    };
+    let mut result = Args {
+        command,
+        input: None,
+        output: None,
+        format: Format::Human,
+        operations: vec![],
+        client: false,
+        server: false,
+        split: false,
+        recursion_limit: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .cloned()
+                .ok_or_else(|| Error::Usage(format!("value of {arg} is needed")))
+        };
+        match arg.as_str() {
+            "-o" | "--output" => result.output = Some(value()?),
+            "-f" | "--format" => {
+                result.format = match value()?.as_str() {
+                    "human" => Format::Human,
+                    "json" => Format::Json,
+                    other => return Err(Error::Usage(format!("unknown format: {other}"))),
+                }
+            }
+            "--operation" => result.operations.push(value()?),
+            "--client" => result.client = true,
+            "--server" => result.server = true,
+            "--split-read-write" => result.split = true,
+            "--recursion-limit" => {
+                let v = value()?;
+                let limit = v
+                    .parse()
+                    .map_err(|_| Error::Usage(format!("invalid recursion limit: {v}")))?;
+                result.recursion_limit = Some(limit);
+            }
+            "-" => result.input = None,
+            option if option.starts_with('-') => {
+                return Err(Error::Usage(format!("unknown option: {option}")))
+            }
+            file if result.input.is_none() => result.input = Some(file.into()),
+            file => return Err(Error::Usage(format!("unexpected argument: {file}"))),
+        }
+    }
+    if !result.operations.is_empty() && !matches!(command, Command::Compile | Command::Generate) {
+        return Err(Error::Usage(
+            "--operation is supported by compile and generate only".into(),
+        ));
+    }
+    if result.recursion_limit.is_some() && command != Command::Deref {
+        return Err(Error::Usage(
+            "--recursion-limit is supported by deref only".into(),
+        ));
+    }
+    if command == Command::Generate && result.output.is_none() {
+        return Err(Error::Usage("output directory is needed".into()));
+    }
+    Ok(result)
+}
+
+fn run(args: &Args) -> Result<(), Error> {
+    if matches!(args.command, Command::Bundle | Command::Deref) {
+        return run_bundle(args);
+    }
+    let (d, spans) = load(args)?;
+    match args.command {
+        Command::Parse => {
+            let contents = match args.format {
+                Format::Human => serde_yaml::to_string(&d).map_err(|err| err.to_string()),
+                Format::Json => serde_json::to_string_pretty(&d).map_err(|err| err.to_string()),
+            };
+            println!("{}", contents.map_err(Error::Serialize)?.trim_end());
+            Ok(())
+        }
+        Command::Validate => validate(args, &d, &spans),
+        Command::Compile => {
+            let compiled = compile(&d, &spans)?;
+            let operations = select(args, &compiled.operations)?;
+            match args.format {
+                Format::Human => {
+                    for op in operations {
+                        println!("{}", describe(op));
+                    }
+                    for name in compiled.schemas.keys() {
+                        println!("schema {}", name.as_str());
+                    }
+                }
+                Format::Json => {
+                    let operations: Vec<_> = operations
+                        .iter()
+                        .map(|op| {
+                            json!({
+                                "method": op.op_type.as_str(),
+                                "location": op.location.to_string(),
+                                "operationId": op.operation_id.map(|v| v.as_str()),
+                            })
+                        })
+                        .collect();
+                    let schemas: Vec<_> = compiled.schemas.keys().map(|v| v.as_str()).collect();
+                    print_json(&json!({"operations": operations, "schemas": schemas}))?;
+                }
+            }
+            Ok(())
+        }
+        Command::Model => {
+            let compiled = compile(&d, &spans)?;
+            let mut models = model::build(&compiled)
+                .map_err(|err| Error::Model(file_name(&spans), format!("{err:?}")))?;
+            let mut operations = model::operation::build(&compiled, &mut models)
+                .map_err(|err| Error::Model(file_name(&spans), format!("{err:?}")))?;
+            if args.split {
+                model::split::split(&mut models, &mut operations);
+            }
+            let generator = codegen::rust::Generator::new(&models);
+            let models: Vec<_> = models
+                .iter()
+                .map(|(name, m)| (generator.type_name(name), kind(m)))
+                .collect();
+            match args.format {
+                Format::Human => {
+                    for (name, kind) in models {
+                        println!("{kind} {name}");
+                    }
+                }
+                Format::Json => {
+                    let models: Vec<_> = models
+                        .iter()
+                        .map(|(name, kind)| json!({"name": name, "kind": kind}))
+                        .collect();
+                    print_json(&json!(models))?;
+                }
+            }
+            Ok(())
+        }
+        Command::Generate => {
+            // Invalid defaults are refused as their values can't be
+            // generated for the types
+            let defaults: Vec<_> = check::example::check(&d)
+                .into_iter()
+                .filter(|v| v.location.ends_with("/default"))
+                .collect();
+            for v in defaults.iter() {
+                eprintln!("{}: default {v}", locate(&spans, &v.location));
+            }
+            if !defaults.is_empty() {
+                return Err(Error::Invalid(defaults.len()));
+            }
+            let compiled = compile(&d, &spans)?;
+            let selected = select(args, &compiled.operations)?;
+            let mut models = model::build(&compiled)
+                .map_err(|err| Error::Model(file_name(&spans), format!("{err:?}")))?;
+            let mut operations: Vec<_> = model::operation::build(&compiled, &mut models)
+                .map_err(|err| Error::Model(file_name(&spans), format!("{err:?}")))?
+                .into_iter()
+                .filter(|op| selected.iter().any(|v| std::ptr::eq(*v, op.operation)))
+                .collect();
+            if args.split {
+                model::split::split(&mut models, &mut operations);
+            }
+            let generator = codegen::rust::Generator::new(&models);
+            for (name, field) in generator.ignored_defaults(&operations) {
+                eprintln!("warning: default of {name}.{field} can't be generated and is ignored");
+            }
+            let dir = std::path::Path::new(args.output.as_deref().unwrap_or_default());
+            std::fs::create_dir_all(dir)
+                .map_err(|err| Error::Io(dir.display().to_string(), err))?;
+            let mut written = vec![];
+            let options = match args.client || args.server {
+                true => codegen::rust::Options {
+                    client: args.client,
+                    server: args.server,
+                },
+                false => codegen::rust::Options::default(),
+            };
+            for file in codegen::rust::generate(&models, &operations, options) {
+                let path = dir.join(&file.path);
+                std::fs::write(&path, file.contents)
+                    .map_err(|err| Error::Io(path.display().to_string(), err))?;
+                written.push(path.display().to_string());
+            }
+            match args.format {
+                Format::Human => written.iter().for_each(|path| println!("{path}")),
+                Format::Json => print_json(&json!(written))?,
+            }
+            Ok(())
+        }
+        Command::Bundle | Command::Deref => unreachable!(),
+    }
+}
+
+fn validate(args: &Args, d: &Description, spans: &Spans) -> Result<(), Error> {
+    // Location and description of the problem
+    let mut problems: Vec<(String, String)> = check::example::check(d)
+        .iter()
+        .map(|v| {
+            let what = match v.location.ends_with("/default") {
+                true => "default",
+                false => "example",
+            };
+            (locate(spans, &v.location), format!("{what} {v}"))
+        })
+        .collect();
+    match compile::compile(d) {
+        Ok(compiled) => {
+            if let Err(err) = Router::new(&compiled.operations) {
+                problems.push((locate(spans, &err.pointer()), err.to_string()));
+            }
+            let models = model::build(&compiled).and_then(|mut models| {
+                model::operation::build(&compiled, &mut models).map(|_| models)
+            });
+            if let Err(err) = models {
+                problems.push((file_name(spans), format!("model: {err:?}")));
+            }
+        }
+        Err(err) => problems.push((
+            locate(spans, &err.pointer()),
+            format!("compilation: {err:?}"),
+        )),
+    }
+    match args.format {
+        Format::Human => {
+            for (location, problem) in problems.iter() {
+                println!("{location}: {problem}");
+            }
+        }
+        Format::Json => {
+            let list: Vec<_> = problems
+                .iter()
+                .map(|(location, problem)| json!({"location": location, "message": problem}))
+                .collect();
+            print_json(&json!({"valid": problems.is_empty(), "problems": list}))?
+        }
+    }
+    match problems.len() {
+        0 => Ok(()),
+        n => Err(Error::Invalid(n)),
+    }
+}
+
+// Bundle (and dereference) description
+fn run_bundle(args: &Args) -> Result<(), Error> {
+    let Some(input) = &args.input else {
+        return Err(Error::Usage("input file is needed".into()));
+    };
+    let path = std::path::Path::new(input);
+    let doc = match args.command {
+        Command::Deref => {
+            let doc = bundle::bundle(path)
+                .map_err(|err| Error::Bundle(input.clone(), err.to_string()))?;
+            let options = deref::Options {
+                recursion_limit: args.recursion_limit,
+            };
+            deref::deref_description(&doc, &options)
+                .map_err(|err| Error::Deref(input.clone(), err.to_string()))?
+                .0
+        }
+        _ => {
+            bundle::bundle_description(path)
+                .map_err(|err| Error::Bundle(input.clone(), err.to_string()))?
+                .0
+        }
+    };
+    let format = match (args.format, &args.output) {
+        (Format::Json, _) => load::Format::Json,
+        (Format::Human, Some(output)) => {
+            load::Format::from_path(std::path::Path::new(output)).unwrap_or(load::Format::Yaml)
+        }
+        (Format::Human, None) => load::Format::Yaml,
+    };
+    let contents =
+        transform::serialize(&doc, format).map_err(|err| Error::Serialize(err.to_string()))?;
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, contents).map_err(|err| Error::Io(output.clone(), err))
+        }
+        None => {
+            print!("{contents}");
+            Ok(())
+        }
+    }
+}
+
+// Description from input file or standard input
+fn load(args: &Args) -> Result<(Description, Spans), Error> {
+    let name = args.input.as_deref().unwrap_or("<stdin>");
+    match &args.input {
+        Some(input) => load::from_path_spanned(std::path::Path::new(input)),
+        None => {
+            let mut contents = String::new();
+            std::io::stdin()
+                .read_to_string(&mut contents)
+                .map_err(load::Error::Io)
+                .and_then(|_| load::from_str_spanned(&contents, None, Some(name)))
+        }
+    }
+    .map_err(|err| Error::Load(name.into(), err))
+}
+
+fn compile<'a>(d: &'a Description, spans: &Spans) -> Result<compile::Compiled<'a>, Error> {
+    compile::compile(d)
+        .map_err(|err| Error::Compile(locate(spans, &err.pointer()), format!("{err:?}")))
+}
+
+// Source location of the node as file:line:column
+fn locate(spans: &Spans, pointer: &str) -> String {
+    match spans.find(pointer) {
+        Some(span) => span.to_string(),
+        None => file_name(spans),
+    }
+}
+
+fn file_name(spans: &Spans) -> String {
+    spans.file.clone().unwrap_or_default()
+}
+
+// Operations selected by operationId. All operations are selected if
+// none is specified.
+fn select<'o, 'a>(
+    args: &Args,
+    operations: &'o [Operation<'a>],
+) -> Result<Vec<&'o Operation<'a>>, Error> {
+    if args.operations.is_empty() {
+        return Ok(operations.iter().collect());
+    }
+    args.operations
+        .iter()
+        .map(|id| {
+            operations
+                .iter()
+                .find(|op| op.operation_id.is_some_and(|v| v.as_str() == id))
+                .ok_or_else(|| Error::OperationNotFound(id.clone()))
+        })
+        .collect()
+}
+
+fn describe(op: &Operation) -> String {
+    let method = op.op_type.as_str().to_uppercase();
+    match op.operation_id {
+        Some(id) => format!("{method} {} ({})", op.location, id.as_str()),
+        None => format!("{method} {}", op.location),
+    }
+}
+
+fn kind(m: &model::Model) -> &'static str {
+    match m {
+        model::Model::Object(_) => "object",
+        model::Model::Enum(_) => "enum",
+        model::Model::RustType(..) => "rust-type",
+        model::Model::Alias(..) => "alias",
+        model::Model::OneOf(_) => "one-of",
+        model::Model::AnyOf(_) => "any-of",
+        model::Model::AllOf(_) => "all-of",
+    }
+}
+
+fn print_json(v: &serde_json::Value) -> Result<(), Error> {
+    let contents =
+        serde_json::to_string_pretty(v).map_err(|err| Error::Serialize(err.to_string()))?;
+    println!("{contents}");
+    Ok(())
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Usage(err) => err.fmt(f),
+            Self::Io(name, err) => write!(f, "{name}: {err}"),
+            Self::Load(name, load::Error::Parse(err)) if err.location.is_some() => {
+                write!(f, "{name}:{err}")
+            }
+            Self::Load(name, err) => write!(f, "{name}: {err}"),
+            Self::Compile(name, err) => write!(f, "{name}: compilation error: {err}"),
+            Self::Model(name, err) => write!(f, "{name}: model error: {err}"),
+            Self::Bundle(name, err) | Self::Deref(name, err) => write!(f, "{name}: {err}"),
+            Self::Serialize(err) => err.fmt(f),
+            Self::OperationNotFound(id) => write!(f, "operation not found: {id}"),
+            Self::Invalid(n) => write!(f, "{n} problems found"),
+        }
+    }
+}
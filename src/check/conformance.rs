@@ -0,0 +1,817 @@
+// SPDX-License-Identifier: MIT
+//
+// Conformance of HTTP requests and responses to operations.
+//
+// Request is matched to the operation by method and templated path
+// (concrete segments take precedence over parameters). Parameters are
+// decoded according to their style and validated against their
+// schemas as well as JSON bodies. Responses are checked against the
+// response of the status code (exact code, then NXX pattern, then
+// default response).
+//
+
+use crate::compile::data_type::CompiledType;
+use crate::compile::data_type::DataType;
+use crate::compile::data_type::NormalCompiledType;
+use crate::compile::data_type::NullableCompiledType;
+use crate::compile::data_type::TypeOrSchemaRef;
+use crate::compile::operation::parameter::Parameter as CompiledParameter;
+use crate::compile::operation::request_body::RequestBodyOrReference;
+use crate::compile::operation::response_body::ResponseBodyOrReference;
+use crate::compile::operation::Operation;
+use crate::compile::router;
+use crate::compile::router::Router;
+use crate::compile::schema_compiler;
+use crate::compile::stack::Stack;
+use crate::compile::Compiled;
+use crate::compile::Schemas;
+use crate::schema::components::Components;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::parameter::ContentSchema;
+use crate::schema::parameter::Parameter;
+use crate::schema::parameter::SerializeStyle;
+use crate::schema::path_item::OperationType;
+use crate::schema::Description;
+use crate::transform::pointer;
+use crate::validate;
+use crate::validate::Validator;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    // Prefix of all paths (e.g. /v1) that is not a part of templates
+    pub base_path: String,
+}
+
+pub struct Request<'r> {
+    pub method: &'r str,
+    // Absolute URL or path with query
+    pub url: &'r str,
+    pub headers: &'r [(&'r str, &'r str)],
+    pub body: Option<&'r [u8]>,
+}
+
+pub struct Response<'r> {
+    pub status: u16,
+    pub headers: &'r [(&'r str, &'r str)],
+    pub body: Option<&'r [u8]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Place {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    // Method and path
+    OperationNotFound(String, String),
+    ParameterMissing(Place, String),
+    // Parameter with content is not a valid JSON
+    ParameterFormat(Place, String, String),
+    Parameter(Place, String, validate::Error),
+    // Schema of the parameter can't be compiled
+    ParameterSchema(Place, String, String),
+    BodyMissing,
+    ContentTypeMissing,
+    // Content type is not described
+    ContentType(String),
+    BodyFormat(String),
+    Body(validate::Error),
+    // Response of the status code is not described
+    Status(u16),
+}
+
+pub struct Checker<'a> {
+    components: Option<&'a Components>,
+    compiled: &'a Compiled<'a>,
+    router: Router<'a>,
+    options: Options,
+}
+
+// Operation of the request and violations of its description
+#[derive(Debug)]
+pub struct Checked<'a> {
+    pub operation: &'a Operation<'a>,
+    pub errors: Vec<Error>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(
+        d: &'a Description,
+        compiled: &'a Compiled<'a>,
+        options: Options,
+    ) -> Result<Self, router::Error<'a>> {
+        Ok(Self {
+            components: d.components.as_ref(),
+            compiled,
+            router: Router::new(&compiled.operations)?,
+            options,
+        })
+    }
+
+    // Find operation by method and path. Paths matching the request
+    // are tried from the most specific one, e.g. PUT /pets/mine is
+    // found at /pets/{id} if /pets/mine has no PUT operation.
+    pub fn find<'p>(
+        &self,
+        method: &str,
+        path: &'p str,
+    ) -> Option<(&'a Operation<'a>, Vec<router::Param<'a, 'p>>)> {
+        let op_type = OperationType::from_method(&method.to_ascii_lowercase())?;
+        let path = path.strip_prefix(self.options.base_path.as_str())?;
+        self.router
+            .find_all(path)
+            .into_iter()
+            .find_map(|found| Some((found.route.operation(op_type)?, found.params)))
+    }
+
+    pub fn check_request(&self, req: &Request) -> Result<Checked<'a>, Error> {
+        let (path, query) = split_url(req.url);
+        let (op, path_params) = self
+            .find(req.method, path)
+            .ok_or_else(|| Error::OperationNotFound(req.method.into(), path.into()))?;
+        let query = parse_query(query);
+        let cookies = req
+            .headers
+            .iter()
+            .filter(|(name, _)| name.eq_ignore_ascii_case("cookie"))
+            .flat_map(|(_, v)| v.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .collect::<Vec<_>>();
+        let mut errors = vec![];
+
+        let places = [
+            (Place::Path, op.path_params.values().collect::<Vec<_>>()),
+            (Place::Query, op.query_params.iter().collect()),
+            (Place::Header, op.header_params.iter().collect()),
+            (Place::Cookie, op.cookie_params.iter().collect()),
+        ];
+        for (place, params) in places {
+            for CompiledParameter {
+                schema_param: p, ..
+            } in params
+            {
+                let name = p.name.as_str();
+                let raw = match place {
+                    Place::Path => path_params
+                        .iter()
+                        .find(|param| param.name == name)
+                        .map(|param| Raw::Single(param.raw)),
+                    Place::Query => query_raw(p, &query),
+                    Place::Header => req
+                        .headers
+                        .iter()
+                        .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                        .map(|(_, v)| Raw::Single(v)),
+                    Place::Cookie => cookies
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, v)| Raw::Single(v)),
+                };
+                match raw {
+                    Some(raw) => errors.extend(self.parameter(place, p, raw)),
+                    None if p.is_required() => {
+                        errors.push(Error::ParameterMissing(place, name.into()))
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let body = match &op.request_body_or_ref {
+            None => None,
+            Some(RequestBodyOrReference::Body(body)) => Some(body),
+            Some(RequestBodyOrReference::Reference(sref)) => self.compiled.request_bodies.get(sref),
+        };
+        if let Some(body) = body {
+            match req.body.filter(|b| !b.is_empty()) {
+                None if body.required => errors.push(Error::BodyMissing),
+                None => {}
+                Some(data) => errors.extend(content(
+                    &self.compiled.schemas,
+                    req.headers,
+                    &body.content_types,
+                    body.json_type_or_ref.as_ref(),
+                    data,
+                )),
+            }
+        }
+        Ok(Checked {
+            operation: op,
+            errors,
+        })
+    }
+
+    pub fn check_response(&self, op: &Operation<'a>, resp: &Response) -> Vec<Error> {
+        let responses = &op.request_responses;
+        let found = |specific: bool| {
+            responses
+                .codes
+                .iter()
+                .find(|(c, _)| {
+                    matches!(c, HttpStatusCode::Specific(_)) == specific && c.matches(resp.status)
+                })
+                .map(|(_, resp)| resp)
+        };
+        let Some(found) = found(true)
+            .or_else(|| found(false))
+            .or(responses.default.as_ref())
+        else {
+            return vec![Error::Status(resp.status)];
+        };
+        let body = match found {
+            ResponseBodyOrReference::Body(body) => Some(body),
+            ResponseBodyOrReference::Reference(sref) => self.compiled.response_bodies.get(sref),
+        };
+        match (body, resp.body.filter(|b| !b.is_empty())) {
+            (Some(body), Some(data)) => content(
+                &self.compiled.schemas,
+                resp.headers,
+                &body.content_types,
+                body.json_type_or_ref.as_ref(),
+                data,
+            ),
+            _ => vec![],
+        }
+    }
+
+    fn parameter(&self, place: Place, p: &Parameter, raw: Raw) -> Vec<Error> {
+        let name = || p.name.as_str().to_string();
+        let (schema, value) = match &p.content_schema {
+            ContentSchema::SchemaAndStyle(s) => (&s.schema, None),
+            // Value is serialized according to the media type. Only
+            // JSON values are checked.
+            ContentSchema::Content(c) => match c.content.get("application/json") {
+                Some(mt) => {
+                    let Some(schema) = mt.schema.as_ref() else {
+                        return vec![];
+                    };
+                    let value = match serde_json::from_str(&raw.first()) {
+                        Ok(value) => value,
+                        Err(err) => {
+                            return vec![Error::ParameterFormat(place, name(), err.to_string())]
+                        }
+                    };
+                    (schema, Some(value))
+                }
+                None => return vec![],
+            },
+            ContentSchema::None(_) => return vec![],
+        };
+        let root = Stack::default();
+        let compiled = match schema_compiler::compile(schema, self.components, &root, 0) {
+            Ok(compiled) => compiled,
+            Err(err) => return vec![Error::ParameterSchema(place, name(), format!("{err:?}"))],
+        };
+        let value = value.unwrap_or_else(|| {
+            let shape = Shape::new(&compiled.schemas, &compiled.type_or_ref);
+            let style = p.style().unwrap_or(SerializeStyle::Simple);
+            decode(&shape, style, p.explode(), p.name.as_str(), raw)
+        });
+        Validator::new(&compiled.schemas)
+            .validate(&value, &compiled.type_or_ref, "")
+            .into_iter()
+            .map(|err| Error::Parameter(place, name(), err))
+            .collect()
+    }
+}
+
+// Check body against described media types. Only JSON bodies are
+// validated against schema.
+fn content(
+    schemas: &Schemas,
+    headers: &[(&str, &str)],
+    content_types: &[&str],
+    json: Option<&TypeOrSchemaRef>,
+    data: &[u8],
+) -> Vec<Error> {
+    let Some(content_type) = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, v)| media_type(v))
+    else {
+        return vec![Error::ContentTypeMissing];
+    };
+    if !content_types
+        .iter()
+        .any(|range| media_range_matches(range, &content_type))
+    {
+        return vec![Error::ContentType(content_type)];
+    }
+    let Some(t) = json.filter(|_| content_type == "application/json") else {
+        return vec![];
+    };
+    match serde_json::from_slice(data) {
+        Ok(value) => Validator::new(schemas)
+            .validate(&value, t, "")
+            .into_iter()
+            .map(Error::Body)
+            .collect(),
+        Err(err) => vec![Error::BodyFormat(err.to_string())],
+    }
+}
+
+// Media type without parameters in lower case
+fn media_type(header: &str) -> String {
+    header
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn media_range_matches(range: &str, media_type: &str) -> bool {
+    let range = self::media_type(range);
+    match range.strip_suffix("/*") {
+        Some("*") => true,
+        Some(prefix) => media_type
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/')),
+        None => range == media_type,
+    }
+}
+
+fn split_url(url: &str) -> (&str, &str) {
+    // Strip scheme and authority of absolute URL
+    let url = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |i| &rest[i..]),
+        None => url,
+    };
+    let url = url.split_once('#').map_or(url, |(url, _)| url);
+    url.split_once('?').unwrap_or((url, ""))
+}
+
+// Pairs of the query string (not decoded)
+fn parse_query(query: &str) -> Vec<(&str, &str)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+        .collect()
+}
+
+fn form_decode(s: &str) -> String {
+    pointer::percent_decode(&s.replace('+', " "))
+}
+
+fn query_raw<'q>(p: &Parameter, query: &[(&'q str, &'q str)]) -> Option<Raw<'q>> {
+    let name = p.name.as_str();
+    match p.style() {
+        Some(SerializeStyle::DeepObject) => {
+            let pairs: Vec<_> = query
+                .iter()
+                .filter_map(|(k, v)| {
+                    let k = form_decode(k);
+                    let property = k.strip_prefix(name)?.strip_prefix('[')?.strip_suffix(']')?;
+                    Some((property.to_string(), form_decode(v)))
+                })
+                .collect();
+            (!pairs.is_empty()).then_some(Raw::Pairs(pairs))
+        }
+        // Properties of exploded object are parameters itself
+        Some(SerializeStyle::Form) if p.explode() && is_object(p) => Some(Raw::Pairs(
+            query
+                .iter()
+                .map(|(k, v)| (form_decode(k), form_decode(v)))
+                .collect(),
+        )),
+        _ => {
+            let values: Vec<_> = query
+                .iter()
+                .filter(|(k, _)| form_decode(k) == name)
+                .map(|(_, v)| *v)
+                .collect();
+            (!values.is_empty()).then_some(Raw::Multi(values))
+        }
+    }
+}
+
+fn is_object(p: &Parameter) -> bool {
+    use crate::schema::data_type::DataType as SchemaDataType;
+    use crate::schema::data_type::MaybeNullableTypeSchema;
+    use crate::schema::data_type::TypeSchema;
+
+    match &p.content_schema {
+        ContentSchema::SchemaAndStyle(s) => match &s.schema {
+            SchemaDataType::ActualType(at) => matches!(
+                at.type_schema,
+                MaybeNullableTypeSchema::Object(_)
+                    | MaybeNullableTypeSchema::Normal(TypeSchema::Object(_))
+            ),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// Raw (percent-encoded) value of the parameter
+enum Raw<'r> {
+    Single(&'r str),
+    // Values of the same query parameter
+    Multi(Vec<&'r str>),
+    // Decoded properties of the object (deepObject or exploded form)
+    Pairs(Vec<(String, String)>),
+}
+
+impl Raw<'_> {
+    fn first(&self) -> String {
+        match self {
+            Self::Single(v) => pointer::percent_decode(v),
+            Self::Multi(vs) => vs.first().map(|v| form_decode(v)).unwrap_or_default(),
+            Self::Pairs(_) => String::new(),
+        }
+    }
+}
+
+// Structure of the parameter value needed to decode it
+enum Shape {
+    Primitive(Primitive),
+    Array(Primitive),
+    Object(Vec<(String, Primitive)>),
+}
+
+#[derive(Clone, Copy)]
+enum Primitive {
+    Boolean,
+    Integer,
+    Number,
+    String,
+}
+
+impl Shape {
+    fn new(schemas: &Schemas, t: &TypeOrSchemaRef) -> Self {
+        match compiled_type(schemas, t) {
+            Some(CompiledType::Normal(NormalCompiledType::Array(arr)))
+            | Some(CompiledType::Nullable(NullableCompiledType::Array(arr))) => {
+                Self::Array(Primitive::new(schemas, &arr.items))
+            }
+            Some(CompiledType::Normal(NormalCompiledType::Object(obj)))
+            | Some(CompiledType::Nullable(NullableCompiledType::Object(obj))) => Self::Object(
+                obj.properties
+                    .iter()
+                    .map(|(name, t)| (name.as_str().to_string(), Primitive::new(schemas, t)))
+                    .collect(),
+            ),
+            _ => Self::Primitive(Primitive::new(schemas, t)),
+        }
+    }
+}
+
+impl Primitive {
+    fn new(schemas: &Schemas, t: &TypeOrSchemaRef) -> Self {
+        match compiled_type(schemas, t) {
+            Some(CompiledType::Normal(NormalCompiledType::Boolean(_)))
+            | Some(CompiledType::Nullable(NullableCompiledType::Boolean(_))) => Self::Boolean,
+            Some(CompiledType::Normal(NormalCompiledType::Integer(_)))
+            | Some(CompiledType::Nullable(NullableCompiledType::Integer(_))) => Self::Integer,
+            Some(CompiledType::Normal(NormalCompiledType::Number(_)))
+            | Some(CompiledType::Nullable(NullableCompiledType::Number(_))) => Self::Number,
+            _ => Self::String,
+        }
+    }
+
+    // Values those can't be converted are kept as strings so the
+    // validator reports them
+    fn value(self, s: String) -> Value {
+        let converted = match self {
+            Self::Boolean => s.parse::<bool>().ok().map(Value::from),
+            Self::Integer => s.parse::<i64>().ok().map(Value::from),
+            Self::Number => s
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            Self::String => None,
+        };
+        converted.unwrap_or(Value::String(s))
+    }
+}
+
+fn compiled_type<'s, 'a>(
+    schemas: &'s Schemas<'a>,
+    t: &'s TypeOrSchemaRef<'a>,
+) -> Option<&'s CompiledType<'a>> {
+    let dt = match t {
+        TypeOrSchemaRef::DataType(dt) => dt,
+        TypeOrSchemaRef::Reference(name) => schemas.get(name)?,
+    };
+    match dt {
+        DataType::ActualType(t) => Some(&t.compiled_type),
+        DataType::OneOf(_) | DataType::AllOf(_) | DataType::AnyOf(_) => None,
+    }
+}
+
+// Decode parameter value serialized with style
+fn decode(shape: &Shape, style: SerializeStyle, explode: bool, name: &str, raw: Raw) -> Value {
+    let s = match raw {
+        Raw::Single(s) => s.to_string(),
+        Raw::Multi(values) => match shape {
+            Shape::Array(item) if explode || values.len() > 1 => {
+                return values.iter().map(|v| item.value(form_decode(v))).collect();
+            }
+            _ => values.first().map(|v| v.to_string()).unwrap_or_default(),
+        },
+        Raw::Pairs(pairs) => {
+            let Shape::Object(properties) = shape else {
+                return Value::Null;
+            };
+            return object(properties, pairs);
+        }
+    };
+    // Strip prefixes of path styles
+    let (s, separator) = match style {
+        SerializeStyle::Label => {
+            let s = s.strip_prefix('.').unwrap_or(&s).to_string();
+            (s, if explode { '.' } else { ',' })
+        }
+        SerializeStyle::Matrix => {
+            let s = s.strip_prefix(';').unwrap_or(&s).to_string();
+            match shape {
+                Shape::Object(properties) if explode => {
+                    return object(properties, pairs(&s, ';', '='));
+                }
+                Shape::Array(item) if explode => {
+                    let prefix = format!("{name}=");
+                    return s
+                        .split(';')
+                        .map(|v| {
+                            item.value(pointer::percent_decode(
+                                v.strip_prefix(&prefix).unwrap_or(v),
+                            ))
+                        })
+                        .collect();
+                }
+                _ => {}
+            }
+            let s = s
+                .strip_prefix(name)
+                .and_then(|s| s.strip_prefix('='))
+                .unwrap_or(&s)
+                .to_string();
+            (s, ',')
+        }
+        SerializeStyle::SpaceDelimited => (s.replace("%20", " "), ' '),
+        SerializeStyle::PipeDelimited => (s.replace("%7C", "|").replace("%7c", "|"), '|'),
+        SerializeStyle::Form | SerializeStyle::Simple | SerializeStyle::DeepObject => (s, ','),
+    };
+    match shape {
+        Shape::Primitive(p) => p.value(pointer::percent_decode(&s)),
+        Shape::Array(item) => s
+            .split(separator)
+            .map(|v| item.value(pointer::percent_decode(v)))
+            .collect(),
+        Shape::Object(properties) if explode => object(properties, pairs(&s, separator, '=')),
+        Shape::Object(properties) => {
+            let values: Vec<_> = s.split(separator).collect();
+            let pairs = values
+                .chunks(2)
+                .map(|kv| {
+                    let value = kv.get(1).copied().unwrap_or_default();
+                    (
+                        pointer::percent_decode(kv[0]),
+                        pointer::percent_decode(value),
+                    )
+                })
+                .collect();
+            object(properties, pairs)
+        }
+    }
+}
+
+fn pairs(s: &str, separator: char, assign: char) -> Vec<(String, String)> {
+    s.split(separator)
+        .map(|kv| {
+            let (k, v) = kv.split_once(assign).unwrap_or((kv, ""));
+            (pointer::percent_decode(k), pointer::percent_decode(v))
+        })
+        .collect()
+}
+
+// Object of known properties. Unknown ones are kept as strings.
+fn object(properties: &[(String, Primitive)], pairs: Vec<(String, String)>) -> Value {
+    Value::Object(
+        pairs
+            .into_iter()
+            .map(|(k, v)| {
+                let v = match properties.iter().find(|(name, _)| *name == k) {
+                    Some((_, p)) => p.value(v),
+                    None => Value::String(v),
+                };
+                (k, v)
+            })
+            .collect(),
+    )
+}
+
+impl std::fmt::Display for Place {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path => "path".fmt(f),
+            Self::Query => "query".fmt(f),
+            Self::Header => "header".fmt(f),
+            Self::Cookie => "cookie".fmt(f),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OperationNotFound(method, path) => {
+                write!(f, "operation not found: {method} {path}")
+            }
+            Self::ParameterMissing(place, name) => {
+                write!(f, "required {place} parameter is missing: {name}")
+            }
+            Self::ParameterFormat(place, name, err) => {
+                write!(f, "{place} parameter {name} is not valid: {err}")
+            }
+            Self::Parameter(place, name, err) => write!(f, "{place} parameter {name}: {err}"),
+            Self::ParameterSchema(place, name, err) => {
+                write!(
+                    f,
+                    "schema of {place} parameter {name} can't be compiled: {err}"
+                )
+            }
+            Self::BodyMissing => write!(f, "required body is missing"),
+            Self::ContentTypeMissing => write!(f, "content type of the body is missing"),
+            Self::ContentType(content_type) => {
+                write!(f, "content type is not described: {content_type}")
+            }
+            Self::BodyFormat(err) => write!(f, "body is not valid JSON: {err}"),
+            Self::Body(err) => write!(f, "body: {err}"),
+            Self::Status(status) => write!(f, "response is not described: {status}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile::compile;
+
+    #[test]
+    fn requests_and_responses() {
+        let spec = include_str!("../../test/test-vectors/conformance.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let options = Options {
+            base_path: "/v1".into(),
+        };
+        let checker = Checker::new(&description, &compiled, options).unwrap();
+        let check = |method, url, headers: &[(&str, &str)], body: Option<&str>| {
+            let req = Request {
+                method,
+                url,
+                headers,
+                body: body.map(str::as_bytes),
+            };
+            checker.check_request(&req).map(|checked| {
+                let errors: Vec<_> = checked.errors.iter().map(|e| e.to_string()).collect();
+                (checked.operation.location.path().unwrap().as_str(), errors)
+            })
+        };
+
+        // Concrete segments take precedence
+        assert_eq!(
+            check("GET", "https://example.com/v1/files/latest.txt", &[], None),
+            Ok(("/files/latest.txt", vec![]))
+        );
+        assert_eq!(
+            check("GET", "/v1/files/report.2024.json", &[], None),
+            Ok(("/files/{name}.{ext}", vec![]))
+        );
+        assert_eq!(
+            check("get", "/v1/files/report.pdf", &[], None),
+            Ok((
+                "/files/{name}.{ext}",
+                vec!["path parameter ext: value is not in enum: pdf (schema /enum)".into()]
+            ))
+        );
+        assert_eq!(
+            check("DELETE", "/v1/files/latest.txt", &[], None),
+            Err(Error::OperationNotFound(
+                "DELETE".into(),
+                "/v1/files/latest.txt".into()
+            ))
+        );
+
+        let headers = [
+            ("x-request-id", "42"),
+            ("Content-Type", "application/json; charset=utf-8"),
+            ("Cookie", "theme=dark; session=abcdef"),
+        ];
+        assert_eq!(
+            check(
+                "PUT",
+                "/v1/items/7?tags=a&tags=b&filter[limit]=10",
+                &headers,
+                Some(r#"{"name": "item", "count": 3}"#)
+            ),
+            Ok(("/items/{id}", vec![]))
+        );
+        assert_eq!(
+            check(
+                "PUT",
+                "/v1/items/0?tags=a&tags=b&tags=c&filter%5Blimit%5D=1000",
+                &[("Content-Type", "application/json"), ("Cookie", "session=ab")],
+                Some(r#"{"count": "3"}"#)
+            ),
+            Ok((
+                "/items/{id}",
+                vec![
+                    "path parameter id: value is less than minimum 1 (schema /minimum)".into(),
+                    "query parameter tags: array has more than 2 items (schema /maxItems)".into(),
+                    "query parameter filter: /limit: value is greater than maximum 100 (schema /properties/limit/maximum)".into(),
+                    "required header parameter is missing: X-Request-Id".into(),
+                    "cookie parameter session: string is shorter than 4 (schema /minLength)".into(),
+                    "body: required property is missing: name (schema /components/schemas/Item/required)".into(),
+                    "body: /count: expected integer (schema /components/schemas/Item/properties/count/type)".into(),
+                ]
+            ))
+        );
+        assert_eq!(
+            check("PUT", "/v1/items/x", &[("X-Request-Id", "1")], None),
+            Ok((
+                "/items/{id}",
+                vec![
+                    "path parameter id: expected integer (schema /type)".into(),
+                    "required body is missing".into(),
+                ]
+            ))
+        );
+
+        // Less specific path is used if the most specific one has no
+        // operation of the method
+        assert_eq!(
+            check("GET", "/v1/items/mine", &[], None),
+            Ok(("/items/mine", vec![]))
+        );
+        assert_eq!(
+            check("PUT", "/v1/items/mine", &[("X-Request-Id", "1")], None),
+            Ok((
+                "/items/{id}",
+                vec![
+                    "path parameter id: expected integer (schema /type)".into(),
+                    "required body is missing".into(),
+                ]
+            ))
+        );
+
+        let op = checker.find("PUT", "/v1/items/1").unwrap().0;
+        let respond = |status, content_type, body: &str| {
+            let headers = [("Content-Type", content_type)];
+            let resp = Response {
+                status,
+                headers: &headers,
+                body: Some(body.as_bytes()),
+            };
+            let errors = checker.check_response(op, &resp);
+            errors.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+        };
+        assert!(respond(200, "application/json", r#"{"name": "item"}"#).is_empty());
+        assert_eq!(
+            respond(200, "application/json", "{"),
+            ["body is not valid JSON: EOF while parsing an object at line 1 column 1"]
+        );
+        assert_eq!(
+            respond(200, "application/xml", "<item/>"),
+            ["content type is not described: application/xml"]
+        );
+        assert!(respond(404, "text/plain", "not found").is_empty());
+        assert_eq!(
+            respond(500, "text/plain", "oops"),
+            ["response is not described: 500"]
+        );
+    }
+
+    // Errors of path parameters are in order of the template
+    #[test]
+    fn path_params_order() {
+        let spec = include_str!("../../test/test-vectors/path-params.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let checker = Checker::new(&description, &compiled, Options::default()).unwrap();
+        let req = Request {
+            method: "GET",
+            url: "/mars/a/b/c",
+            headers: &[],
+            body: None,
+        };
+        let checked = checker.check_request(&req).unwrap();
+        let names: Vec<_> = checked
+            .errors
+            .iter()
+            .map(|err| match err {
+                Error::Parameter(Place::Path, name, _) => name.as_str(),
+                _ => panic!("{err} is not path parameter error"),
+            })
+            .collect();
+        assert_eq!(names, ["region", "zone", "rack", "slot"]);
+    }
+}
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: MIT
+//
+// Check that examples match their schemas.
+//
+// Examples of media types, parameters, headers and schemas (and
+// defaults of schemas) are validated against compiled schema.
+// Location of the mismatch is JSON pointer to the example inside
+// description.
+//
+
+use crate::compile::schema_compiler;
+use crate::compile::stack::Stack;
+use crate::schema::components::Components;
+use crate::schema::data_type::object::AdditionalProperties;
+use crate::schema::data_type::DataType as SchemaDataType;
+use crate::schema::data_type::MaybeNullableTypeSchema;
+use crate::schema::data_type::NullableTypeSchema;
+use crate::schema::data_type::TypeSchema;
+use crate::schema::example::ExampleOrReference;
+use crate::schema::example::Examples;
+use crate::schema::header::ContentSchema as HeaderContentSchema;
+use crate::schema::header::Header;
+use crate::schema::header::HeaderOrReference;
+use crate::schema::media_type::MediaType;
+use crate::schema::parameter::ContentSchema as ParameterContentSchema;
+use crate::schema::parameter::Parameter;
+use crate::schema::parameter::ParameterOrReference;
+use crate::schema::path_item::PathItem;
+use crate::schema::request_body::RequestBody;
+use crate::schema::request_body::RequestBodyOrReference;
+use crate::schema::response::Response;
+use crate::schema::responses::ResponseOrReference;
+use crate::schema::Description;
+use crate::transform::pointer;
+use crate::validate::Validator;
+use serde_yaml::Value;
+
+const MAX_DEPTH: u32 = 1024;
+
+#[derive(Debug)]
+pub struct Mismatch {
+    // JSON pointer to the example inside description
+    pub location: String,
+    pub error: Error,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    // JSON pointer inside example value and the reason
+    Value(String, String),
+    WrongReference(String),
+    SchemaCompilation(String),
+}
+
+pub fn check(d: &Description) -> Vec<Mismatch> {
+    let mut checker = Checker {
+        components: d.components.as_ref(),
+        mismatches: vec![],
+    };
+    for (path, item) in d.paths.iter().flatten() {
+        checker.path_item(&child("/paths", &path.to_string()), item);
+    }
+    for (name, item) in d.webhooks.iter().flatten() {
+        checker.path_item(&child("/webhooks", name.as_str()), item);
+    }
+    if let Some(components) = &d.components {
+        checker.components(components);
+    }
+    checker.mismatches
+}
+
+fn child(at: &str, token: &str) -> String {
+    format!("{at}/{}", pointer::escape(token))
+}
+
+struct Checker<'a> {
+    components: Option<&'a Components>,
+    mismatches: Vec<Mismatch>,
+}
+
+impl<'a> Checker<'a> {
+    fn components(&mut self, c: &'a Components) {
+        for (name, schema) in c.schemas.iter().flatten() {
+            self.schema(&child("/components/schemas", name.as_str()), schema, 0);
+        }
+        for (name, p) in c.parameters.iter().flatten() {
+            if let ParameterOrReference::Parameter(p) = p {
+                self.parameter(&child("/components/parameters", name.as_str()), p);
+            }
+        }
+        for (name, body) in c.request_bodies.iter().flatten() {
+            if let RequestBodyOrReference::RequestBody(body) = body {
+                self.request_body(&child("/components/requestBodies", name.as_str()), body);
+            }
+        }
+        for (name, resp) in c.responses.iter().flatten() {
+            if let ResponseOrReference::Response(resp) = resp {
+                self.response(&child("/components/responses", name.as_str()), resp);
+            }
+        }
+        for (name, header) in c.headers.iter().flatten() {
+            if let HeaderOrReference::Header(header) = header {
+                self.header(&child("/components/headers", name), header);
+            }
+        }
+    }
+
+    fn path_item(&mut self, at: &str, item: &'a PathItem) {
+        self.parameters(at, &item.parameters);
+        for (op_type, op) in item.operations_iter() {
+            let at = child(at, op_type.as_str());
+            self.parameters(&at, &op.parameters);
+            if let Some(RequestBodyOrReference::RequestBody(body)) = &op.request_body {
+                self.request_body(&child(&at, "requestBody"), body);
+            }
+            if let Some(responses) = &op.responses {
+                let at = child(&at, "responses");
+                if let Some(ResponseOrReference::Response(resp)) = &responses.default {
+                    self.response(&child(&at, "default"), resp);
+                }
+                for (code, resp) in responses.codes.iter() {
+                    if let ResponseOrReference::Response(resp) = resp {
+                        self.response(&child(&at, &code.to_string()), resp);
+                    }
+                }
+            }
+        }
+    }
+
+    fn parameters(&mut self, at: &str, ps: &'a Option<Vec<ParameterOrReference>>) {
+        for (i, p) in ps.iter().flatten().enumerate() {
+            if let ParameterOrReference::Parameter(p) = p {
+                self.parameter(&child(&child(at, "parameters"), &i.to_string()), p);
+            }
+        }
+    }
+
+    fn parameter(&mut self, at: &str, p: &'a Parameter) {
+        match &p.content_schema {
+            ParameterContentSchema::SchemaAndStyle(s) => {
+                self.examples(at, &s.schema, &p.example, &p.examples);
+                self.schema(&child(at, "schema"), &s.schema, 0);
+            }
+            ParameterContentSchema::Content(c) => self.content(at, &c.content),
+            ParameterContentSchema::None(_) => {}
+        }
+    }
+
+    fn header(&mut self, at: &str, h: &'a Header) {
+        match &h.content_schema {
+            HeaderContentSchema::SchemaAndStyle(s) => {
+                self.examples(at, &s.schema, &h.example, &h.examples);
+                self.schema(&child(at, "schema"), &s.schema, 0);
+            }
+            HeaderContentSchema::Content(c) => self.content(at, &c.content),
+        }
+    }
+
+    fn request_body(&mut self, at: &str, body: &'a RequestBody) {
+        self.content(at, &body.content);
+    }
+
+    fn response(&mut self, at: &str, resp: &'a Response) {
+        if let Some(content) = &resp.content {
+            self.content(at, content);
+        }
+        for (name, header) in resp.headers.iter().flatten() {
+            if let HeaderOrReference::Header(header) = header {
+                self.header(&child(&child(at, "headers"), name.as_str()), header);
+            }
+        }
+    }
+
+    fn content(&mut self, at: &str, content: &'a indexmap::IndexMap<String, MediaType>) {
+        for (mime, mt) in content.iter() {
+            let at = child(&child(at, "content"), mime);
+            if let Some(schema) = &mt.schema {
+                self.examples(&at, schema, &mt.example, &mt.examples);
+                self.schema(&child(&at, "schema"), schema, 0);
+            }
+        }
+    }
+
+    // Examples and defaults inside schema and its subschemas.
+    // Referenced schemas are checked as components.
+    fn schema(&mut self, at: &str, dt: &'a SchemaDataType, depth: u32) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+        let mut subschemas = |name: &str, dts: &'a Vec<SchemaDataType>| {
+            for (i, dt) in dts.iter().enumerate() {
+                self.schema(&child(&child(at, name), &i.to_string()), dt, depth + 1);
+            }
+        };
+        match dt {
+            SchemaDataType::OneOf(v) => subschemas("oneOf", &v.one_of),
+            SchemaDataType::AllOf(v) => subschemas("allOf", &v.all_of),
+            SchemaDataType::AnyOf(v) => subschemas("anyOf", &v.any_of),
+            SchemaDataType::ActualType(t) => {
+                if let Some(example) = &t.example {
+                    self.value(&child(at, "example"), dt, example);
+                }
+                for (i, example) in t.examples.iter().flatten().enumerate() {
+                    self.value(&child(&child(at, "examples"), &i.to_string()), dt, example);
+                }
+                if let Some(default) = t.default_value() {
+                    self.json_value(&child(at, "default"), dt, &default);
+                }
+                let (obj, arr) = match &t.type_schema {
+                    MaybeNullableTypeSchema::Normal(TypeSchema::Object(obj))
+                    | MaybeNullableTypeSchema::Object(obj) => (Some(obj), None),
+                    MaybeNullableTypeSchema::Normal(TypeSchema::Array(arr))
+                    | MaybeNullableTypeSchema::Array(arr) => (None, Some(arr)),
+                    MaybeNullableTypeSchema::Nullable(v) => match &v.schema {
+                        NullableTypeSchema::Object(obj) => (Some(obj), None),
+                        NullableTypeSchema::Array(arr) => (None, Some(arr)),
+                        _ => (None, None),
+                    },
+                    _ => (None, None),
+                };
+                for (name, dt) in obj.iter().flat_map(|obj| obj.properties.iter().flatten()) {
+                    let at = child(&child(at, "properties"), name.as_str());
+                    self.schema(&at, dt, depth + 1);
+                }
+                if let Some(AdditionalProperties::Schema(dt)) =
+                    obj.and_then(|obj| obj.additional_properties.as_deref())
+                {
+                    self.schema(&child(at, "additionalProperties"), dt, depth + 1);
+                }
+                if let Some(items) = arr.and_then(|arr| arr.items.as_ref()) {
+                    self.schema(&child(at, "items"), items, depth + 1);
+                }
+            }
+            SchemaDataType::Reference(_)
+            | SchemaDataType::Empty(_)
+            | SchemaDataType::UnknownType(_) => {}
+        }
+    }
+
+    fn examples(
+        &mut self,
+        at: &str,
+        schema: &'a SchemaDataType,
+        example: &Option<Value>,
+        examples: &'a Option<Examples>,
+    ) {
+        if let Some(example) = example {
+            self.value(&child(at, "example"), schema, example);
+        }
+        for (name, example) in examples.iter().flatten() {
+            let at = child(&child(at, "examples"), name.as_str());
+            let example = match example {
+                ExampleOrReference::Example(example) => Some(example),
+                ExampleOrReference::Reference(r) => {
+                    let example = r
+                        .sref
+                        .example_sref()
+                        .and_then(|sref| self.components?.find_example(&sref));
+                    if example.is_none() {
+                        self.mismatches.push(Mismatch {
+                            location: at.clone(),
+                            error: Error::WrongReference(r.sref.to_string()),
+                        });
+                    }
+                    example
+                }
+            };
+            // External values are not checked
+            if let Some(value) = example.and_then(|example| example.value.as_ref()) {
+                self.value(&child(&at, "value"), schema, value);
+            }
+        }
+    }
+
+    fn value(&mut self, at: &str, schema: &'a SchemaDataType, value: &Value) {
+        match serde_json::to_value(value) {
+            Ok(value) => self.json_value(at, schema, &value),
+            Err(err) => self.mismatches.push(Mismatch {
+                location: at.into(),
+                error: Error::Value(String::new(), err.to_string()),
+            }),
+        }
+    }
+
+    fn json_value(&mut self, at: &str, schema: &'a SchemaDataType, value: &serde_json::Value) {
+        let root = Stack::default();
+        let compiled = match schema_compiler::compile(schema, self.components, &root, 0) {
+            Ok(compiled) => compiled,
+            Err(err) => {
+                self.mismatches.push(Mismatch {
+                    location: at.into(),
+                    error: Error::SchemaCompilation(format!("{err:?}")),
+                });
+                return;
+            }
+        };
+        // Schema pointer is not reported: location of the example is
+        // enough to find the schema
+        let errors = Validator::new(&compiled.schemas).validate(value, &compiled.type_or_ref, "");
+        self.mismatches
+            .extend(errors.into_iter().map(|err| Mismatch {
+                location: at.into(),
+                error: Error::Value(err.instance, err.kind.to_string()),
+            }));
+    }
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.error)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Value(instance, reason) if instance.is_empty() => reason.fmt(f),
+            Self::Value(instance, reason) => write!(f, "{instance}: {reason}"),
+            Self::WrongReference(r) => write!(f, "example not found: {r}"),
+            Self::SchemaCompilation(err) => write!(f, "schema compilation error: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_examples() {
+        let spec = include_str!("../../test/test-vectors/examples.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let mismatches: Vec<String> = check(&description).iter().map(|m| m.to_string()).collect();
+        assert_eq!(
+            mismatches,
+            [
+                "/paths/~1pets/get/parameters/0/example: expected integer",
+                "/paths/~1pets/get/responses/200/content/application~1json/examples/invalid/value: \
+                 /0: required property is missing: name",
+                "/paths/~1pets/get/responses/200/content/application~1json/examples/missing: \
+                 example not found: #/components/examples/Missing",
+                "/paths/~1pets/post/requestBody/content/application~1json/example: \
+                 /kind: value is not in enum: mouse",
+                "/components/schemas/Pet/properties/kind/default: value is not in enum: mouse",
+                "/components/schemas/Pet/properties/age/example: expected integer",
+                "/components/schemas/Pet/properties/owner/default: /name: expected string",
+            ]
+        );
+    }
+}
@@ -0,0 +1,7 @@
+// SPDX-License-Identifier: MIT
+//
+// Consistency checks of the openapi description
+//
+
+pub mod conformance;
+pub mod example;
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT
+//
+// Code generation from the model
+//
+
+pub mod rust;
+
+// Generated file. Path is relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    pub path: String,
+    pub contents: String,
+}
+
+// Words of the name, e.g. "petId", "pet_id" and "PET-ID" are split
+// into "pet" and "id"
+pub fn words(s: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut prev: Option<char> = None;
+    let chars: Vec<char> = s.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            prev = None;
+            continue;
+        }
+        let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+        let boundary = match prev {
+            Some(p) if c.is_uppercase() => {
+                p.is_lowercase() || p.is_ascii_digit() || (p.is_uppercase() && next_lower)
+            }
+            _ => false,
+        };
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.extend(c.to_lowercase());
+        prev = Some(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
@@ -0,0 +1,512 @@
+// SPDX-License-Identifier: MIT
+//
+// Rust HTTP client generator.
+//
+// Client has one async method per operation. Requests are built from
+// the path template and parameters serialized according to their
+// style and are sent by the transport (trait to be implemented with
+// any HTTP library or by test double). Responses are decoded into
+// per-operation enums with variant per described status (see
+// operations).
+//
+
+use crate::codegen::rust::escape;
+use crate::codegen::rust::field_name;
+use crate::codegen::rust::operations::content_type;
+use crate::codegen::rust::operations::field_names;
+use crate::codegen::rust::operations::response_name;
+use crate::codegen::rust::operations::split_template;
+use crate::codegen::rust::operations::style;
+use crate::codegen::rust::operations::variant_name;
+use crate::codegen::rust::Generator;
+use crate::codegen::rust::HEADER;
+use crate::model::operation::Operation;
+use crate::model::operation::Parameter;
+use crate::model::operation::Place;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::template::Token;
+use std::fmt::Write;
+
+// Types and functions used by generated operations
+const RUNTIME: &str = r#"
+// Request to be sent by transport. URL includes base URL of the
+// client, path and query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+// Sender of requests, e.g. reqwest or hyper client or test double
+pub trait Transport {
+    type Error;
+
+    fn send(
+        &self,
+        request: Request,
+    ) -> impl std::future::Future<Output = Result<Response, Self::Error>> + Send;
+}
+
+#[derive(Debug)]
+pub enum Error<E> {
+    Transport(E),
+    // Parameter or request body can't be serialized
+    Serialize(serde_json::Error),
+    // Response body doesn't match the type of the response
+    Deserialize(u16, serde_json::Error),
+    // Status is not described by the operation
+    UnexpectedStatus(Response),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "transport error: {err}"),
+            Self::Serialize(err) => write!(f, "serialization error: {err}"),
+            Self::Deserialize(status, err) => write!(f, "response {status}: {err}"),
+            Self::UnexpectedStatus(response) => {
+                write!(f, "unexpected response status {}", response.status)
+            }
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for Error<E> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+fn value<E>(v: &impl Serialize) -> Result<Value, Error<E>> {
+    serde_json::to_value(v).map_err(Error::Serialize)
+}
+
+// Value of the parameter with JSON content
+fn json_value<E>(v: &impl Serialize) -> Result<Value, Error<E>> {
+    match value(v)? {
+        Value::Null => Ok(Value::Null),
+        v => Ok(Value::String(v.to_string())),
+    }
+}
+
+// Absent parameters are null or empty arrays (optional arrays default
+// to empty)
+fn is_absent(v: &Value) -> bool {
+    v.is_null() || v.as_array().is_some_and(Vec::is_empty)
+}
+
+fn scalar(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+// Percent-encode all but unreserved characters
+fn encode(s: &str) -> String {
+    let mut result = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                result.push(b as char)
+            }
+            b => result.push_str(&format!("%{b:02X}")),
+        }
+    }
+    result
+}
+
+// Path (simple, label or matrix style) or header (simple style)
+// parameter
+fn simple(name: &str, style: Style, explode: bool, v: &Value, enc: fn(&str) -> String) -> String {
+    let (prefix, separator) = match style {
+        Style::Label => (".".to_string(), if explode { "." } else { "," }),
+        Style::Matrix => (format!(";{name}="), if explode { ";" } else { "," }),
+        _ => (String::new(), ","),
+    };
+    match v {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|v| enc(&scalar(v))).collect();
+            match style {
+                Style::Matrix if explode => items.iter().map(|v| format!(";{name}={v}")).collect(),
+                _ => prefix + &items.join(separator),
+            }
+        }
+        Value::Object(map) => {
+            let pairs = map.iter().map(|(k, v)| (enc(k), enc(&scalar(v))));
+            match style {
+                Style::Matrix if explode => pairs.map(|(k, v)| format!(";{k}={v}")).collect(),
+                _ if explode => {
+                    let pairs: Vec<String> = pairs.map(|(k, v)| format!("{k}={v}")).collect();
+                    prefix + &pairs.join(separator)
+                }
+                _ => {
+                    let items: Vec<String> = pairs.flat_map(|(k, v)| [k, v]).collect();
+                    prefix + &items.join(",")
+                }
+            }
+        }
+        v => prefix + &enc(&scalar(v)),
+    }
+}
+
+// Query (form, spaceDelimited, pipeDelimited or deepObject style) or
+// cookie (form style) parameter as name and value pairs. Names and
+// items are percent-encoded, delimiters of the items are not.
+fn pairs(name: &str, style: Style, explode: bool, v: &Value) -> Vec<(String, String)> {
+    let pair = |k: &str, v: &Value| (encode(k), encode(&scalar(v)));
+    match v {
+        v if is_absent(v) => vec![],
+        Value::Array(items) if explode && style == Style::Form => {
+            items.iter().map(|v| pair(name, v)).collect()
+        }
+        Value::Array(items) => {
+            let separator = match style {
+                Style::SpaceDelimited => "%20",
+                Style::PipeDelimited => "|",
+                _ => ",",
+            };
+            let items: Vec<String> = items.iter().map(|v| encode(&scalar(v))).collect();
+            vec![(encode(name), items.join(separator))]
+        }
+        Value::Object(map) if style == Style::DeepObject => map
+            .iter()
+            .map(|(k, v)| pair(&format!("{name}[{k}]"), v))
+            .collect(),
+        Value::Object(map) if explode => map.iter().map(|(k, v)| pair(k, v)).collect(),
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .flat_map(|(k, v)| [encode(k), encode(&scalar(v))])
+                .collect();
+            vec![(encode(name), items.join(","))]
+        }
+        v => vec![pair(name, v)],
+    }
+}
+
+// Query of the encoded pairs
+fn query_string(pairs: &[(String, String)]) -> String {
+    let pairs: Vec<String> = pairs.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    match pairs.is_empty() {
+        true => String::new(),
+        false => format!("?{}", pairs.join("&")),
+    }
+}
+
+fn json<T: DeserializeOwned, E>(response: &Response) -> Result<T, Error<E>> {
+    serde_json::from_slice(&response.body).map_err(|err| Error::Deserialize(response.status, err))
+}
+"#;
+
+const CLIENT: &str = r#"
+pub struct Client<T> {
+    base_url: String,
+    transport: T,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(base_url: impl Into<String>, transport: T) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            base_url,
+            transport,
+        }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+"#;
+
+impl Generator<'_, '_> {
+    // Source of the client of operations
+    pub fn client(&self, operations: &[Operation]) -> String {
+        let mut out = String::from(HEADER);
+        out.push_str("\n#![allow(clippy::all, deprecated, dead_code, unused_imports)]\n\n");
+        out.push_str("use super::operations::*;\n");
+        out.push_str("use super::types::*;\n");
+        out.push_str("use serde::de::DeserializeOwned;\n");
+        out.push_str("use serde::Serialize;\n");
+        out.push_str("use serde_json::Value;\n");
+        out.push_str(CLIENT);
+        for op in operations {
+            self.method(&mut out, op);
+        }
+        out.push_str("}\n");
+        out.push_str(RUNTIME);
+        out
+    }
+
+    fn method(&self, out: &mut String, op: &Operation) {
+        let type_name = self.type_name(&op.name);
+        let compiled = op.operation;
+        let method = compiled.op_type.as_str().to_uppercase();
+        let path = compiled
+            .location
+            .path()
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+
+        // Arguments: structs of parameters by location and body
+        let mut signature = vec!["&self".to_string()];
+        for params in op.parameters.iter() {
+            signature.push(format!(
+                "{}: &{}",
+                arg_name(params.place),
+                self.type_name(&params.name)
+            ));
+        }
+        if let Some(b) = &op.request_body {
+            let t = match b.simple_type {
+                Some(_) => format!("&{}", self.body_type(b)),
+                None => self.body_type(b),
+            };
+            match b.required {
+                true => signature.push(format!("body: {t}")),
+                false => signature.push(format!("body: Option<{t}>")),
+            }
+        }
+        let fields = |place: Place| {
+            op.parameters
+                .iter()
+                .find(|params| params.place == place)
+                .map(|params| {
+                    field_names(params)
+                        .into_iter()
+                        .zip(&params.parameters)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+
+        out.push('\n');
+        if let Some(summary) = compiled.summary {
+            for line in summary.as_str().lines() {
+                let _ = writeln!(out, "    /// {line}");
+            }
+            out.push_str("    ///\n");
+        }
+        let _ = writeln!(out, "    /// {method} {path}");
+        if compiled.deprecated {
+            out.push_str("    #[deprecated]\n");
+        }
+        let _ = writeln!(
+            out,
+            "    pub async fn {}({}) -> Result<{}, Error<T::Error>> {{",
+            field_name(&type_name),
+            signature.join(", "),
+            response_name(&type_name)
+        );
+
+        // URL
+        let path_fields = fields(Place::Path);
+        let mut template = String::new();
+        let mut values = vec![];
+        for part in split_template(&path) {
+            match part {
+                Token::Literal(s) => {
+                    template.push_str(&escape(&s.replace('{', "{{").replace('}', "}}")))
+                }
+                Token::Name(name) => {
+                    match path_fields.iter().find(|(_, p)| p.name.as_str() == name) {
+                        Some((field, p)) => {
+                            template.push_str("{}");
+                            values.push(format!(
+                                "simple(\"{}\", {}, {}, &{}(&path.{field})?, encode)",
+                                escape(name),
+                                style(p.style),
+                                p.explode,
+                                value_fn(p)
+                            ));
+                        }
+                        None => template.push_str(&format!("{{{{{}}}}}", escape(name))),
+                    }
+                }
+            }
+        }
+        let query_fields = fields(Place::Query);
+        let _ = writeln!(
+            out,
+            "        let {}url = format!(\"{{}}{template}\", self.base_url{});",
+            if query_fields.is_empty() { "" } else { "mut " },
+            values.iter().map(|v| format!(", {v}")).collect::<String>()
+        );
+        if !query_fields.is_empty() {
+            out.push_str("        let mut query_pairs = vec![];\n");
+            for (field, p) in query_fields.iter() {
+                let _ = writeln!(
+                    out,
+                    "        query_pairs.extend(pairs(\"{}\", {}, {}, &{}(&query.{field})?));",
+                    escape(p.name.as_str()),
+                    style(p.style),
+                    p.explode,
+                    value_fn(p)
+                );
+            }
+            out.push_str("        url.push_str(&query_string(&query_pairs));\n");
+        }
+
+        // Headers and cookies
+        let header_fields = fields(Place::Header);
+        let cookie_fields = fields(Place::Cookie);
+        let no_headers =
+            header_fields.is_empty() && cookie_fields.is_empty() && op.request_body.is_none();
+        let _ = writeln!(
+            out,
+            "        let {}request_headers: Vec<(String, String)> = vec![];",
+            if no_headers { "" } else { "mut " }
+        );
+        for (field, p) in header_fields.iter() {
+            let _ = writeln!(out, "        let v = {}(&headers.{field})?;", value_fn(p));
+            out.push_str("        if !is_absent(&v) {\n");
+            let _ = writeln!(
+                out,
+                "            request_headers.push((\"{}\".into(), simple(\"\", Style::Simple, {}, &v, str::to_string)));",
+                escape(p.name.as_str()),
+                p.explode
+            );
+            out.push_str("        }\n");
+        }
+        if !cookie_fields.is_empty() {
+            out.push_str("        let mut cookie_pairs = vec![];\n");
+            for (field, p) in cookie_fields.iter() {
+                let _ = writeln!(
+                    out,
+                    "        cookie_pairs.extend(pairs(\"{}\", Style::Form, {}, &{}(&cookies.{field})?));",
+                    escape(p.name.as_str()),
+                    p.explode,
+                    value_fn(p)
+                );
+            }
+            out.push_str("        if !cookie_pairs.is_empty() {\n");
+            out.push_str("            let cookie_pairs: Vec<String> = cookie_pairs.iter().map(|(k, v)| format!(\"{k}={v}\")).collect();\n");
+            out.push_str("            request_headers.push((\"Cookie\".into(), cookie_pairs.join(\"; \")));\n");
+            out.push_str("        }\n");
+        }
+
+        // Body
+        match &op.request_body {
+            Some(b) => {
+                let encode = |v: &str| match b.simple_type {
+                    Some(_) => format!("serde_json::to_vec({v}).map_err(Error::Serialize)?"),
+                    None => v.to_string(),
+                };
+                let content_type = format!(
+                    "request_headers.push((\"Content-Type\".into(), \"{}\".into()));",
+                    escape(&content_type(b))
+                );
+                match b.required {
+                    true => {
+                        let _ = writeln!(out, "        {content_type}");
+                        let _ =
+                            writeln!(out, "        let request_body = Some({});", encode("body"));
+                    }
+                    false => {
+                        out.push_str("        let request_body = match body {\n");
+                        out.push_str("            Some(body) => {\n");
+                        let _ = writeln!(out, "                {content_type}");
+                        let _ = writeln!(out, "                Some({})", encode("body"));
+                        out.push_str("            }\n            None => None,\n        };\n");
+                    }
+                }
+            }
+            None => out.push_str("        let request_body = None;\n"),
+        }
+
+        // Response
+        out.push_str("        let request = Request {\n");
+        let _ = writeln!(out, "            method: \"{method}\",");
+        out.push_str("            url,\n            headers: request_headers,\n            body: request_body,\n        };\n");
+        out.push_str("        let response = self.transport.send(request).await.map_err(Error::Transport)?;\n");
+        let response_name = response_name(&type_name);
+        out.push_str("        match response.status {\n");
+        for resp in op.responses.iter() {
+            let pattern = match resp.status {
+                Some(HttpStatusCode::Specific(code)) => code.code().to_string(),
+                Some(HttpStatusCode::Pattern(p)) => format!("{0}00..={0}99", p.class()),
+                None => "_".into(),
+            };
+            let body = resp.body.as_ref().map(|b| match b.simple_type {
+                Some(_) => "json(&response)?",
+                None => "response.body",
+            });
+            let fields: Vec<&str> = match resp.status {
+                Some(HttpStatusCode::Specific(_)) => body.into_iter().collect(),
+                _ => std::iter::once("response.status").chain(body).collect(),
+            };
+            let variant = variant_name(resp.status);
+            let value = match fields.is_empty() {
+                true => format!("{response_name}::{variant}"),
+                false => format!("{response_name}::{variant}({})", fields.join(", ")),
+            };
+            let _ = writeln!(out, "            {pattern} => Ok({value}),");
+        }
+        if !op.responses.iter().any(|resp| resp.status.is_none()) {
+            out.push_str("            _ => Err(Error::UnexpectedStatus(response)),\n");
+        }
+        out.push_str("        }\n");
+        out.push_str("    }\n");
+    }
+}
+
+// Argument of the method with parameters at the location
+fn arg_name(place: Place) -> &'static str {
+    match place {
+        Place::Path => "path",
+        Place::Query => "query",
+        Place::Header => "headers",
+        Place::Cookie => "cookies",
+    }
+}
+
+fn value_fn(p: &Parameter) -> &'static str {
+    match p.json {
+        true => "json_value",
+        false => "value",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codegen::rust::generate;
+    use crate::codegen::rust::Options;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    #[test]
+    fn client() {
+        let spec = include_str!("../../../test/test-vectors/client.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = model::operation::build(&compiled, &mut models).unwrap();
+        let files = generate(&models, &operations, Options::default());
+        assert_eq!(
+            files[0].contents,
+            format!("{HEADER}\npub mod client;\npub mod operations;\npub mod types;\n")
+        );
+        assert_eq!(files[3].path, "client.rs");
+        let expected = include_str!("../../../test/test-vectors/codegen-client.rs");
+        assert_eq!(files[3].contents, expected);
+    }
+}
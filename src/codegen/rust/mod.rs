@@ -0,0 +1,762 @@
+// SPDX-License-Identifier: MIT
+//
+// Rust code generator.
+//
+// Models are generated as serde serializable types: objects as
+// structs, string enumerations as enums, oneOf and anyOf as untagged
+// enums and allOf as structs of flattened parts. Types of the
+// operations (see operations) and their client (see client) and
+// server (see server) are generated if there are any operations.
+//
+
+pub mod client;
+pub mod operations;
+pub mod server;
+
+use crate::codegen::words;
+use crate::codegen::File;
+use crate::model::name::Name;
+use crate::model::operation::Operation;
+use crate::model::operation::Place;
+use crate::model::split::Usage;
+use crate::model::AllOf;
+use crate::model::Doc;
+use crate::model::Enum;
+use crate::model::Model;
+use crate::model::NormalType;
+use crate::model::NullableType;
+use crate::model::Object;
+use crate::model::PropertyType;
+use crate::model::SimpleType;
+use crate::model::Union;
+use crate::schema::data_type::numerical;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+pub(crate) const HEADER: &str = "// Generated by openapirs. Do not edit.\n";
+
+// Keywords those can't be used as identifiers (even raw ones)
+const RESERVED: &[&str] = &["crate", "self", "Self", "super"];
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+pub struct Generator<'a, 'm> {
+    models: &'m [(Name<'a>, Model<'a>)],
+    // Rust names of the models
+    names: HashMap<&'m Name<'a>, String>,
+}
+
+// Parts of the generated code besides types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    pub client: bool,
+    pub server: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            client: true,
+            server: false,
+        }
+    }
+}
+
+// Generate module with types of all models, types of operations and
+// client and server of operations (as requested by options)
+pub fn generate<'a>(
+    models: &[(Name<'a>, Model<'a>)],
+    operations: &[Operation<'a>],
+    options: Options,
+) -> Vec<File> {
+    let generator = Generator::new(models);
+    let mut files = vec![File {
+        path: "types.rs".into(),
+        contents: generator.types(),
+    }];
+    if !operations.is_empty() {
+        files.push(File {
+            path: "operations.rs".into(),
+            contents: generator.operations(operations),
+        });
+        if options.client {
+            files.push(File {
+                path: "client.rs".into(),
+                contents: generator.client(operations),
+            });
+        }
+        if options.server {
+            files.push(File {
+                path: "server.rs".into(),
+                contents: generator.server(operations),
+            });
+        }
+    }
+    let mut modules: Vec<&str> = files
+        .iter()
+        .filter_map(|file| file.path.strip_suffix(".rs"))
+        .collect();
+    modules.sort();
+    let modules: String = modules.iter().map(|m| format!("pub mod {m};\n")).collect();
+    files.insert(
+        0,
+        File {
+            path: "mod.rs".into(),
+            contents: format!("{HEADER}\n{modules}"),
+        },
+    );
+    files
+}
+
+impl<'a, 'm> Generator<'a, 'm> {
+    pub fn new(models: &'m [(Name<'a>, Model<'a>)]) -> Self {
+        let names = models
+            .iter()
+            .map(|(name, model)| {
+                let rust_name = match model {
+                    Model::Object(Object { rust_name, .. })
+                    | Model::Enum(Enum { rust_name, .. }) => *rust_name,
+                    _ => None,
+                };
+                // Variants of the renamed model keep the name
+                let rust_name = match (rust_name, name) {
+                    (Some(v), Name::Usage(usage, _)) => format!("{v}{}", usage_suffix(*usage)),
+                    (Some(v), _) => v.to_string(),
+                    (None, _) => type_name(name),
+                };
+                (name, rust_name)
+            })
+            .collect();
+        Self { models, names }
+    }
+
+    // Model of the name (if it is generated)
+    pub fn model(&self, name: &Name<'a>) -> Option<&'m Model<'a>> {
+        self.models
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, model)| model)
+    }
+
+    pub fn type_name(&self, name: &Name<'a>) -> String {
+        self.names
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| type_name(name))
+    }
+
+    // Defaults those can't be expressed in Rust and are ignored, as
+    // type name and name of its property or parameter
+    pub fn ignored_defaults(&self, operations: &[Operation<'a>]) -> Vec<(String, String)> {
+        let properties = self.models.iter().flat_map(|(name, model)| {
+            let properties = match model {
+                Model::Object(obj) => obj.properties.as_slice(),
+                _ => &[],
+            };
+            properties
+                .iter()
+                .filter(|(_, pt)| pt.default.is_some() && self.property_default(pt).is_none())
+                .map(move |(pname, _)| (self.type_name(name), pname.as_str().to_string()))
+        });
+        let parameters = operations
+            .iter()
+            .flat_map(|op| op.parameters.iter())
+            .flat_map(|params| {
+                params
+                    .parameters
+                    .iter()
+                    .filter(|p| p.default.is_some() && self.parameter_default(p).is_none())
+                    .map(|p| (self.type_name(&params.name), p.name.as_str().to_string()))
+            });
+        properties.chain(parameters).collect()
+    }
+
+    // Deprecated types are used by generated code without warnings
+    pub(crate) fn has_deprecated(&self) -> bool {
+        self.models.iter().any(|(_, model)| is_deprecated(model))
+    }
+
+    // Source of all types
+    pub fn types(&self) -> String {
+        let mut out = String::from(HEADER);
+        if self.has_deprecated() {
+            out.push_str("\n#![allow(deprecated)]\n");
+        }
+        out.push_str("\nuse serde::Deserialize;\nuse serde::Serialize;\n");
+        for (name, model) in self.models {
+            let rust_name = self.type_name(name);
+            out.push('\n');
+            doc_comment(&mut out, "", model.doc());
+            match model {
+                Model::Object(obj) => self.object(&mut out, &rust_name, obj),
+                Model::Enum(e) => enumeration(&mut out, &rust_name, e),
+                Model::RustType(path, _) => {
+                    let _ = writeln!(out, "pub type {rust_name} = {path};");
+                }
+                Model::Alias(t, _) => {
+                    let _ = writeln!(out, "pub type {rust_name} = {};", self.rust_type(t));
+                }
+                Model::OneOf(u) | Model::AnyOf(u) => self.union(&mut out, &rust_name, u),
+                Model::AllOf(all_of) => self.all_of(&mut out, &rust_name, all_of),
+            }
+        }
+        out
+    }
+
+    // Absent properties with default get it by serde helper. Object
+    // implements Default if all of its properties have defaults or
+    // are optional.
+    fn object(&self, out: &mut String, rust_name: &str, obj: &Object<'a>) {
+        let defaults: Vec<_> = obj
+            .properties
+            .iter()
+            .map(|(_, pt)| self.property_default(pt))
+            .collect();
+        let impl_default = defaults.iter().any(Option::is_some)
+            && obj
+                .properties
+                .iter()
+                .zip(&defaults)
+                .all(|((_, pt), default)| default.is_some() || !pt.required);
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        let _ = writeln!(out, "pub struct {rust_name} {{");
+        let mut fields = vec![];
+        for ((pname, pt), default) in obj.properties.iter().zip(&defaults) {
+            let field = property_field(pname.as_str(), pt);
+            let mut attrs = vec![];
+            if field.trim_start_matches("r#") != pname.as_str() {
+                attrs.push(format!("rename = \"{}\"", escape(pname.as_str())));
+            }
+            let t = self.rust_type(&pt.simple_type);
+            let t = match self.is_optional(pt) {
+                true => format!("Option<{t}>"),
+                false => t,
+            };
+            match (pt.required, default) {
+                (true, _) => {}
+                (false, Some(_)) => {
+                    attrs.push(format!("default = \"{rust_name}::{}\"", default_fn(&field)))
+                }
+                (false, None) => {
+                    attrs.push("default".into());
+                    attrs.push("skip_serializing_if = \"Option::is_none\"".into());
+                }
+            }
+            doc_comment(out, "    ", &pt.doc);
+            if !attrs.is_empty() {
+                let _ = writeln!(out, "    #[serde({})]", attrs.join(", "));
+            }
+            let _ = writeln!(out, "    pub {field}: {t},");
+            fields.push((field, pt, t, default));
+        }
+        out.push_str("}\n");
+
+        // Helpers are used by serde and Default
+        let helpers: Vec<_> = fields
+            .iter()
+            .filter_map(
+                |(field, pt, t, default)| match (default, !pt.required || impl_default) {
+                    (Some(v), true) => Some((field, t, v)),
+                    _ => None,
+                },
+            )
+            .collect();
+        if !helpers.is_empty() {
+            let _ = writeln!(out, "\nimpl {rust_name} {{");
+            for (field, t, v) in helpers {
+                let _ = writeln!(
+                    out,
+                    "    fn {}() -> {t} {{\n        {v}\n    }}",
+                    default_fn(field)
+                );
+            }
+            out.push_str("}\n");
+        }
+        if impl_default {
+            out.push('\n');
+            if obj.properties.iter().any(|(_, pt)| pt.doc.deprecated) {
+                out.push_str("#[allow(deprecated)]\n");
+            }
+            let _ = writeln!(out, "impl Default for {rust_name} {{");
+            out.push_str("    fn default() -> Self {\n        Self {\n");
+            for (field, _, _, default) in fields.iter() {
+                let value = match default {
+                    Some(_) => format!("Self::{}()", default_fn(field)),
+                    None => "None".into(),
+                };
+                let _ = writeln!(out, "            {field}: {value},");
+            }
+            out.push_str("        }\n    }\n}\n");
+        }
+    }
+
+    // Rust expression of the property default. Default which can't be
+    // expressed (e.g. value not matching the type) is ignored.
+    fn property_default(&self, pt: &PropertyType<'a>) -> Option<String> {
+        self.default_expr(&pt.simple_type, pt.default.as_ref()?)
+    }
+
+    // Field of the property is Option if the property may be absent
+    // and has no value to replace it
+    fn is_optional(&self, pt: &PropertyType<'a>) -> bool {
+        !pt.required && !is_nullable(&pt.simple_type) && self.property_default(pt).is_none()
+    }
+
+    fn union(&self, out: &mut String, rust_name: &str, u: &Union) {
+        // Variants are tried in order. Discriminator is not needed
+        // to select the variant.
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        out.push_str("#[serde(untagged)]\n");
+        let _ = writeln!(out, "pub enum {rust_name} {{");
+        let names = unique(u.variants.iter().map(|t| self.variant_name(t)).collect());
+        for (name, t) in names.iter().zip(&u.variants) {
+            let _ = writeln!(out, "    {name}({}),", self.rust_type(t));
+        }
+        out.push_str("}\n");
+    }
+
+    fn all_of(&self, out: &mut String, rust_name: &str, all_of: &AllOf) {
+        out.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        let _ = writeln!(out, "pub struct {rust_name} {{");
+        let names = unique(all_of.parts.iter().map(|t| self.variant_name(t)).collect());
+        for (name, t) in names.iter().zip(&all_of.parts) {
+            out.push_str("    #[serde(flatten)]\n");
+            let _ = writeln!(out, "    pub {}: {},", field_name(name), self.rust_type(t));
+        }
+        out.push_str("}\n");
+    }
+
+    fn variant_name(&self, t: &SimpleType<'a>) -> String {
+        match t {
+            SimpleType::Named(name @ Name::Schemas(_)) => self.type_name(name),
+            SimpleType::Named(_) | SimpleType::RustType(_) => "Variant".into(),
+            SimpleType::Nullable(NullableType::Null) => "Null".into(),
+            SimpleType::Nullable(NullableType::Boolean(_))
+            | SimpleType::Normal(NormalType::Boolean(_)) => "Boolean".into(),
+            SimpleType::Nullable(NullableType::Integer(_))
+            | SimpleType::Normal(NormalType::Integer(_)) => "Integer".into(),
+            SimpleType::Nullable(NullableType::Number(_))
+            | SimpleType::Normal(NormalType::Number(_)) => "Number".into(),
+            SimpleType::Nullable(NullableType::String(_))
+            | SimpleType::Normal(NormalType::String(_)) => "String".into(),
+            SimpleType::Nullable(NullableType::Enum(name))
+            | SimpleType::Normal(NormalType::Enum(name))
+            | SimpleType::Nullable(NullableType::Object(name))
+            | SimpleType::Normal(NormalType::Object(name)) => self.type_name(name),
+            SimpleType::Nullable(NullableType::Array(_))
+            | SimpleType::Normal(NormalType::Array(_)) => "Array".into(),
+        }
+    }
+
+    pub fn rust_type(&self, t: &SimpleType<'a>) -> String {
+        match t {
+            SimpleType::Nullable(t) => format!("Option<{}>", self.nullable_type(t)),
+            SimpleType::Normal(t) => self.normal_type(t),
+            SimpleType::RustType(path) => path.to_string(),
+            SimpleType::Named(name) => self.type_name(name),
+        }
+    }
+
+    fn nullable_type(&self, t: &NullableType<'a>) -> String {
+        match t {
+            NullableType::Null => "()".into(),
+            NullableType::Boolean(_) => "bool".into(),
+            NullableType::Integer(numerical::NullableIntegerType::WithFormat(
+                numerical::NullableIntegerWithFormat::Int32(_),
+            )) => "i32".into(),
+            NullableType::Integer(_) => "i64".into(),
+            NullableType::Number(numerical::NullableNumberType::WithFormat(
+                numerical::NullableNumberWithFormat::Float(_),
+            )) => "f32".into(),
+            NullableType::Number(_) => "f64".into(),
+            NullableType::String(_) => "String".into(),
+            NullableType::Enum(name) | NullableType::Object(name) => self.type_name(name),
+            NullableType::Array(items) => format!("Vec<{}>", self.rust_type(items)),
+        }
+    }
+
+    // Rust expression of the value of the type (e.g. default value).
+    // Values of the models are constructed as literals too. None if
+    // the value doesn't match the type or type has no literals.
+    pub(crate) fn default_expr(&self, t: &SimpleType<'a>, v: &Value) -> Option<String> {
+        let expr = match (t, v) {
+            (SimpleType::Nullable(_), Value::Null) => return Some("None".into()),
+            (SimpleType::Nullable(NullableType::Boolean(_)), Value::Bool(b))
+            | (SimpleType::Normal(NormalType::Boolean(_)), Value::Bool(b)) => b.to_string(),
+            (SimpleType::Nullable(NullableType::Integer(_)), Value::Number(n))
+            | (SimpleType::Normal(NormalType::Integer(_)), Value::Number(n)) => {
+                n.as_i64()?.to_string()
+            }
+            (SimpleType::Nullable(NullableType::Number(_)), Value::Number(n))
+            | (SimpleType::Normal(NormalType::Number(_)), Value::Number(n)) => match n.is_f64() {
+                true => n.to_string(),
+                false => format!("{n}.0"),
+            },
+            (SimpleType::Nullable(NullableType::String(_)), Value::String(s))
+            | (SimpleType::Normal(NormalType::String(_)), Value::String(s))
+            | (SimpleType::RustType("String"), Value::String(s)) => {
+                format!("\"{}\".into()", escape(s))
+            }
+            (SimpleType::Nullable(NullableType::Array(items)), Value::Array(values))
+            | (SimpleType::Normal(NormalType::Array(items)), Value::Array(values)) => {
+                let values = values
+                    .iter()
+                    .map(|v| self.default_expr(items, v))
+                    .collect::<Option<Vec<_>>>()?;
+                format!("vec![{}]", values.join(", "))
+            }
+            (SimpleType::Nullable(NullableType::Enum(name)), v)
+            | (SimpleType::Normal(NormalType::Enum(name)), v)
+            | (SimpleType::Nullable(NullableType::Object(name)), v)
+            | (SimpleType::Normal(NormalType::Object(name)), v)
+            | (SimpleType::Named(name), v) => self.model_expr(name, v)?,
+            _ => return None,
+        };
+        match t {
+            SimpleType::Nullable(_) => Some(format!("Some({expr})")),
+            _ => Some(expr),
+        }
+    }
+
+    // Value of the model. Variants of oneOf and anyOf are tried in
+    // order as serde does.
+    fn model_expr(&self, name: &Name<'a>, v: &Value) -> Option<String> {
+        let rust_name = self.type_name(name);
+        match self.model(name)? {
+            Model::Enum(e) => e
+                .members
+                .iter()
+                .zip(member_names(e))
+                .find(|(m, _)| Some(m.value) == v.as_str())
+                .map(|(_, variant)| format!("{rust_name}::{variant}")),
+            Model::Alias(t, _) => self.default_expr(t, v),
+            Model::Object(obj) => {
+                let values = v.as_object()?;
+                let fields = obj
+                    .properties
+                    .iter()
+                    .map(|(pname, pt)| {
+                        let value = match values.get(pname.as_str()) {
+                            Some(v) => {
+                                let expr = self.default_expr(&pt.simple_type, v)?;
+                                match self.is_optional(pt) {
+                                    true => format!("Some({expr})"),
+                                    false => expr,
+                                }
+                            }
+                            None => match self.property_default(pt) {
+                                Some(expr) => expr,
+                                None if !pt.required => "None".into(),
+                                None => return None,
+                            },
+                        };
+                        Some(format!("{}: {value}", property_field(pname.as_str(), pt)))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("{rust_name} {{ {} }}", fields.join(", ")))
+            }
+            Model::OneOf(u) | Model::AnyOf(u) => {
+                let names = unique(u.variants.iter().map(|t| self.variant_name(t)).collect());
+                names.iter().zip(&u.variants).find_map(|(variant, t)| {
+                    let expr = self.default_expr(t, v)?;
+                    Some(format!("{rust_name}::{variant}({expr})"))
+                })
+            }
+            Model::AllOf(all_of) => {
+                let names = unique(all_of.parts.iter().map(|t| self.variant_name(t)).collect());
+                let fields = names
+                    .iter()
+                    .zip(&all_of.parts)
+                    .map(|(part, t)| {
+                        let expr = self.default_expr(t, v)?;
+                        Some(format!("{}: {expr}", field_name(part)))
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                Some(format!("{rust_name} {{ {} }}", fields.join(", ")))
+            }
+            Model::RustType(..) => None,
+        }
+    }
+
+    fn normal_type(&self, t: &NormalType<'a>) -> String {
+        match t {
+            NormalType::Boolean(_) => "bool".into(),
+            // Integer without format is not limited to 32 bits
+            NormalType::Integer(numerical::IntegerType::WithFormat(
+                numerical::IntegerWithFormat::Int32(_),
+            )) => "i32".into(),
+            NormalType::Integer(_) => "i64".into(),
+            NormalType::Number(numerical::NumberType::WithFormat(
+                numerical::NumberWithFormat::Float(_),
+            )) => "f32".into(),
+            NormalType::Number(_) => "f64".into(),
+            NormalType::String(_) => "String".into(),
+            NormalType::Enum(name) | NormalType::Object(name) => self.type_name(name),
+            NormalType::Array(items) => format!("Vec<{}>", self.rust_type(items)),
+        }
+    }
+}
+
+fn enumeration(out: &mut String, rust_name: &str, e: &Enum) {
+    let default = match e.members.iter().any(|m| m.default) {
+        true => " Default,",
+        false => "",
+    };
+    let _ = writeln!(
+        out,
+        "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash,{default} Serialize, Deserialize)]"
+    );
+    let _ = writeln!(out, "pub enum {rust_name} {{");
+    let names = member_names(e);
+    for (name, m) in names.iter().zip(&e.members) {
+        if let Some(description) = m.description {
+            for line in description.lines() {
+                let _ = writeln!(out, "    /// {line}");
+            }
+        }
+        if m.default {
+            out.push_str("    #[default]\n");
+        }
+        let _ = writeln!(out, "    #[serde(rename = \"{}\")]", escape(m.value));
+        let _ = writeln!(out, "    {name},");
+    }
+    out.push_str("}\n");
+}
+
+// Variants of the enum in order of members
+fn member_names(e: &Enum) -> Vec<String> {
+    unique(
+        e.members
+            .iter()
+            .map(|m| {
+                m.rust_name
+                    .map_or_else(|| variant_name(m.value), String::from)
+            })
+            .collect(),
+    )
+}
+
+// Title, description and external documentation as doc comment and
+// deprecation as attribute
+fn doc_comment(out: &mut String, indent: &str, doc: &Doc) {
+    let mut sections = vec![];
+    if let Some(title) = doc.title {
+        sections.push(title.trim().to_string());
+    }
+    if let Some(description) = doc.description {
+        sections.push(description.trim().to_string());
+    }
+    if let Some(external_docs) = doc.external_docs {
+        let url = external_docs.url.as_str();
+        sections.push(match &external_docs.description {
+            Some(description) => format!("See [{}]({url})", description.as_str().trim()),
+            None => format!("See <{url}>"),
+        });
+    }
+    for line in sections.join("\n\n").lines() {
+        match line.trim_end() {
+            "" => {
+                let _ = writeln!(out, "{indent}///");
+            }
+            line => {
+                let _ = writeln!(out, "{indent}/// {line}");
+            }
+        }
+    }
+    if doc.deprecated {
+        let _ = writeln!(out, "{indent}#[deprecated]");
+    }
+}
+
+// Model or any of its properties is deprecated
+fn is_deprecated(model: &Model) -> bool {
+    match model {
+        Model::Object(obj) => {
+            obj.doc.deprecated || obj.properties.iter().any(|(_, pt)| pt.doc.deprecated)
+        }
+        model => model.doc().deprecated,
+    }
+}
+
+// Helper returning default of the field
+// Field name of the property
+fn property_field(pname: &str, pt: &PropertyType) -> String {
+    pt.rust_name.map_or_else(|| field_name(pname), String::from)
+}
+
+fn default_fn(field: &str) -> String {
+    format!("default_{}", field.trim_start_matches("r#"))
+}
+
+fn is_nullable(t: &SimpleType) -> bool {
+    matches!(t, SimpleType::Nullable(_))
+}
+
+// Name of the type defined by model location, e.g. property "kind"
+// of schema "Pet" is "PetKind"
+pub fn type_name(name: &Name) -> String {
+    match name {
+        Name::Schemas(name) => upper_camel(name.as_str()),
+        Name::Property(pname, parent) => type_name(parent) + &upper_camel(pname.as_str()),
+        Name::Operation(op_type, path, id) => match id {
+            Some(id) => upper_camel(id.as_str()),
+            None => upper_camel(&format!("{} {path}", op_type.as_str())),
+        },
+        Name::Parameter(pname, parent) => type_name(parent) + &upper_camel(pname.as_str()),
+        Name::Parameters(place, parent) => {
+            let suffix = match place {
+                Place::Path => "Path",
+                Place::Query => "Query",
+                Place::Header => "Headers",
+                Place::Cookie => "Cookies",
+            };
+            type_name(parent) + suffix
+        }
+        Name::RequestBody(parent) => type_name(parent) + "RequestBody",
+        Name::RequestBodies(name) => upper_camel(name.as_str()) + "RequestBody",
+        Name::Response(Some(code), parent) => {
+            format!(
+                "{}Response{}",
+                type_name(parent),
+                code.to_string().to_lowercase()
+            )
+        }
+        Name::Response(None, parent) => type_name(parent) + "ResponseDefault",
+        Name::Responses(name) => upper_camel(name.as_str()) + "Response",
+        Name::Header(hname, parent) => type_name(parent) + &upper_camel(hname.as_str()) + "Header",
+        Name::Item(parent) => type_name(parent) + "Item",
+        Name::Variant(i, parent) => format!("{}Variant{}", type_name(parent), i + 1),
+        Name::Usage(usage, parent) => type_name(parent) + usage_suffix(*usage),
+    }
+}
+
+fn usage_suffix(usage: Usage) -> &'static str {
+    match usage {
+        Usage::Request => "Request",
+        Usage::Response => "Response",
+    }
+}
+
+pub fn upper_camel(s: &str) -> String {
+    let name: String = words(s)
+        .iter()
+        .map(|w| {
+            let mut chars = w.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .concat();
+    match name.chars().next() {
+        Some(c) if c.is_ascii_digit() => format!("N{name}"),
+        Some(_) => name,
+        None => "Empty".into(),
+    }
+}
+
+pub fn field_name(s: &str) -> String {
+    let name = words(s).join("_");
+    match name.chars().next() {
+        None => "empty".into(),
+        Some(c) if c.is_ascii_digit() => format!("n{name}"),
+        Some(_) if RESERVED.contains(&name.as_str()) => format!("{name}_"),
+        Some(_) if KEYWORDS.contains(&name.as_str()) => format!("r#{name}"),
+        Some(_) => name,
+    }
+}
+
+fn variant_name(value: &str) -> String {
+    let name = upper_camel(value);
+    match RESERVED.contains(&name.as_str()) {
+        true => format!("{name}_"),
+        false => name,
+    }
+}
+
+// Numbered duplicates, e.g. [A, A] becomes [A1, A2]
+pub(crate) fn unique(names: Vec<String>) -> Vec<String> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            if names.iter().filter(|n| *n == name).count() == 1 {
+                return name.clone();
+            }
+            let n = names[..i].iter().filter(|n| *n == name).count() + 1;
+            format!("{name}{n}")
+        })
+        .collect()
+}
+
+// Escape string literal
+pub(crate) fn escape(s: &str) -> String {
+    s.escape_default().to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(upper_camel("pet_id"), "PetId");
+        assert_eq!(upper_camel("HTTPResponse"), "HttpResponse");
+        assert_eq!(upper_camel("2fa"), "N2fa");
+        assert_eq!(field_name("petId"), "pet_id");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("self"), "self_");
+        assert_eq!(field_name("X-Rate-Limit"), "x_rate_limit");
+        assert_eq!(
+            unique(vec!["A".into(), "B".into(), "A".into()]),
+            ["A1", "B", "A2"]
+        );
+    }
+
+    #[test]
+    fn types() {
+        let spec = include_str!("../../../test/test-vectors/codegen.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let models = model::build(&compiled).unwrap();
+        let files = generate(&models, &[], Options::default());
+        assert_eq!(files[0].path, "mod.rs");
+        assert_eq!(files[1].path, "types.rs");
+        let expected = include_str!("../../../test/test-vectors/codegen-types.rs");
+        assert_eq!(files[1].contents, expected);
+    }
+
+    #[test]
+    fn ignored_defaults() {
+        let spec = include_str!("../../../test/test-vectors/examples.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let models = model::build(&compiled).unwrap();
+        let generator = Generator::new(&models);
+        let ignored: Vec<_> = generator.ignored_defaults(&[]);
+        assert_eq!(
+            ignored,
+            [
+                ("Pet".into(), "kind".into()),
+                ("Pet".into(), "owner".into())
+            ]
+        );
+        let types = generator.types();
+        assert!(types.contains("pub kind: Option<PetKind>,"));
+        assert!(
+            types.contains("fn default_tags() -> Vec<String> {\n        vec![\"indoor\".into()]")
+        );
+        assert!(!types.contains("unwrap()"));
+    }
+}
@@ -0,0 +1,325 @@
+// SPDX-License-Identifier: MIT
+//
+// Types of the operations shared by generated client and server:
+// struct of the parameters per location (path, query, headers and
+// cookies) and response enum with variant per described status.
+// Variants of 2XX statuses are success ones.
+//
+
+use crate::codegen::rust::field_name;
+use crate::codegen::rust::unique;
+use crate::codegen::rust::Generator;
+use crate::codegen::rust::HEADER;
+use crate::model::operation::Body;
+use crate::model::operation::Operation;
+use crate::model::operation::Parameter;
+use crate::model::operation::Parameters;
+use crate::model::SimpleType;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::parameter::SerializeStyle;
+use crate::schema::template;
+use crate::schema::template::Token;
+use std::fmt::Write;
+
+impl Generator<'_, '_> {
+    // Source of the module with types of operations
+    pub fn operations(&self, operations: &[Operation]) -> String {
+        let mut out = String::from(HEADER);
+        // Types are not used if operations have no parameters or bodies
+        match self.has_deprecated() {
+            true => out.push_str("\n#![allow(deprecated, unused_imports)]\n"),
+            false => out.push_str("\n#![allow(unused_imports)]\n"),
+        }
+        out.push_str("\nuse super::types::*;\n");
+        for op in operations {
+            for params in op.parameters.iter() {
+                self.parameters_struct(&mut out, params);
+            }
+            self.response_enum(&mut out, op);
+        }
+        out
+    }
+
+    fn parameters_struct(&self, out: &mut String, params: &Parameters) {
+        let rust_name = self.type_name(&params.name);
+        let optional = params.parameters.iter().all(|p| !p.required);
+        let derive_default = optional
+            && params
+                .parameters
+                .iter()
+                .all(|p| self.parameter_default(p).is_none());
+        let default = if derive_default { ", Default" } else { "" };
+        let _ = writeln!(out, "\n#[derive(Debug, Clone, PartialEq{default})]");
+        let _ = writeln!(out, "pub struct {rust_name} {{");
+        let fields = field_names(params);
+        for (name, p) in fields.iter().zip(&params.parameters) {
+            if name.trim_start_matches("r#") != p.name.as_str() {
+                let _ = writeln!(out, "    /// {}", p.name.as_str());
+            }
+            if p.deprecated {
+                out.push_str("    #[deprecated]\n");
+            }
+            let _ = writeln!(out, "    pub {name}: {},", self.parameter_type(p));
+        }
+        out.push_str("}\n");
+        if !optional || derive_default {
+            return;
+        }
+
+        // Absent parameters have defaults of the schema
+        out.push('\n');
+        if params.parameters.iter().any(|p| p.deprecated) {
+            out.push_str("#[allow(deprecated)]\n");
+        }
+        let _ = writeln!(out, "impl Default for {rust_name} {{");
+        out.push_str("    fn default() -> Self {\n        Self {\n");
+        for (name, p) in fields.iter().zip(&params.parameters) {
+            let value = self.parameter_default(p).unwrap_or_else(|| "None".into());
+            let _ = writeln!(out, "            {name}: {value},");
+        }
+        out.push_str("        }\n    }\n}\n");
+    }
+
+    fn response_enum(&self, out: &mut String, op: &Operation) {
+        let response_name = response_name(&self.type_name(&op.name));
+        out.push_str("\n#[derive(Debug, Clone, PartialEq)]\n");
+        let _ = writeln!(out, "pub enum {response_name} {{");
+        for resp in op.responses.iter() {
+            let body = resp.body.as_ref().map(|b| self.body_type(b));
+            let fields: Vec<String> = match resp.status {
+                Some(HttpStatusCode::Specific(_)) => body.into_iter().collect(),
+                _ => std::iter::once("u16".into()).chain(body).collect(),
+            };
+            let variant = variant_name(resp.status);
+            match fields.is_empty() {
+                true => {
+                    let _ = writeln!(out, "    {variant},");
+                }
+                false => {
+                    let _ = writeln!(out, "    {variant}({}),", fields.join(", "));
+                }
+            }
+        }
+        out.push_str("}\n");
+
+        // Status of the default response is known at runtime only
+        let _ = writeln!(out, "\nimpl {response_name} {{");
+        out.push_str("    pub fn is_success(&self) -> bool {\n");
+        // Enum without variants is matched by value
+        match op.responses.is_empty() {
+            true => out.push_str("        match *self {\n"),
+            false => out.push_str("        match self {\n"),
+        }
+        for resp in op.responses.iter() {
+            let variant = variant_name(resp.status);
+            let has_fields =
+                resp.body.is_some() || !matches!(resp.status, Some(HttpStatusCode::Specific(_)));
+            let _ = match (resp.status, has_fields) {
+                (None, _) => writeln!(
+                    out,
+                    "            Self::{variant}(status, ..) => (200..300).contains(status),"
+                ),
+                (_, true) => writeln!(out, "            Self::{variant}(..) => {},", resp.success),
+                (_, false) => writeln!(out, "            Self::{variant} => {},", resp.success),
+            };
+        }
+        out.push_str("        }\n    }\n}\n");
+    }
+
+    // Rust expression of the parameter default. Default which can't
+    // be expressed is ignored (see default_expr).
+    pub(crate) fn parameter_default(&self, p: &Parameter) -> Option<String> {
+        self.default_expr(&p.simple_type, p.default.as_ref()?)
+    }
+
+    // Type of the parameter field. Parameter is optional if it is not
+    // required and has no default.
+    pub(crate) fn parameter_type(&self, p: &Parameter) -> String {
+        let t = self.rust_type(&p.simple_type);
+        let nullable = matches!(p.simple_type, SimpleType::Nullable(_));
+        match p.required || self.parameter_default(p).is_some() || nullable {
+            true => t,
+            false => format!("Option<{t}>"),
+        }
+    }
+
+    pub(crate) fn body_type(&self, b: &Body) -> String {
+        match &b.simple_type {
+            Some(t) => self.rust_type(t),
+            None => "Vec<u8>".into(),
+        }
+    }
+}
+
+pub(crate) fn response_name(type_name: &str) -> String {
+    format!("{type_name}Response")
+}
+
+// Fields of the parameters struct in order of parameters
+pub(crate) fn field_names(params: &Parameters) -> Vec<String> {
+    let names = params
+        .parameters
+        .iter()
+        .map(|p| field_name(p.name.as_str()))
+        .collect();
+    unique(names)
+}
+
+// Variant of the response enum, e.g. Ok200 or Status4xx
+pub(crate) fn variant_name(status: Option<&HttpStatusCode>) -> String {
+    match status {
+        Some(HttpStatusCode::Specific(code)) => {
+            let code = code.code();
+            format!("{}{code}", reason(code).unwrap_or("Status"))
+        }
+        Some(HttpStatusCode::Pattern(p)) => format!("Status{}xx", p.class()),
+        None => "Default".into(),
+    }
+}
+
+// Reason phrases of common statuses
+fn reason(code: u16) -> Option<&'static str> {
+    Some(match code {
+        100 => "Continue",
+        101 => "SwitchingProtocols",
+        200 => "Ok",
+        201 => "Created",
+        202 => "Accepted",
+        203 => "NonAuthoritativeInformation",
+        204 => "NoContent",
+        205 => "ResetContent",
+        206 => "PartialContent",
+        300 => "MultipleChoices",
+        301 => "MovedPermanently",
+        302 => "Found",
+        303 => "SeeOther",
+        304 => "NotModified",
+        307 => "TemporaryRedirect",
+        308 => "PermanentRedirect",
+        400 => "BadRequest",
+        401 => "Unauthorized",
+        402 => "PaymentRequired",
+        403 => "Forbidden",
+        404 => "NotFound",
+        405 => "MethodNotAllowed",
+        406 => "NotAcceptable",
+        407 => "ProxyAuthenticationRequired",
+        408 => "RequestTimeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "LengthRequired",
+        412 => "PreconditionFailed",
+        413 => "PayloadTooLarge",
+        414 => "UriTooLong",
+        415 => "UnsupportedMediaType",
+        416 => "RangeNotSatisfiable",
+        417 => "ExpectationFailed",
+        422 => "UnprocessableEntity",
+        423 => "Locked",
+        425 => "TooEarly",
+        428 => "PreconditionRequired",
+        429 => "TooManyRequests",
+        431 => "RequestHeaderFieldsTooLarge",
+        451 => "UnavailableForLegalReasons",
+        500 => "InternalServerError",
+        501 => "NotImplemented",
+        502 => "BadGateway",
+        503 => "ServiceUnavailable",
+        504 => "GatewayTimeout",
+        505 => "HttpVersionNotSupported",
+        _ => return None,
+    })
+}
+
+pub(crate) fn style(style: SerializeStyle) -> &'static str {
+    match style {
+        SerializeStyle::Matrix => "Style::Matrix",
+        SerializeStyle::Label => "Style::Label",
+        SerializeStyle::Form => "Style::Form",
+        SerializeStyle::Simple => "Style::Simple",
+        SerializeStyle::SpaceDelimited => "Style::SpaceDelimited",
+        SerializeStyle::PipeDelimited => "Style::PipeDelimited",
+        SerializeStyle::DeepObject => "Style::DeepObject",
+    }
+}
+
+// Content type of the body to be sent. Media ranges can't be sent.
+pub(crate) fn content_type(b: &Body) -> String {
+    match b.simple_type {
+        Some(_) => "application/json".into(),
+        None => b
+            .content_types
+            .iter()
+            .find(|v| !v.contains('*'))
+            .map_or_else(|| "application/octet-stream".into(), |v| v.to_string()),
+    }
+}
+
+// Tokens of the path template. Unclosed brace (rejected by the router)
+// leaves the whole path literal.
+pub(crate) fn split_template(path: &str) -> Vec<Token<'_>> {
+    template::tokenize(path).unwrap_or_else(|_| vec![Token::Literal(path)])
+}
+
+// Code generated for operation without responses is compiled with
+// the tests (see test::no_responses)
+#[cfg(test)]
+#[allow(dead_code)]
+#[rustfmt::skip]
+#[path = "../../../test/test-vectors/codegen-one-of/mod.rs"]
+mod codegen_one_of;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codegen::rust::generate;
+    use crate::codegen::rust::Options;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    fn code(s: &str) -> HttpStatusCode {
+        s.parse().ok().unwrap()
+    }
+
+    #[test]
+    fn template() {
+        let parts = split_template("/pets/{petId}/photos/{name}.{ext}");
+        let parts: Vec<_> = parts
+            .iter()
+            .map(|v| match v {
+                Token::Literal(s) => format!("'{s}'"),
+                Token::Name(s) => s.to_string(),
+            })
+            .collect();
+        assert_eq!(
+            parts,
+            ["'/pets/'", "petId", "'/photos/'", "name", "'.'", "ext"]
+        );
+        assert_eq!(variant_name(Some(&code("404"))), "NotFound404");
+        assert_eq!(variant_name(Some(&code("299"))), "Status299");
+        assert_eq!(variant_name(Some(&code("5XX"))), "Status5xx");
+        assert_eq!(variant_name(None), "Default");
+    }
+
+    #[test]
+    fn no_responses() {
+        let spec = include_str!("../../../test/test-vectors/request-body-with-one-of-schema.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = model::operation::build(&compiled, &mut models).unwrap();
+        assert!(operations[0].responses.is_empty());
+        let files = generate(&models, &operations, Options::default());
+        let expected = [
+            include_str!("../../../test/test-vectors/codegen-one-of/mod.rs"),
+            include_str!("../../../test/test-vectors/codegen-one-of/types.rs"),
+            include_str!("../../../test/test-vectors/codegen-one-of/operations.rs"),
+            include_str!("../../../test/test-vectors/codegen-one-of/client.rs"),
+        ];
+        assert_eq!(files.len(), expected.len());
+        for (file, expected) in files.iter().zip(expected) {
+            assert_eq!(file.contents, expected, "{}", file.path);
+        }
+    }
+}
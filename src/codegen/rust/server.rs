@@ -0,0 +1,990 @@
+// SPDX-License-Identifier: MIT
+//
+// Rust server stub generator (axum).
+//
+// Operations are grouped by their first tag into service traits with
+// one async method per operation (Api trait for operations without
+// tags). Method gets request struct with parameters and body decoded
+// according to their style and returns response enum of the
+// operation (see operations). Router function mounts implementation
+// of the trait on axum router. Request with invalid parameters or
+// body gets 400 response with validation errors.
+//
+// Path templates are mounted with a capture per segment with
+// parameters. Segments with several parameters (e.g. {name}.{ext})
+// are split by the handler. Templates different only in such
+// segments share the route and their handlers are tried from the
+// most specific one.
+//
+
+use crate::codegen::rust::escape;
+use crate::codegen::rust::field_name;
+use crate::codegen::rust::operations::content_type;
+use crate::codegen::rust::operations::field_names;
+use crate::codegen::rust::operations::response_name;
+use crate::codegen::rust::operations::split_template;
+use crate::codegen::rust::operations::style;
+use crate::codegen::rust::operations::variant_name;
+use crate::codegen::rust::upper_camel;
+use crate::codegen::rust::Generator;
+use crate::codegen::rust::HEADER;
+use crate::compile::router;
+use crate::model::operation::Operation;
+use crate::model::operation::Parameter;
+use crate::model::operation::Parameters;
+use crate::model::operation::Place;
+use crate::model::Model;
+use crate::model::NormalType;
+use crate::model::NullableType;
+use crate::model::SimpleType;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::template::Token;
+use std::fmt::Write;
+
+// Types and functions used by generated handlers
+const RUNTIME: &str = r#"
+// Invalid parameter or body of the request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    // path, query, header, cookie or body
+    #[serde(rename = "in")]
+    pub place: &'static str,
+    pub name: &'static str,
+    pub message: String,
+}
+
+fn bad_request(errors: Vec<ValidationError>) -> Response {
+    let body = serde_json::json!({ "title": "Bad request", "errors": errors });
+    json_response(400, &body)
+}
+
+fn not_found() -> Response {
+    StatusCode::NOT_FOUND.into_response()
+}
+
+fn status(code: u16) -> StatusCode {
+    StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn empty_response(code: u16) -> Response {
+    status(code).into_response()
+}
+
+fn json_response(code: u16, body: &impl Serialize) -> Response {
+    match serde_json::to_vec(body) {
+        Ok(body) => (status(code), [(CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
+
+fn bytes_response(code: u16, content_type: &'static str, body: Vec<u8>) -> Response {
+    (status(code), [(CONTENT_TYPE, content_type)], body).into_response()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+// Type of the raw value. Raw values are converted to JSON values of
+// the type and then deserialized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    // JSON if raw value is valid JSON, string otherwise
+    Json,
+}
+
+// Value of the parameter: scalar, array or object of scalars (with
+// kinds of known properties)
+enum Shape {
+    Scalar(Kind),
+    Array(Kind),
+    Object(&'static [(&'static str, Kind)]),
+}
+
+// Part of the path segment with several parameters
+enum Part {
+    Literal(&'static str),
+    Param,
+}
+
+// Deserialize value. Error is added if value is invalid or required
+// value is missing.
+// Value of the absent parameter is the default one
+fn with_default(value: Result<Value, String>, default: &str) -> Result<Value, String> {
+    match value {
+        Ok(Value::Null) => serde_json::from_str(default).map_err(|err| err.to_string()),
+        value => value,
+    }
+}
+
+fn check<T: DeserializeOwned>(
+    errors: &mut Vec<ValidationError>,
+    place: &'static str,
+    name: &'static str,
+    required: bool,
+    value: Result<Value, String>,
+) -> Option<T> {
+    let message = match value {
+        Ok(Value::Null) if required => "required".to_string(),
+        Ok(v) => match serde_json::from_value(v) {
+            Ok(v) => return Some(v),
+            Err(err) => err.to_string(),
+        },
+        Err(message) => message,
+    };
+    errors.push(ValidationError {
+        place,
+        name,
+        message,
+    });
+    None
+}
+
+fn scalar(raw: &str, kind: Kind) -> Result<Value, String> {
+    match kind {
+        Kind::String => Ok(Value::String(raw.into())),
+        Kind::Integer => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| raw.parse::<u64>().map(Value::from))
+            .map_err(|_| format!("invalid integer: {raw}")),
+        Kind::Number => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("invalid number: {raw}")),
+        Kind::Boolean => raw
+            .parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| format!("invalid boolean: {raw}")),
+        Kind::Json => Ok(serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.into()))),
+    }
+}
+
+fn object(pairs: &[(&str, &str)], fields: &[(&str, Kind)]) -> Result<Value, String> {
+    let mut object = serde_json::Map::new();
+    for (k, v) in pairs {
+        let kind = fields
+            .iter()
+            .find(|(name, _)| name == k)
+            .map_or(Kind::Json, |(_, kind)| *kind);
+        object.insert(k.to_string(), scalar(v, kind)?);
+    }
+    Ok(Value::Object(object))
+}
+
+// Value of the shape from items separated by separator. Object is
+// key=value items if exploded, alternating keys and values otherwise.
+fn shaped(raw: &str, separator: char, explode: bool, shape: &Shape) -> Result<Value, String> {
+    let items: Vec<&str> = match raw.is_empty() {
+        true => vec![],
+        false => raw.split(separator).collect(),
+    };
+    match shape {
+        Shape::Scalar(kind) => scalar(raw, *kind),
+        Shape::Array(kind) => items
+            .iter()
+            .map(|v| scalar(v, *kind))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Shape::Object(fields) => {
+            let pairs: Vec<(&str, &str)> = match explode {
+                true => items.iter().map(|v| v.split_once('=').unwrap_or((v, ""))).collect(),
+                false => items
+                    .chunks(2)
+                    .map(|v| (v[0], v.get(1).copied().unwrap_or_default()))
+                    .collect(),
+            };
+            object(&pairs, fields)
+        }
+    }
+}
+
+// Path (simple, label or matrix style) or header (simple style)
+// parameter
+fn simple_value(
+    raw: Option<&str>,
+    name: &str,
+    style: Style,
+    explode: bool,
+    shape: &Shape,
+) -> Result<Value, String> {
+    let Some(raw) = raw else {
+        return Ok(Value::Null);
+    };
+    match style {
+        Style::Label => {
+            let raw = raw.strip_prefix('.').ok_or("label style expected")?;
+            shaped(raw, if explode { '.' } else { ',' }, explode, shape)
+        }
+        Style::Matrix => {
+            let raw = raw.strip_prefix(';').ok_or("matrix style expected")?;
+            let pairs: Vec<(&str, &str)> = raw
+                .split(';')
+                .map(|v| v.split_once('=').unwrap_or((v, "")))
+                .collect();
+            match shape {
+                Shape::Object(fields) if explode => object(&pairs, fields),
+                Shape::Array(kind) if explode => pairs
+                    .iter()
+                    .filter(|(k, _)| *k == name)
+                    .map(|(_, v)| scalar(v, *kind))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array),
+                _ => match pairs.iter().find(|(k, _)| *k == name) {
+                    Some((_, v)) => shaped(v, ',', false, shape),
+                    None => Err(format!("{name} expected")),
+                },
+            }
+        }
+        _ => shaped(raw, ',', explode, shape),
+    }
+}
+
+// Query (form, spaceDelimited, pipeDelimited or deepObject style) or
+// cookie (form style) parameter
+fn form_value(
+    pairs: &[(String, String)],
+    name: &str,
+    style: Style,
+    explode: bool,
+    shape: &Shape,
+) -> Result<Value, String> {
+    let values: Vec<&str> = pairs
+        .iter()
+        .filter(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+        .collect();
+    match shape {
+        Shape::Object(fields) if explode || style == Style::DeepObject => {
+            let pairs: Vec<(&str, &str)> = pairs
+                .iter()
+                .filter_map(|(k, v)| {
+                    let key = match style {
+                        Style::DeepObject => k
+                            .strip_prefix(name)?
+                            .strip_prefix('[')?
+                            .strip_suffix(']')?,
+                        _ => fields.iter().find(|(f, _)| f == k)?.0,
+                    };
+                    Some((key, v.as_str()))
+                })
+                .collect();
+            match pairs.is_empty() {
+                true => Ok(Value::Null),
+                false => object(&pairs, fields),
+            }
+        }
+        Shape::Array(kind) if explode && style == Style::Form => match values.is_empty() {
+            true => Ok(Value::Null),
+            false => values
+                .iter()
+                .map(|v| scalar(v, *kind))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+        },
+        _ => {
+            let separator = match style {
+                Style::SpaceDelimited => ' ',
+                Style::PipeDelimited => '|',
+                _ => ',',
+            };
+            match values.first() {
+                Some(v) => shaped(v, separator, false, shape),
+                None => Ok(Value::Null),
+            }
+        }
+    }
+}
+
+// Value of the parameter with JSON content
+fn json_value(raw: Option<&str>) -> Result<Value, String> {
+    match raw {
+        Some(raw) => serde_json::from_str(raw).map_err(|err| err.to_string()),
+        None => Ok(Value::Null),
+    }
+}
+
+fn json_body(body: &Bytes) -> Result<Value, String> {
+    match body.is_empty() {
+        true => Ok(Value::Null),
+        false => serde_json::from_slice(body).map_err(|err| err.to_string()),
+    }
+}
+
+fn bytes_body(errors: &mut Vec<ValidationError>, body: &Bytes) -> Option<Vec<u8>> {
+    if body.is_empty() {
+        errors.push(ValidationError {
+            place: "body",
+            name: "",
+            message: "required".into(),
+        });
+        return None;
+    }
+    Some(body.to_vec())
+}
+
+fn header<'h>(headers: &'h HeaderMap, name: &str) -> Option<&'h str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn first<'p>(pairs: &'p [(String, String)], name: &str) -> Option<&'p str> {
+    pairs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+}
+
+// Query value (plus is space)
+fn decode(s: &str) -> String {
+    percent_decode(&s.replace('+', " "))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = s.get(i + 1..i + 3).and_then(|v| u8::from_str_radix(v, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                result.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+fn query_pairs(query: Option<&str>) -> Vec<(String, String)> {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let (k, v) = v.split_once('=').unwrap_or((v, ""));
+            (decode(k), decode(v))
+        })
+        .collect()
+}
+
+fn cookie_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|v| v.trim().split_once('='))
+        .map(|(k, v)| (percent_decode(k), percent_decode(v)))
+        .collect()
+}
+
+// Values of the parameters of the path segment with several
+// parameters (e.g. {name}.{ext}). Parameters are matched greedily.
+fn split_segment(segment: Option<&str>, parts: &[Part]) -> Option<Vec<String>> {
+    let segment = segment?;
+    match parts {
+        [] => segment.is_empty().then(Vec::new),
+        [Part::Literal(s), rest @ ..] => split_segment(segment.strip_prefix(s), rest),
+        [Part::Param] => (!segment.is_empty()).then(|| vec![segment.to_string()]),
+        [Part::Param, rest @ ..] => {
+            let [Part::Literal(s), ..] = rest else {
+                return None;
+            };
+            segment
+                .rmatch_indices(s)
+                .filter(|(i, _)| *i > 0)
+                .find_map(|(i, _)| {
+                    let mut values = split_segment(Some(&segment[i..]), rest)?;
+                    values.insert(0, segment[..i].to_string());
+                    Some(values)
+                })
+        }
+    }
+}
+"#;
+
+// Value of the request field
+enum Field<'o, 'a> {
+    Parameters(&'o Parameters<'a>),
+    Body,
+}
+
+impl Generator<'_, '_> {
+    // Source of the service traits and routers of operations
+    pub fn server(&self, operations: &[Operation]) -> String {
+        let mut out = String::from(HEADER);
+        out.push_str(
+            "\n#![allow(clippy::all, deprecated, dead_code, unused_imports, unused_variables)]\n\n",
+        );
+        for import in [
+            "super::operations::*",
+            "super::types::*",
+            "axum::body::Bytes",
+            "axum::extract::Path",
+            "axum::http::header::CONTENT_TYPE",
+            "axum::http::header::COOKIE",
+            "axum::http::HeaderMap",
+            "axum::http::StatusCode",
+            "axum::http::Uri",
+            "axum::response::IntoResponse",
+            "axum::response::Response",
+            "serde::de::DeserializeOwned",
+            "serde::Serialize",
+            "serde_json::Value",
+            "std::collections::HashMap",
+            "std::future::Future",
+            "std::sync::Arc",
+        ] {
+            let _ = writeln!(out, "use {import};");
+        }
+        for op in operations {
+            self.request_struct(&mut out, op);
+            self.response_impl(&mut out, op);
+        }
+
+        // Services in order of first operations
+        let mut services: Vec<(Option<&str>, Vec<&Operation>)> = vec![];
+        for op in operations {
+            let tag = op.operation.tags.first().map(|v| v.as_str());
+            match services.iter_mut().find(|(t, _)| *t == tag) {
+                Some((_, ops)) => ops.push(op),
+                None => services.push((tag, vec![op])),
+            }
+        }
+        for (tag, ops) in services {
+            let service = match tag {
+                Some(tag) => format!("{}Api", upper_camel(tag)),
+                None => "Api".into(),
+            };
+            self.service_trait(&mut out, &service, &ops);
+            router(&mut out, tag, &service, &ops, |op| {
+                field_name(&self.type_name(&op.name))
+            });
+            for op in ops {
+                self.handler(&mut out, &service, op);
+            }
+        }
+        out.push_str(RUNTIME);
+        out
+    }
+
+    // Fields of the request: parameters by location and body
+    fn request_fields<'o, 'a>(&self, op: &'o Operation<'a>) -> Vec<(Field<'o, 'a>, &'static str)> {
+        let mut fields = vec![];
+        for params in op.parameters.iter() {
+            let name = match params.place {
+                Place::Path => "path",
+                Place::Query => "query",
+                Place::Header => "headers",
+                Place::Cookie => "cookies",
+            };
+            fields.push((Field::Parameters(params), name));
+        }
+        if op.request_body.is_some() {
+            fields.push((Field::Body, "body"));
+        }
+        fields
+    }
+
+    fn request_struct(&self, out: &mut String, op: &Operation) {
+        let type_name = self.type_name(&op.name);
+        out.push_str("\n#[derive(Debug, Clone, PartialEq)]\n");
+        let _ = writeln!(out, "pub struct {type_name}Request {{");
+        for (field, name) in self.request_fields(op) {
+            let t = match field {
+                Field::Parameters(params) => self.type_name(&params.name),
+                Field::Body => {
+                    let Some(b) = &op.request_body else {
+                        continue;
+                    };
+                    match b.required {
+                        true => self.body_type(b),
+                        false => format!("Option<{}>", self.body_type(b)),
+                    }
+                }
+            };
+            let _ = writeln!(out, "    pub {name}: {t},");
+        }
+        out.push_str("}\n");
+    }
+
+    fn response_impl(&self, out: &mut String, op: &Operation) {
+        let _ = writeln!(
+            out,
+            "\nimpl IntoResponse for {} {{",
+            response_name(&self.type_name(&op.name))
+        );
+        out.push_str("    fn into_response(self) -> Response {\n");
+        out.push_str("        match self {\n");
+        for resp in op.responses.iter() {
+            let (pattern, code) = match resp.status {
+                Some(HttpStatusCode::Specific(code)) => (vec![], code.code().to_string()),
+                _ => (vec!["status"], "status".to_string()),
+            };
+            let (pattern, value) = match &resp.body {
+                Some(b) => {
+                    let value = match b.simple_type {
+                        Some(_) => format!("json_response({code}, &body)"),
+                        None => format!(
+                            "bytes_response({code}, \"{}\", body)",
+                            escape(&content_type(b))
+                        ),
+                    };
+                    (pattern.into_iter().chain(["body"]).collect(), value)
+                }
+                None => (pattern, format!("empty_response({code})")),
+            };
+            let variant = variant_name(resp.status);
+            match pattern.is_empty() {
+                true => {
+                    let _ = writeln!(out, "            Self::{variant} => {value},");
+                }
+                false => {
+                    let _ = writeln!(
+                        out,
+                        "            Self::{variant}({}) => {value},",
+                        pattern.join(", ")
+                    );
+                }
+            }
+        }
+        out.push_str("        }\n    }\n}\n");
+    }
+
+    fn service_trait(&self, out: &mut String, service: &str, ops: &[&Operation]) {
+        let _ = writeln!(out, "\npub trait {service}: Send + Sync + 'static {{");
+        for (i, op) in ops.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            let compiled = op.operation;
+            if let Some(summary) = compiled.summary {
+                for line in summary.as_str().lines() {
+                    let _ = writeln!(out, "    /// {line}");
+                }
+                out.push_str("    ///\n");
+            }
+            let _ = writeln!(
+                out,
+                "    /// {} {}",
+                compiled.op_type.as_str().to_uppercase(),
+                compiled
+                    .location
+                    .path()
+                    .map(|v| v.as_str())
+                    .unwrap_or_default()
+            );
+            if compiled.deprecated {
+                out.push_str("    #[deprecated]\n");
+            }
+            let type_name = self.type_name(&op.name);
+            let _ = writeln!(
+                out,
+                "    fn {}(&self, request: {type_name}Request) -> impl Future<Output = {}> + Send;",
+                field_name(&type_name),
+                response_name(&type_name)
+            );
+        }
+        out.push_str("}\n");
+    }
+
+    fn handler(&self, out: &mut String, service: &str, op: &Operation) {
+        let type_name = self.type_name(&op.name);
+        let path = template(op);
+        let _ = writeln!(
+            out,
+            "\nasync fn handle_{}<S: {service}>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {{",
+            field_name(&type_name)
+        );
+
+        // Segments with several parameters are split first. Path not
+        // matching the template is left to the next candidate.
+        let mut raw_path = vec![];
+        for (i, segment) in path.split('/').enumerate() {
+            let parts = split_template(segment);
+            let params: Vec<&str> = parts
+                .iter()
+                .filter_map(|part| match part {
+                    Token::Name(name) => Some(*name),
+                    Token::Literal(_) => None,
+                })
+                .collect();
+            match parts.as_slice() {
+                [] | [Token::Literal(_)] => {}
+                [Token::Name(name)] => {
+                    raw_path.push((*name, format!("params.get(\"p{i}\").map(String::as_str)")))
+                }
+                _ => {
+                    let parts: Vec<String> = parts
+                        .iter()
+                        .map(|part| match part {
+                            Token::Literal(s) => format!("Part::Literal(\"{}\")", escape(s)),
+                            Token::Name(_) => "Part::Param".into(),
+                        })
+                        .collect();
+                    let _ = writeln!(
+                        out,
+                        "    let Some(s{i}) = split_segment(params.get(\"p{i}\").map(String::as_str), &[{}]) else {{",
+                        parts.join(", ")
+                    );
+                    out.push_str("        return None;\n    };\n");
+                    for (k, name) in params.iter().enumerate() {
+                        raw_path.push((name, format!("s{i}.get({k}).map(String::as_str)")));
+                    }
+                }
+            }
+        }
+        let places: Vec<Place> = op.parameters.iter().map(|params| params.place).collect();
+        if places.contains(&Place::Query) {
+            out.push_str("    let query_pairs = query_pairs(uri.query());\n");
+        }
+        if places.contains(&Place::Cookie) {
+            out.push_str("    let cookies = cookie_pairs(&headers);\n");
+        }
+
+        // Values are checked and then moved to the request
+        let fields = self.request_fields(op);
+        let mut values = vec![];
+        let mut checks = vec![];
+        for (field, _) in fields.iter() {
+            match field {
+                Field::Parameters(params) => {
+                    let mut members = vec![];
+                    for (name, p) in field_names(params).iter().zip(&params.parameters) {
+                        let raw = match p.place {
+                            Place::Path => raw_path
+                                .iter()
+                                .find(|(name, _)| *name == p.name.as_str())
+                                .map(|(_, raw)| raw.clone())
+                                .unwrap_or_else(|| "None".into()),
+                            _ => self.raw_value(p),
+                        };
+                        checks.push(self.check(p, &raw));
+                        members.push(format!("{name}: v{}", checks.len() - 1));
+                    }
+                    values.push(format!(
+                        "{} {{ {} }}",
+                        self.type_name(&params.name),
+                        members.join(", ")
+                    ));
+                }
+                Field::Body => {
+                    let Some(b) = &op.request_body else {
+                        continue;
+                    };
+                    checks.push(match (&b.simple_type, b.required) {
+                        (Some(_), required) => {
+                            format!(
+                                "check(&mut errors, \"body\", \"\", {required}, json_body(&body))"
+                            )
+                        }
+                        (None, true) => "bytes_body(&mut errors, &body)".into(),
+                        (None, false) => "Some((!body.is_empty()).then(|| body.to_vec()))".into(),
+                    });
+                    values.push(format!("v{}", checks.len() - 1));
+                }
+            }
+        }
+        if !checks.is_empty() {
+            out.push_str("    let mut errors = vec![];\n");
+            for (i, check) in checks.iter().enumerate() {
+                let _ = writeln!(out, "    let v{i} = {check};");
+            }
+            let vars: Vec<String> = (0..checks.len()).map(|i| format!("v{i}")).collect();
+            let somes: Vec<String> = vars.iter().map(|v| format!("Some({v})")).collect();
+            let comma = if vars.len() == 1 { "," } else { "" };
+            let _ = writeln!(
+                out,
+                "    let ({}{comma}) = ({}{comma}) else {{",
+                somes.join(", "),
+                vars.join(", ")
+            );
+            out.push_str("        return Some(bad_request(errors));\n    };\n");
+        }
+        let _ = writeln!(out, "    let request = {type_name}Request {{");
+        for ((_, name), value) in fields.iter().zip(&values) {
+            let _ = writeln!(out, "        {name}: {value},");
+        }
+        out.push_str("    };\n");
+        let _ = writeln!(
+            out,
+            "    Some(service.{}(request).await.into_response())",
+            field_name(&type_name)
+        );
+        out.push_str("}\n");
+    }
+
+    // Raw value of the parameter (not in path). Query and cookie
+    // parameters not serialized as JSON are decoded from pairs.
+    fn raw_value(&self, p: &Parameter) -> String {
+        let name = escape(p.name.as_str());
+        match p.place {
+            Place::Header => format!("header(&headers, \"{name}\")"),
+            Place::Query => format!("first(&query_pairs, \"{name}\")"),
+            Place::Cookie => format!("first(&cookies, \"{name}\")"),
+            Place::Path => "None".into(),
+        }
+    }
+
+    fn check(&self, p: &Parameter, raw: &str) -> String {
+        let name = escape(p.name.as_str());
+        let place = match p.place {
+            Place::Path => "path",
+            Place::Query => "query",
+            Place::Header => "header",
+            Place::Cookie => "cookie",
+        };
+        let shape = self.shape(&p.simple_type);
+        let value = match (p.json, p.place) {
+            (true, _) => format!("json_value({raw})"),
+            (false, Place::Query) => format!(
+                "form_value(&query_pairs, \"{name}\", {}, {}, &{shape})",
+                style(p.style),
+                p.explode
+            ),
+            (false, Place::Cookie) => format!(
+                "form_value(&cookies, \"{name}\", Style::Form, {}, &{shape})",
+                p.explode
+            ),
+            (false, _) => format!(
+                "simple_value({raw}, \"{name}\", {}, {}, &{shape})",
+                style(p.style),
+                p.explode
+            ),
+        };
+        // Defaults ignored by the parameter type are ignored here too
+        let value = match (&p.default, self.parameter_default(p)) {
+            (Some(v), Some(_)) => format!("with_default({value}, \"{}\")", escape(&v.to_string())),
+            _ => value,
+        };
+        format!(
+            "check(&mut errors, \"{place}\", \"{name}\", {}, {value})",
+            p.required
+        )
+    }
+
+    // Shape of the parameter value
+    fn shape(&self, t: &SimpleType) -> String {
+        match t {
+            SimpleType::Normal(NormalType::Array(items))
+            | SimpleType::Nullable(NullableType::Array(items)) => {
+                format!("Shape::Array({})", self.kind(items))
+            }
+            SimpleType::Normal(NormalType::Object(name))
+            | SimpleType::Nullable(NullableType::Object(name))
+            | SimpleType::Named(name) => match self.model(name) {
+                Some(Model::Object(object)) => {
+                    let fields: Vec<String> = object
+                        .properties
+                        .iter()
+                        .map(|(name, property)| {
+                            format!(
+                                "(\"{}\", {})",
+                                escape(name.as_str()),
+                                self.kind(&property.simple_type)
+                            )
+                        })
+                        .collect();
+                    format!("Shape::Object(&[{}])", fields.join(", "))
+                }
+                Some(Model::Alias(t, _)) => self.shape(t),
+                _ => format!("Shape::Scalar({})", self.kind(t)),
+            },
+            t => format!("Shape::Scalar({})", self.kind(t)),
+        }
+    }
+
+    // Kind of the scalar value
+    fn kind(&self, t: &SimpleType) -> &'static str {
+        match t {
+            SimpleType::Normal(NormalType::Boolean(_))
+            | SimpleType::Nullable(NullableType::Boolean(_)) => "Kind::Boolean",
+            SimpleType::Normal(NormalType::Integer(_))
+            | SimpleType::Nullable(NullableType::Integer(_)) => "Kind::Integer",
+            SimpleType::Normal(NormalType::Number(_))
+            | SimpleType::Nullable(NullableType::Number(_)) => "Kind::Number",
+            SimpleType::Normal(NormalType::String(_) | NormalType::Enum(_))
+            | SimpleType::Nullable(NullableType::String(_) | NullableType::Enum(_)) => {
+                "Kind::String"
+            }
+            SimpleType::RustType("String") => "Kind::String",
+            SimpleType::Named(name) => match self.model(name) {
+                Some(Model::Enum(_)) => "Kind::String",
+                Some(Model::Alias(t, _)) => self.kind(t),
+                _ => "Kind::Json",
+            },
+            _ => "Kind::Json",
+        }
+    }
+}
+
+// Operations of the route by method
+type Methods<'o, 'a> = Vec<(&'o str, Vec<&'o Operation<'a>>)>;
+
+// Router of the service. Segments with parameters are captured as p
+// and index of the segment. Templates different only in segments with
+// several parameters share the route, so their handlers are tried in
+// order of precedence (see compile::router).
+fn router(
+    out: &mut String,
+    tag: Option<&str>,
+    service: &str,
+    ops: &[&Operation],
+    method_name: impl Fn(&Operation) -> String,
+) {
+    let mut routes: Vec<(String, Methods)> = vec![];
+    for op in ops {
+        let route = route(template(op));
+        let method = op.operation.op_type.as_str();
+        let position = match routes.iter().position(|(r, _)| *r == route) {
+            Some(position) => position,
+            None => {
+                routes.push((route, vec![]));
+                routes.len() - 1
+            }
+        };
+        let methods = &mut routes[position].1;
+        match methods.iter_mut().find(|(m, _)| *m == method) {
+            Some((_, candidates)) => candidates.push(op),
+            None => methods.push((method, vec![op])),
+        }
+    }
+    let name = match tag {
+        Some(tag) => format!("{}_router", field_name(tag)),
+        None => "router".into(),
+    };
+    let _ = writeln!(
+        out,
+        "\n// Router serving operations of the service\npub fn {name}<S: {service}>(service: S) -> axum::Router {{"
+    );
+    out.push_str("    let service = Arc::new(service);\n");
+    out.push_str("    axum::Router::new()");
+    for (route, methods) in routes {
+        let has_params = route.contains("{p");
+        let _ = write!(
+            out,
+            "\n        .route(\n            \"{}\",\n            ",
+            escape(&route)
+        );
+        for (i, (method, mut candidates)) in methods.into_iter().enumerate() {
+            let prefix = match i {
+                0 => "axum::routing::",
+                _ => "\n            .",
+            };
+            let (extractor, params) = match has_params {
+                true => ("Path(params): Path<HashMap<String, String>>, ", "&params"),
+                false => ("", "&HashMap::new()"),
+            };
+            let _ = write!(
+                out,
+                "{prefix}{method}({{\n                let service = service.clone();\n                move |{extractor}uri: Uri, headers: HeaderMap, body: Bytes| async move {{\n"
+            );
+            // Sort is stable, so candidates of the same precedence
+            // keep the order of operations
+            candidates.sort_by_cached_key(|op| {
+                let path = op.operation.location.path();
+                let segments = path.and_then(|path| router::parse(path).ok());
+                std::cmp::Reverse(segments.map(|segments| router::rank(&segments)))
+            });
+            for op in candidates {
+                let _ = write!(
+                    out,
+                    "                    if let Some(response) = handle_{}(&*service, {params}, &uri, &headers, &body).await {{\n                        return response;\n                    }}\n",
+                    method_name(op)
+                );
+            }
+            out.push_str("                    not_found()\n                }\n            })");
+        }
+        out.push_str(",\n        )");
+    }
+    out.push_str("\n}\n");
+}
+
+// Path template of the operation
+fn template<'o>(op: &'o Operation) -> &'o str {
+    op.operation
+        .location
+        .path()
+        .map(|v| v.as_str())
+        .unwrap_or_default()
+}
+
+// Path of axum route (0.8 syntax)
+fn route(path: &str) -> String {
+    let segments: Vec<String> = path
+        .split('/')
+        .enumerate()
+        .map(|(i, segment)| match segment.contains('{') {
+            true => format!("{{p{i}}}"),
+            false => segment.to_string(),
+        })
+        .collect();
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codegen::rust::generate;
+    use crate::codegen::rust::Options;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    #[test]
+    fn server() {
+        let spec = include_str!("../../../test/test-vectors/client.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = model::operation::build(&compiled, &mut models).unwrap();
+        let options = Options {
+            client: false,
+            server: true,
+        };
+        let files = generate(&models, &operations, options);
+        assert_eq!(
+            files[0].contents,
+            format!("{HEADER}\npub mod operations;\npub mod server;\npub mod types;\n")
+        );
+        assert_eq!(files[3].path, "server.rs");
+        let expected = include_str!("../../../test/test-vectors/codegen-server.rs");
+        assert_eq!(files[3].contents, expected);
+        assert_eq!(
+            route("/pets/{petId}/photos/{name}.{ext}"),
+            "/pets/{p2}/photos/{p4}"
+        );
+    }
+
+    // Templates different only in segments with several parameters
+    // share the route
+    #[test]
+    fn shared_routes() {
+        let spec = include_str!("../../../test/test-vectors/server-routes.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = model::operation::build(&compiled, &mut models).unwrap();
+        let options = Options {
+            client: false,
+            server: true,
+        };
+        let files = generate(&models, &operations, options);
+        let expected = include_str!("../../../test/test-vectors/codegen-server-routes.rs");
+        assert_eq!(files[3].contents, expected);
+    }
+}
@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: MIT
+//
+// Validation keywords of the schema object
+// collected into a single constraint set.
+//
+
+use crate::schema::data_type::array::Array;
+use crate::schema::data_type::numerical;
+use crate::schema::data_type::numerical::Numerical;
+use crate::schema::data_type::object::Ecma262RegEx;
+use crate::schema::data_type::object::Object;
+use crate::schema::data_type::MaybeNullableTypeSchema;
+use crate::schema::data_type::NullableTypeSchema;
+use crate::schema::data_type::StringType;
+use crate::schema::data_type::TypeSchema;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Constraints<'a> {
+    // Strings
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    pub pattern: Option<&'a Ecma262RegEx>,
+    // Integers and numbers. Values are converted to f64 so
+    // integers above 2^53 are not exact.
+    pub minimum: Option<Bound>,
+    pub maximum: Option<Bound>,
+    pub multiple_of: Option<f64>,
+    // Arrays
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+    pub unique_items: bool,
+    // Objects
+    pub min_properties: Option<u64>,
+    pub max_properties: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bound {
+    pub value: f64,
+    pub exclusive: bool,
+}
+
+// Combination of constraints no value can satisfy
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // minLength, maxLength
+    Length(u64, u64),
+    // minimum, maximum
+    Range(f64, f64),
+    MultipleOf(f64),
+    // minItems, maxItems
+    Items(u64, u64),
+    // minProperties, maxProperties
+    Properties(u64, u64),
+}
+
+impl<'a> Constraints<'a> {
+    pub fn new(ts: &'a MaybeNullableTypeSchema) -> Self {
+        match ts {
+            MaybeNullableTypeSchema::Nullable(proxy) => match &proxy.schema {
+                NullableTypeSchema::Null | NullableTypeSchema::Boolean(_) => Self::default(),
+                NullableTypeSchema::Object(obj) => Self::object(obj),
+                NullableTypeSchema::Array(arr) => Self::array(arr),
+                NullableTypeSchema::Integer(v) => match v {
+                    numerical::NullableIntegerType::WithFormat(v) => match v {
+                        numerical::NullableIntegerWithFormat::Int32(v) => Self::numerical(v),
+                        numerical::NullableIntegerWithFormat::Int64(v) => Self::numerical(v),
+                    },
+                    numerical::NullableIntegerType::WithOutFormat(v) => Self::numerical(v),
+                },
+                NullableTypeSchema::Number(v) => match v {
+                    numerical::NullableNumberType::WithFormat(v) => match v {
+                        numerical::NullableNumberWithFormat::Float(v) => Self::numerical(v),
+                        numerical::NullableNumberWithFormat::Double(v) => Self::numerical(v),
+                    },
+                    numerical::NullableNumberType::WithOutFormat(v) => Self::numerical(v),
+                },
+                NullableTypeSchema::String(v) => Self::string(v),
+            },
+            MaybeNullableTypeSchema::Normal(ts) => match ts {
+                TypeSchema::Null | TypeSchema::Boolean(_) => Self::default(),
+                TypeSchema::Object(obj) => Self::object(obj),
+                TypeSchema::Array(arr) => Self::array(arr),
+                TypeSchema::Integer(v) => match v {
+                    numerical::IntegerType::WithFormat(v) => match v {
+                        numerical::IntegerWithFormat::Int32(v) => Self::numerical(v),
+                        numerical::IntegerWithFormat::Int64(v) => Self::numerical(v),
+                    },
+                    numerical::IntegerType::WithOutFormat(v) => Self::numerical(v),
+                },
+                TypeSchema::Number(v) => match v {
+                    numerical::NumberType::WithFormat(v) => match v {
+                        numerical::NumberWithFormat::Float(v) => Self::numerical(v),
+                        numerical::NumberWithFormat::Double(v) => Self::numerical(v),
+                    },
+                    numerical::NumberType::WithOutFormat(v) => Self::numerical(v),
+                },
+                TypeSchema::String(v) => Self::string(v),
+            },
+            MaybeNullableTypeSchema::Object(obj) => Self::object(obj),
+            MaybeNullableTypeSchema::Array(arr) => Self::array(arr),
+        }
+    }
+
+    fn string<D>(s: &'a StringType<D>) -> Self {
+        Self {
+            min_length: s.min_length,
+            max_length: s.max_length,
+            pattern: s.pattern.as_ref(),
+            ..Self::default()
+        }
+    }
+
+    fn numerical<T: Into<Float> + Copy, D>(n: &'a Numerical<T, D>) -> Self {
+        let bound = |value: T, exclusive| Bound {
+            value: value.into().0,
+            exclusive,
+        };
+        Self {
+            minimum: n.minimum.map(|v| bound(v, n.exclisive_minimum)),
+            maximum: n.maximum.map(|v| bound(v, n.exclisive_maximum)),
+            multiple_of: n.multiple_of.map(|v| v.into().0),
+            ..Self::default()
+        }
+    }
+
+    fn array(arr: &'a Array) -> Self {
+        Self {
+            min_items: arr.min_items,
+            max_items: arr.max_items,
+            unique_items: arr.unique_items,
+            ..Self::default()
+        }
+    }
+
+    fn object(obj: &'a Object) -> Self {
+        Self {
+            min_properties: obj.min_properties,
+            max_properties: obj.max_properties,
+            ..Self::default()
+        }
+    }
+
+    // Check that at least one value may satisfy constraints
+    pub fn check(&self) -> Result<(), Error> {
+        if let (Some(min), Some(max)) = (self.min_length, self.max_length) {
+            if min > max {
+                return Err(Error::Length(min, max));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.minimum, self.maximum) {
+            let empty = min.value > max.value
+                || (min.value == max.value && (min.exclusive || max.exclusive));
+            if empty {
+                return Err(Error::Range(min.value, max.value));
+            }
+        }
+        if let Some(multiple_of) = self.multiple_of {
+            if multiple_of <= 0.0 || !multiple_of.is_finite() {
+                return Err(Error::MultipleOf(multiple_of));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_items, self.max_items) {
+            if min > max {
+                return Err(Error::Items(min, max));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_properties, self.max_properties) {
+            if min > max {
+                return Err(Error::Properties(min, max));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Conversion of numerical schema values (i64 can't be converted
+// to f64 with From)
+struct Float(f64);
+
+impl From<i32> for Float {
+    fn from(v: i32) -> Self {
+        Self(v.into())
+    }
+}
+
+impl From<i64> for Float {
+    fn from(v: i64) -> Self {
+        Self(v as f64)
+    }
+}
+
+impl From<f32> for Float {
+    fn from(v: f32) -> Self {
+        Self(v.into())
+    }
+}
+
+impl From<f64> for Float {
+    fn from(v: f64) -> Self {
+        Self(v)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Length(min, max) => write!(f, "minLength {min} is greater than maxLength {max}"),
+            Self::Range(min, max) => write!(f, "range from {min} to {max} is empty"),
+            Self::MultipleOf(v) => write!(f, "multipleOf {v} must be greater than 0"),
+            Self::Items(min, max) => write!(f, "minItems {min} is greater than maxItems {max}"),
+            Self::Properties(min, max) => {
+                write!(f, "minProperties {min} is greater than maxProperties {max}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::data_type::DataType;
+
+    fn constraints(s: &str) -> Result<(), Error> {
+        let DataType::ActualType(at) = serde_yaml::from_str(s).unwrap() else {
+            panic!("not an actual type: {s}")
+        };
+        Constraints::new(&at.type_schema).check()
+    }
+
+    #[test]
+    fn impossible_combinations() {
+        assert_eq!(
+            constraints("{type: string, minLength: 3, maxLength: 10, pattern: '^[a-z]+$'}"),
+            Ok(())
+        );
+        assert_eq!(
+            constraints("{type: string, minLength: 10, maxLength: 3}"),
+            Err(Error::Length(10, 3))
+        );
+        assert_eq!(
+            constraints("{type: integer, format: int64, minimum: 5, maximum: 1}"),
+            Err(Error::Range(5.0, 1.0))
+        );
+        assert_eq!(
+            constraints("{type: number, minimum: 1, maximum: 1, exclusiveMaximum: true}"),
+            Err(Error::Range(1.0, 1.0))
+        );
+        assert_eq!(
+            constraints("{type: integer, multipleOf: 0}"),
+            Err(Error::MultipleOf(0.0))
+        );
+        assert_eq!(
+            constraints("{type: array, items: {type: string}, minItems: 2, maxItems: 1}"),
+            Err(Error::Items(2, 1))
+        );
+        assert_eq!(
+            constraints("{type: object, nullable: true, minProperties: 2, maxProperties: 1}"),
+            Err(Error::Properties(2, 1))
+        );
+    }
+}
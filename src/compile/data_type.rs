@@ -4,19 +4,24 @@
 // with all required schemas with it.
 //
 
+use crate::compile::constraints::Constraints;
 use crate::compile::Schemas;
 use crate::schema::data_type::default::NonNullableDefault;
 use crate::schema::data_type::default::NullableDefault;
 use crate::schema::data_type::numerical;
 use crate::schema::data_type::ActualType as SchemaActualType;
+use crate::schema::data_type::Annotations;
 use crate::schema::data_type::BooleanType;
 use crate::schema::data_type::StringType;
+use crate::schema::discriminator::Discriminator;
+use crate::schema::extensions::Extensions;
 use crate::schema::sref::SRefSchemasObjectName;
 use crate::schema::PropertyName;
+use serde_json::Value;
 
 #[derive(Debug)]
 pub enum DataType<'a> {
-    ActualType(ActualType<'a>),
+    ActualType(Box<ActualType<'a>>),
     OneOf(OneOfType<'a>),
     AllOf(AllOfType<'a>),
     AnyOf(AnyOfType<'a>),
@@ -33,16 +38,21 @@ pub enum TypeOrSchemaRef<'a> {
 #[derive(Debug)]
 pub struct OneOfType<'a> {
     pub one_of: Vec<TypeOrSchemaRef<'a>>,
+    pub discriminator: Option<&'a Discriminator>,
+    pub annotations: &'a Annotations,
 }
 
 #[derive(Debug)]
 pub struct AllOfType<'a> {
     pub all_of: Vec<TypeOrSchemaRef<'a>>,
+    pub annotations: &'a Annotations,
 }
 
 #[derive(Debug)]
 pub struct AnyOfType<'a> {
     pub any_of: Vec<TypeOrSchemaRef<'a>>,
+    pub discriminator: Option<&'a Discriminator>,
+    pub annotations: &'a Annotations,
 }
 
 #[derive(Debug)]
@@ -50,6 +60,24 @@ pub struct ActualType<'a> {
     pub compiled_type: CompiledType<'a>,
     pub readonly: bool,
     pub writeonly: bool,
+    pub default: Option<Value>,
+    pub annotations: &'a Annotations,
+    pub constraints: Constraints<'a>,
+    pub extensions: &'a Extensions,
+}
+
+impl<'a> ActualType<'a> {
+    pub fn new(at: &'a SchemaActualType, compiled_type: CompiledType<'a>) -> Self {
+        Self {
+            compiled_type,
+            readonly: at.readonly,
+            writeonly: at.writeonly,
+            default: at.default_value(),
+            annotations: &at.annotations,
+            constraints: Constraints::new(&at.type_schema),
+            extensions: &at.extensions,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -82,12 +110,15 @@ pub enum NormalCompiledType<'a> {
 #[derive(Debug, Default)]
 pub struct CompiledObject<'a> {
     pub properties: indexmap::IndexMap<PropertyName, TypeOrSchemaRef<'a>>,
+    pub required: Vec<&'a PropertyName>,
+    // Not defined additional properties are allowed
+    pub additional_properties: Option<CompiledAdditionalProperties<'a>>,
 }
 
 #[derive(Debug)]
 pub enum CompiledAdditionalProperties<'a> {
     Bool(bool),
-    Schema(DataType<'a>),
+    Schema(Box<TypeOrSchemaRef<'a>>),
 }
 
 #[derive(Debug)]
@@ -105,11 +136,9 @@ impl<'a> DataTypeWithSchema<'a> {
     pub fn actual_type(at: &'a SchemaActualType, compiled_type: CompiledType<'a>) -> Self {
         Self {
             schemas: Schemas::default(),
-            type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(ActualType {
-                readonly: at.readonly,
-                writeonly: at.writeonly,
-                compiled_type,
-            })),
+            type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(Box::new(
+                ActualType::new(at, compiled_type),
+            ))),
         }
     }
 }
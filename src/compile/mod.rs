@@ -4,20 +4,31 @@
 //
 // This operation collects all operations
 // required to be generated and all types those
-// operations depends on.
+// operations depends on. Operations marked with x-ignore
+// are skipped. Callbacks of operations and webhooks are
+// compiled the same way as ordinary operations.
 //
 
+pub mod constraints;
 pub mod data_type;
 pub mod operation;
+pub mod router;
 pub mod schema_compiler;
 pub mod stack;
 
 use crate::compile::data_type::DataType;
+use crate::compile::operation::link::Target;
 use crate::compile::operation::request_body::RequestBody;
 use crate::compile::operation::response_body::ResponseBody;
+use crate::compile::operation::response_body::ResponseBodyOrReference;
+use crate::compile::operation::Location;
 use crate::compile::operation::Operation;
 use crate::compile::stack::Stack;
 use crate::schema;
+use crate::schema::callback::CallbackOrReference;
+use crate::schema::operation::Operation as SchemaOperation;
+use crate::schema::path_item::OperationType;
+use crate::schema::path_item::PathItem;
 use crate::schema::sref::SRefRequestBody;
 use crate::schema::sref::SRefResponsesName;
 use crate::schema::sref::SRefSchemasObjectName;
@@ -32,50 +43,372 @@ pub struct Compiled<'a> {
     pub response_bodies: ResponseBodies<'a>,
     pub schemas: Schemas<'a>,
     pub operations: Vec<Operation<'a>>,
+    // Operations those are initiated by API provider
+    pub callbacks: Vec<Operation<'a>>,
+    pub webhooks: Vec<Operation<'a>>,
 }
 
 type CResult<'a, T> = Result<T, operation::Error<'a>>;
 
-pub fn compile(d: &schema::Description) -> CResult<Compiled> {
-    let mut schema_chain = Stack::default();
-    let mut request_bodies = RequestBodies::default();
-    let mut response_bodies = ResponseBodies::default();
-    let operations = d
-        .paths
-        .as_ref()
-        .map(|paths| {
-            Ok(paths
-                .iter()
-                .map(|(path, item)| -> CResult<Vec<Operation>> {
-                    item.operations_iter()
-                        .map(|(op_type, op)| {
-                            let cdata = operation::CompileData {
-                                path,
-                                item,
-                                op,
-                                components: &d.components,
-                                schema_chain: &schema_chain,
-                                request_bodies: &request_bodies,
-                                response_bodies: &response_bodies,
-                            };
-                            let opr = cdata.compile_operation(op_type)?;
-                            schema_chain.merge(opr.schemas);
-                            request_bodies.extend(opr.request_bodies);
-                            response_bodies.extend(opr.response_bodies);
-                            Ok(opr.op)
-                        })
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten()
-                .collect())
-        })
-        .unwrap_or(Ok(vec![]))?;
-    Ok(Compiled {
-        response_bodies,
-        request_bodies,
-        schemas: schema_chain.done(),
+pub fn compile(d: &schema::Description) -> CResult<'_, Compiled<'_>> {
+    let mut collector = Collector::default();
+    let mut operations = vec![];
+    let mut callbacks = vec![];
+    for (path, item) in d.paths.iter().flatten() {
+        operations.extend(collector.compile_item(d, item, Location::Path(path))?);
+        for (op_type, op) in item.operations_iter() {
+            let location = Location::Path(path);
+            if is_ignored(location, op_type, op)? {
+                continue;
+            }
+            for (name, callback) in op.callbacks.iter().flatten() {
+                let callback = match callback {
+                    CallbackOrReference::Callback(callback) => callback,
+                    CallbackOrReference::Reference(r) => r
+                        .sref
+                        .callback_sref()
+                        .and_then(|sref| d.components.as_ref()?.find_callback(&sref))
+                        .ok_or(operation::Error::WrongCallbackReference(location, r))?,
+                };
+                for (expression, item) in callback.expressions.iter() {
+                    let location = Location::Callback(path, op_type, name, expression);
+                    callbacks.extend(collector.compile_item(d, item, location)?);
+                }
+            }
+        }
+    }
+    let mut webhooks = vec![];
+    for (name, item) in d.webhooks.iter().flatten() {
+        webhooks.extend(collector.compile_item(d, item, Location::Webhook(name))?);
+    }
+    let compiled = Compiled {
+        response_bodies: collector.response_bodies,
+        request_bodies: collector.request_bodies,
+        schemas: collector.schema_chain.done(),
         operations,
-    })
+        callbacks,
+        webhooks,
+    };
+    check_links(&compiled)?;
+    Ok(compiled)
+}
+
+// Links of responses must refer to compiled operations
+fn check_links<'a>(compiled: &Compiled<'a>) -> CResult<'a, ()> {
+    let all = || {
+        compiled
+            .operations
+            .iter()
+            .chain(&compiled.callbacks)
+            .chain(&compiled.webhooks)
+    };
+    for op in all() {
+        let responses = &op.request_responses;
+        for resp in responses.default.iter().chain(responses.codes.values()) {
+            let body = match resp {
+                ResponseBodyOrReference::Body(body) => Some(body),
+                ResponseBodyOrReference::Reference(sref) => compiled.response_bodies.get(sref),
+            };
+            for link in body.iter().flat_map(|body| body.links.iter()) {
+                let exists = match &link.target {
+                    Target::OperationId(id) => all().any(|op| op.operation_id == Some(*id)),
+                    Target::Operation(_, path, op_type) => compiled
+                        .operations
+                        .iter()
+                        .any(|op| op.location == Location::Path(path) && op.op_type == *op_type),
+                    Target::External(_) => true,
+                };
+                if !exists {
+                    return Err(operation::Error::LinkTarget(
+                        op.location,
+                        op.op_type,
+                        link.name,
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn is_ignored<'a>(
+    location: Location<'a>,
+    op_type: &'static OperationType,
+    op: &'a SchemaOperation,
+) -> CResult<'a, bool> {
+    op.extensions
+        .ignore()
+        .map_err(|err| operation::Error::Extension(location, op_type, err))
+}
+
+// Operations share schemas, request and response bodies those are
+// collected during compilation.
+#[derive(Default)]
+struct Collector<'a> {
+    schema_chain: Stack<'a, 'a>,
+    request_bodies: RequestBodies<'a>,
+    response_bodies: ResponseBodies<'a>,
+}
+
+impl<'a> Collector<'a> {
+    fn compile_item(
+        &mut self,
+        d: &'a schema::Description,
+        item: &'a PathItem,
+        location: Location<'a>,
+    ) -> CResult<'a, Vec<Operation<'a>>> {
+        let mut operations = vec![];
+        for (op_type, op) in item.operations_iter() {
+            if is_ignored(location, op_type, op)? {
+                continue;
+            }
+            let cdata = operation::CompileData {
+                location,
+                item,
+                op,
+                components: &d.components,
+                security: &d.security,
+                servers: &d.servers,
+                schema_chain: &self.schema_chain,
+                request_bodies: &self.request_bodies,
+                response_bodies: &self.response_bodies,
+            };
+            let opr = cdata.compile_operation(op_type)?;
+            self.schema_chain.merge(opr.schemas);
+            self.request_bodies.extend(opr.request_bodies);
+            self.response_bodies.extend(opr.response_bodies);
+            operations.push(opr.op);
+        }
+        Ok(operations)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile::data_type::CompiledType;
+    use crate::compile::data_type::NormalCompiledType;
+    use crate::compile::data_type::TypeOrSchemaRef;
+    use crate::compile::operation::response_body::ResponseBodyOrReference;
+
+    fn normal<'t, 'a>(t: &'t TypeOrSchemaRef<'a>) -> &'t NormalCompiledType<'a> {
+        match t {
+            TypeOrSchemaRef::DataType(DataType::ActualType(at)) => match &at.compiled_type {
+                CompiledType::Normal(t) => t,
+                _ => panic!("{t:?} is not normal type"),
+            },
+            _ => panic!("{t:?} is not normal type"),
+        }
+    }
+
+    // Object and array schemas with explicit type are parsed as normal
+    // types (keeping the type keyword) and compiled as their untyped
+    // equivalents
+    #[test]
+    fn typed_object_and_array() {
+        let spec = include_str!("../../test/test-vectors/typed-schemas.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+
+        let Some(ResponseBodyOrReference::Body(body)) = compiled.operations[0]
+            .request_responses
+            .codes
+            .values()
+            .next()
+        else {
+            panic!("response body is not compiled")
+        };
+        let NormalCompiledType::Array(pets) = normal(body.json_type_or_ref.as_ref().unwrap())
+        else {
+            panic!("response body is not array")
+        };
+        assert!(matches!(*pets.items, TypeOrSchemaRef::Reference(_)));
+
+        let Some(DataType::ActualType(pet)) = compiled.schemas.values().next() else {
+            panic!("Pet is not compiled")
+        };
+        let CompiledType::Normal(NormalCompiledType::Object(pet)) = &pet.compiled_type else {
+            panic!("Pet is not object")
+        };
+        let tags = &pet.properties[1];
+        assert!(matches!(normal(tags), NormalCompiledType::Array(_)));
+    }
+
+    // Path parameters (and schemas they reference) are compiled in
+    // order of the template
+    #[test]
+    fn path_params_order() {
+        let spec = include_str!("../../test/test-vectors/path-params.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let params: Vec<&str> = compiled.operations[0]
+            .path_params
+            .keys()
+            .map(|v| v.as_str())
+            .collect();
+        assert_eq!(params, ["region", "zone", "rack", "slot"]);
+        let schemas: Vec<&str> = compiled.schemas.keys().map(|v| v.as_str()).collect();
+        assert_eq!(schemas, ["Region", "Zone", "Rack", "Slot"]);
+    }
+
+    #[test]
+    fn callbacks_and_webhooks() {
+        let spec = include_str!("../../test/test-vectors/callbacks.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        assert_eq!(compiled.operations.len(), 1);
+
+        let locations: Vec<String> = compiled
+            .callbacks
+            .iter()
+            .map(|op| op.location.to_string())
+            .collect();
+        assert_eq!(
+            locations,
+            [
+                "/subscriptions Post callback onEvent {$request.body#/callbackUrl}",
+                "/subscriptions Post callback onCancel {$request.body#/callbackUrl}/cancel"
+            ]
+        );
+        assert_eq!(compiled.callbacks[1].op_type, &OperationType::Delete);
+
+        assert_eq!(compiled.webhooks.len(), 1);
+        assert!(matches!(
+            compiled.webhooks[0].location,
+            Location::Webhook(name) if name.as_str() == "newPet"
+        ));
+        let schemas: Vec<&str> = compiled.schemas.keys().map(|v| v.as_str()).collect();
+        assert_eq!(schemas, ["Event", "Pet"]);
+    }
+
+    #[test]
+    fn links() {
+        use crate::compile::operation::link::Value;
+        use crate::schema::runtime_expression::Expression;
+
+        let spec = include_str!("../../test/test-vectors/links.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let responses = &compiled.operations[0].request_responses;
+        let Some(ResponseBodyOrReference::Body(body)) = responses.codes.values().next() else {
+            panic!("response body is not compiled")
+        };
+        let names: Vec<&str> = body.links.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["GetPet", "DeletePet", "Owner"]);
+        assert!(matches!(
+            body.links[0].parameters[0].1,
+            Value::Expression(Expression::Response(_))
+        ));
+        assert!(matches!(body.links[0].parameters[1].1, Value::Constant(_)));
+        assert!(matches!(
+            body.links[1].target,
+            Target::Operation(_, _, OperationType::Delete)
+        ));
+        assert!(matches!(
+            &body.links[2].parameters[0].1,
+            Value::Template(parts) if parts.len() == 3
+        ));
+
+        let spec = spec.replace(
+            "get:\n      operationId: getPet",
+            "get:\n      operationId: findPet",
+        );
+        let description: schema::Description = serde_yaml::from_str(&spec).unwrap();
+        assert!(matches!(
+            compile(&description),
+            Err(operation::Error::LinkTarget(_, _, name)) if name.as_str() == "GetPet"
+        ));
+    }
+
+    #[test]
+    fn operation_metadata() {
+        let spec = include_str!("../../test/test-vectors/metadata.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let tags: Vec<&str> = description
+            .tags
+            .iter()
+            .flatten()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(tags, ["pets", "admin"]);
+
+        let compiled = compile(&description).unwrap();
+        let get = &compiled.operations[0];
+        assert_eq!(get.op_type, &OperationType::Get);
+        assert_eq!(get.summary.map(|v| v.as_str()), Some("List pets"));
+        assert!(!get.deprecated);
+        assert!(get.servers.is_empty());
+
+        let delete = &compiled.operations[1];
+        let tags: Vec<&str> = delete.tags.iter().map(|t| t.as_str()).collect();
+        assert_eq!(tags, ["pets", "admin"]);
+        assert!(delete.deprecated);
+        assert_eq!(delete.servers.len(), 1);
+    }
+
+    #[test]
+    fn servers_precedence() {
+        use crate::schema::server::base_url;
+        use crate::schema::server::Overrides;
+
+        let spec = include_str!("../../test/test-vectors/servers.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let urls: Vec<String> = compiled
+            .operations
+            .iter()
+            .map(|op| base_url(op.servers, &Overrides::default()).unwrap())
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://api.example.com/v1",
+                "https://eu.upload.example.com",
+                "http://localhost:8080"
+            ]
+        );
+
+        let spec = spec.replace("default: api", "default: prod");
+        let description: schema::Description = serde_yaml::from_str(&spec).unwrap();
+        assert!(matches!(
+            compile(&description),
+            Err(operation::Error::Server(_, OperationType::Get, _))
+        ));
+    }
+
+    #[test]
+    fn constraints() {
+        use crate::compile::data_type::CompiledType;
+        use crate::compile::data_type::NormalCompiledType;
+        use crate::compile::data_type::TypeOrSchemaRef;
+        use crate::schema::PropertyName;
+
+        let spec = include_str!("../../test/test-vectors/constraints.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let Some(DataType::ActualType(pet)) = compiled.schemas.values().next() else {
+            panic!("Pet is not compiled")
+        };
+        assert_eq!(pet.constraints.max_properties, Some(3));
+        let CompiledType::Normal(NormalCompiledType::Object(obj)) = &pet.compiled_type else {
+            panic!("Pet is not object")
+        };
+        let constraints = |name: &str| match obj.properties.get(&PropertyName::new(name.into())) {
+            Some(TypeOrSchemaRef::DataType(DataType::ActualType(t))) => t.constraints,
+            _ => panic!("{name} is not compiled"),
+        };
+        assert_eq!(
+            constraints("name").pattern.map(|v| v.as_str()),
+            Some("^[A-Za-z ]+$")
+        );
+        assert!(constraints("age").maximum.is_some_and(|v| v.exclusive));
+        assert_eq!(constraints("weight").multiple_of, Some(0.5));
+        assert!(constraints("tags").unique_items);
+
+        let spec = spec.replace("minimum: 0", "minimum: 100");
+        let description: schema::Description = serde_yaml::from_str(&spec).unwrap();
+        // Error is nested into request body, schema and property errors
+        let err = format!("{:?}", compile(&description).unwrap_err());
+        assert!(err.contains("Constraints(Range(100.0, 100.0))"));
+    }
 }
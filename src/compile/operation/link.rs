@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: MIT
+//
+// Compiled links of the response
+//
+
+use crate::schema::components::Components;
+use crate::schema::link::Link as SchemaLink;
+use crate::schema::link::LinkOrReference;
+use crate::schema::link::Name as LinkName;
+use crate::schema::link::OperationRef;
+use crate::schema::link::ParameterName;
+use crate::schema::operation::OperationId;
+use crate::schema::path::Path;
+use crate::schema::path_item::OperationType;
+use crate::schema::reference::Reference as SchemaReference;
+use crate::schema::runtime_expression;
+use crate::schema::runtime_expression::Expression;
+use crate::schema::runtime_expression::Part;
+use crate::transform::pointer;
+
+#[derive(Debug)]
+pub struct Link<'a> {
+    pub name: &'a LinkName,
+    pub target: Target<'a>,
+    pub parameters: Vec<(&'a ParameterName, Value<'a>)>,
+    pub request_body: Option<Value<'a>>,
+    pub link: &'a SchemaLink,
+}
+
+#[derive(Debug)]
+pub enum Target<'a> {
+    OperationId(&'a OperationId),
+    // Reference to the operation inside the same document
+    Operation(&'a OperationRef, Path, &'static OperationType),
+    // Reference to the operation inside other document
+    External(&'a OperationRef),
+}
+
+#[derive(Debug)]
+pub enum Value<'a> {
+    Constant(&'a serde_yaml::Value),
+    Expression(Expression),
+    // String with embedded expressions
+    Template(Vec<Part>),
+}
+
+#[derive(Debug)]
+pub enum Error<'a> {
+    WrongReference(&'a SchemaReference),
+    // Exactly one of operationRef and operationId must be defined
+    NoTarget(&'a LinkName),
+    WrongOperationRef(&'a LinkName, &'a OperationRef),
+    Expression(&'a LinkName, runtime_expression::Error),
+}
+
+pub fn compile_links<'a>(
+    links: &'a Option<indexmap::IndexMap<LinkName, LinkOrReference>>,
+    components: &'a Option<Components>,
+) -> Result<Vec<Link<'a>>, Error<'a>> {
+    links
+        .iter()
+        .flatten()
+        .map(|(name, link)| {
+            let link = match link {
+                LinkOrReference::Link(link) => link.as_ref(),
+                LinkOrReference::Reference(r) => r
+                    .sref
+                    .link_sref()
+                    .and_then(|sref| components.as_ref()?.find_link(&sref))
+                    .ok_or(Error::WrongReference(r))?,
+            };
+            compile_link(name, link)
+        })
+        .collect()
+}
+
+fn compile_link<'a>(name: &'a LinkName, link: &'a SchemaLink) -> Result<Link<'a>, Error<'a>> {
+    let target = match (&link.operation_ref, &link.operation_id) {
+        (Some(operation_ref), None) => compile_operation_ref(name, operation_ref)?,
+        (None, Some(operation_id)) => Target::OperationId(operation_id),
+        _ => Err(Error::NoTarget(name))?,
+    };
+    let value = |v| compile_value(v).map_err(|err| Error::Expression(name, err));
+    let parameters = link
+        .parameters
+        .iter()
+        .flatten()
+        .map(|(pname, v)| Ok((pname, value(v)?)))
+        .collect::<Result<_, _>>()?;
+    Ok(Link {
+        name,
+        target,
+        parameters,
+        request_body: link.request_body.as_ref().map(value).transpose()?,
+        link,
+    })
+}
+
+fn compile_operation_ref<'a>(
+    name: &'a LinkName,
+    operation_ref: &'a OperationRef,
+) -> Result<Target<'a>, Error<'a>> {
+    let Some(fragment) = operation_ref.as_str().strip_prefix('#') else {
+        return Ok(Target::External(operation_ref));
+    };
+    match pointer::tokens(fragment).as_slice() {
+        [paths, path, method] if paths == "paths" => {
+            let path = path.parse().ok();
+            let op_type = OperationType::from_method(method);
+            path.zip(op_type)
+                .map(|(path, op_type)| Target::Operation(operation_ref, path, op_type))
+                .ok_or(Error::WrongOperationRef(name, operation_ref))
+        }
+        _ => Err(Error::WrongOperationRef(name, operation_ref)),
+    }
+}
+
+fn compile_value(v: &serde_yaml::Value) -> Result<Value<'_>, runtime_expression::Error> {
+    match v.as_str() {
+        Some(s) if Expression::is_expression(s) => s.parse().map(Value::Expression),
+        Some(s) if s.contains("{$") => runtime_expression::parse_template(s).map(Value::Template),
+        _ => Ok(Value::Constant(v)),
+    }
+}
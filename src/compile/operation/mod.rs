@@ -3,17 +3,28 @@
 // Compiled operation
 //
 
+pub mod link;
 pub mod parameter;
 pub mod request_body;
 pub mod response_body;
+pub mod security;
 
+use crate::compile::schema_compiler::Error as SchemaCompileError;
 use crate::compile::stack::Stack;
 use crate::compile::RequestBodies;
 use crate::compile::ResponseBodies;
 use crate::compile::Schemas;
+use crate::schema::callback::Expression as CallbackExpression;
+use crate::schema::callback::Name as CallbackName;
 use crate::schema::components::Components;
+use crate::schema::extensions;
+use crate::schema::extensions::Extensions;
 use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::link::Name as LinkName;
+use crate::schema::operation::Description;
 use crate::schema::operation::Operation as SchemaOperation;
+use crate::schema::operation::OperationId;
+use crate::schema::operation::Summary;
 use crate::schema::parameter::Name as SchemaParameterName;
 use crate::schema::parameter::Parameter as SchemaParameter;
 use crate::schema::path::Path;
@@ -23,23 +34,102 @@ use crate::schema::path_item::PathItem;
 use crate::schema::reference::Reference as SchemaReference;
 use crate::schema::request_body::RequestBodyOrReference as SchemaRequestBodyOrReference;
 use crate::schema::responses::ResponseOrReference as SchemaResponseOrReference;
+use crate::schema::security_requirement::SecurityRequirement as SchemaSecurityRequirement;
+use crate::schema::server;
+use crate::schema::server::Server;
+use crate::schema::ApiDocTag;
+use crate::schema::WebhookName;
+use crate::transform::pointer;
 use parameter::Parameter;
 use request_body::CompileResult as BodyCompileResult;
 use request_body::RequestBodyOrReference;
 use response_body::CompileResult as ResponseCompileResult;
 use response_body::ResponseBodyOrReference;
-use std::collections::HashMap;
+use security::Security;
 
 #[derive(Debug)]
 pub struct Operation<'a> {
     pub op_type: &'static OperationType,
-    pub path: &'a Path,
-    pub path_params: HashMap<&'a SchemaParameterName, Parameter<'a>>,
+    pub location: Location<'a>,
+    pub operation_id: Option<&'a OperationId>,
+    pub tags: Vec<&'a ApiDocTag>,
+    pub summary: Option<&'a Summary>,
+    pub description: Option<&'a Description>,
+    pub deprecated: bool,
+    // Effective servers (operation, path item or description
+    // ones). Empty if servers are not defined at all.
+    pub servers: &'a [Server],
+    // In order of the path template
+    pub path_params: indexmap::IndexMap<&'a SchemaParameterName, Parameter<'a>>,
     pub query_params: Vec<Parameter<'a>>,
     pub header_params: Vec<Parameter<'a>>,
     pub cookie_params: Vec<Parameter<'a>>,
     pub request_body_or_ref: Option<RequestBodyOrReference<'a>>,
     pub request_responses: Responses<'a>,
+    // Effective security (operation or global one)
+    pub security: Security<'a>,
+    pub extensions: &'a Extensions,
+}
+
+// Where operation is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location<'a> {
+    Path(&'a Path),
+    // Callback (name, runtime expression) of the operation at path
+    Callback(
+        &'a Path,
+        &'static OperationType,
+        &'a CallbackName,
+        &'a CallbackExpression,
+    ),
+    Webhook(&'a WebhookName),
+}
+
+impl<'a> Location<'a> {
+    pub fn path(&self) -> Option<&'a Path> {
+        match self {
+            Self::Path(path) => Some(path),
+            Self::Callback(..) | Self::Webhook(_) => None,
+        }
+    }
+
+    // JSON pointer to the path item inside description
+    pub fn pointer(&self) -> String {
+        match self {
+            Self::Path(path) => pointer::join(["paths", &path.to_string()]),
+            Self::Callback(path, parent, name, expression) => pointer::join([
+                "paths",
+                &path.to_string(),
+                parent.as_str(),
+                "callbacks",
+                name.as_str(),
+                expression.as_str(),
+            ]),
+            Self::Webhook(name) => pointer::join(["webhooks", name.as_str()]),
+        }
+    }
+}
+
+impl Operation<'_> {
+    // JSON pointer to the operation object inside description
+    pub fn pointer(&self) -> String {
+        format!("{}/{}", self.location.pointer(), self.op_type.as_str())
+    }
+}
+
+impl std::fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(path) => path.fmt(f),
+            Self::Callback(path, op_type, name, expression) => write!(
+                f,
+                "{path} {op_type:?} callback {} {}",
+                name.as_str(),
+                expression.as_str()
+            ),
+            Self::Webhook(name) => write!(f, "webhook {}", name.as_str()),
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -50,20 +140,110 @@ pub struct Responses<'a> {
 
 #[derive(Debug)]
 pub enum Error<'a> {
-    PathParameter(&'a Path, SchemaParameterName, parameter::Error<'a>),
-    QueryParameter(&'a Path, parameter::Error<'a>),
-    HeaderParameter(&'a Path, parameter::Error<'a>),
-    CookieParameter(&'a Path, parameter::Error<'a>),
-    PathParseError(&'a Path, PathParseError),
-    RequestBodyCompile(&'a Path, &'static OperationType, request_body::Error<'a>),
-    ResponseBodyCompile(&'a Path, &'static OperationType, response_body::Error<'a>),
-    WrongParameterReference(&'a Path, &'a SchemaReference),
+    PathParameter(Location<'a>, SchemaParameterName, parameter::Error<'a>),
+    QueryParameter(Location<'a>, parameter::Error<'a>),
+    HeaderParameter(Location<'a>, parameter::Error<'a>),
+    CookieParameter(Location<'a>, parameter::Error<'a>),
+    PathParseError(Location<'a>, PathParseError),
+    RequestBodyCompile(
+        Location<'a>,
+        &'static OperationType,
+        request_body::Error<'a>,
+    ),
+    ResponseBodyCompile(
+        Location<'a>,
+        &'static OperationType,
+        response_body::Error<'a>,
+    ),
+    WrongParameterReference(Location<'a>, &'a SchemaReference),
     ResponseCodeCompilation(
-        &'a Path,
+        Location<'a>,
         &'static OperationType,
         &'a HttpStatusCode,
         Box<Error<'a>>,
     ),
+    WrongCallbackReference(Location<'a>, &'a SchemaReference),
+    Extension(Location<'a>, &'static OperationType, extensions::Error),
+    Security(Location<'a>, &'static OperationType, security::Error<'a>),
+    Server(Location<'a>, &'static OperationType, server::Error),
+    // Link refers to the operation that doesn't exist
+    LinkTarget(Location<'a>, &'static OperationType, &'a LinkName),
+    ParameterSchema(
+        Location<'a>,
+        &'static OperationType,
+        &'a SchemaParameterName,
+        SchemaCompileError<'a>,
+    ),
+}
+
+impl Error<'_> {
+    // JSON pointer to the node that caused the error. It may point to
+    // the node absent in the description (e.g. to the content of the
+    // referenced response), see schema::span::Spans::find.
+    pub fn pointer(&self) -> String {
+        let op = |location: &Location, op_type: &OperationType| {
+            format!("{}/{}", location.pointer(), op_type.as_str())
+        };
+        match self {
+            Self::PathParameter(location, ..)
+            | Self::QueryParameter(location, _)
+            | Self::HeaderParameter(location, _)
+            | Self::CookieParameter(location, _)
+            | Self::PathParseError(location, _)
+            | Self::WrongParameterReference(location, _)
+            | Self::WrongCallbackReference(location, _) => location.pointer(),
+            Self::RequestBodyCompile(location, op_type, err) => {
+                let at = format!("{}/requestBody", op(location, op_type));
+                match err {
+                    request_body::Error::JsonCompile(err) => {
+                        err.pointer(&format!("{at}/content/application~1json/schema"))
+                    }
+                    request_body::Error::WrongReference(_) => at,
+                }
+            }
+            Self::ResponseCodeCompilation(location, op_type, code, err) => {
+                let at = format!("{}/responses/{code}", op(location, op_type));
+                err.response_pointer(at)
+            }
+            Self::ResponseBodyCompile(location, op_type, _) => {
+                let at = format!("{}/responses/default", op(location, op_type));
+                self.response_pointer(at)
+            }
+            Self::Security(location, op_type, _) => format!("{}/security", op(location, op_type)),
+            Self::Server(location, op_type, _) => format!("{}/servers", op(location, op_type)),
+            Self::Extension(location, op_type, _) | Self::LinkTarget(location, op_type, _) => {
+                op(location, op_type)
+            }
+            Self::ParameterSchema(location, op_type, ..) => {
+                format!("{}/parameters", op(location, op_type))
+            }
+        }
+    }
+
+    // Pointer to the node of the response at the given pointer
+    fn response_pointer(&self, at: String) -> String {
+        match self {
+            Self::ResponseBodyCompile(_, _, response_body::Error::JsonCompile(err)) => {
+                err.pointer(&format!("{at}/content/application~1json/schema"))
+            }
+            Self::ResponseBodyCompile(_, _, response_body::Error::Link(_)) => {
+                format!("{at}/links")
+            }
+            Self::ResponseBodyCompile(
+                _,
+                _,
+                response_body::Error::WrongHeaderReference(name, _),
+            ) => {
+                format!("{at}/headers/{}", pointer::escape(name.as_str()))
+            }
+            Self::ResponseBodyCompile(_, _, response_body::Error::HeaderCompile(name, err)) => err
+                .pointer(&format!(
+                    "{at}/headers/{}/schema",
+                    pointer::escape(name.as_str())
+                )),
+            _ => at,
+        }
+    }
 }
 
 pub struct CompileResult<'a> {
@@ -74,10 +254,12 @@ pub struct CompileResult<'a> {
 }
 
 pub struct CompileData<'a, 'b> {
-    pub path: &'a Path,
+    pub location: Location<'a>,
     pub item: &'a PathItem,
     pub op: &'a SchemaOperation,
     pub components: &'a Option<Components>,
+    pub security: &'a Option<Vec<SchemaSecurityRequirement>>,
+    pub servers: &'a [Server],
     pub schema_chain: &'b Stack<'a, 'b>,
     pub request_bodies: &'b RequestBodies<'a>,
     pub response_bodies: &'b ResponseBodies<'a>,
@@ -97,18 +279,43 @@ impl<'a, 'b> CompileData<'a, 'b> {
             item_parameters: &self.item.parameters,
             components: self.components,
         };
-        let path_params = self
-            .path
-            .path_params_iter()
-            .map(|pname| {
-                let name = pname
-                    .map_err(|err| Error::PathParseError(self.path, err))
-                    .map(|v| SchemaParameterName::new(v.into()))?;
-                parameter_compile
-                    .compile_path_parameter(&name)
-                    .map_err(|err| Error::PathParameter(self.path, name.clone(), err))
-            })
-            .collect::<Result<HashMap<_, _>, _>>()?;
+        let mut path_params = match self.location {
+            Location::Path(path) => path
+                .path_params_iter()
+                .map(|pname| {
+                    let name = pname
+                        .map_err(|err| Error::PathParseError(self.location, err))
+                        .map(|v| SchemaParameterName::new(v.into()))?;
+                    parameter_compile
+                        .compile_path_parameter(&name)
+                        .map_err(|err| Error::PathParameter(self.location, name.clone(), err))
+                })
+                .collect::<Result<indexmap::IndexMap<_, _>, _>>()?,
+            // Callback URLs are runtime expressions and webhooks
+            // have no path at all
+            Location::Callback(..) | Location::Webhook(_) => indexmap::IndexMap::default(),
+        };
+
+        let mut query_params = parameter_compile
+            .compile_params_by_group(SchemaParameter::is_query)
+            .map_err(|err| Error::QueryParameter(self.location, err))?;
+        let mut header_params = parameter_compile
+            .compile_params_by_group(SchemaParameter::is_header)
+            .map_err(|err| Error::HeaderParameter(self.location, err))?;
+        let mut cookie_params = parameter_compile
+            .compile_params_by_group(SchemaParameter::is_cookie)
+            .map_err(|err| Error::CookieParameter(self.location, err))?;
+        let params = path_params
+            .values_mut()
+            .chain(&mut query_params)
+            .chain(&mut header_params)
+            .chain(&mut cookie_params);
+        for p in params {
+            p.compile_schema(self.components.as_ref(), &mut chain)
+                .map_err(|err| {
+                    Error::ParameterSchema(self.location, op_type, &p.schema_param.name, err)
+                })?;
+        }
 
         let request_body_or_ref = self
             .op
@@ -139,7 +346,7 @@ impl<'a, 'b> CompileData<'a, 'b> {
                                 .map(|resp| resp.aggregate(&mut response_bodies, &mut chain))
                                 .map_err(|err| {
                                     Error::ResponseCodeCompilation(
-                                        self.path,
+                                        self.location,
                                         op_type,
                                         code,
                                         Box::new(err),
@@ -152,22 +359,39 @@ impl<'a, 'b> CompileData<'a, 'b> {
             })
             .transpose()?;
 
+        let servers = self.effective_servers();
+        for server in servers {
+            server
+                .template()
+                .map_err(|err| Error::Server(self.location, op_type, err))?;
+        }
+
+        let security = security::CompileData {
+            op_security: &self.op.security,
+            global_security: self.security,
+            components: self.components,
+        }
+        .compile_security()
+        .map_err(|err| Error::Security(self.location, op_type, err))?;
+
         Ok(CompileResult {
             op: Operation {
                 op_type,
-                path: self.path,
+                location: self.location,
+                operation_id: self.op.operation_id.as_ref(),
+                tags: self.op.tags.iter().flatten().collect(),
+                summary: self.op.summary.as_ref(),
+                description: self.op.description.as_ref(),
+                deprecated: self.op.deprecated,
+                servers,
                 path_params,
-                query_params: parameter_compile
-                    .compile_params_by_group(SchemaParameter::is_query)
-                    .map_err(|err| Error::QueryParameter(self.path, err))?,
-                header_params: parameter_compile
-                    .compile_params_by_group(SchemaParameter::is_header)
-                    .map_err(|err| Error::HeaderParameter(self.path, err))?,
-                cookie_params: parameter_compile
-                    .compile_params_by_group(SchemaParameter::is_cookie)
-                    .map_err(|err| Error::CookieParameter(self.path, err))?,
+                query_params,
+                header_params,
+                cookie_params,
                 request_body_or_ref,
                 request_responses: responses.unwrap_or_default(),
+                security,
+                extensions: &self.op.extensions,
             },
             schemas: chain.done(),
             request_bodies,
@@ -175,6 +399,22 @@ impl<'a, 'b> CompileData<'a, 'b> {
         })
     }
 
+    // Servers of the operation override servers of the path item
+    // which override servers of the description. Callbacks and
+    // webhooks are served by API consumer so servers of the
+    // description are not applicable.
+    fn effective_servers(&self) -> &'a [Server] {
+        let global = match self.location {
+            Location::Path(_) => self.servers,
+            Location::Callback(..) | Location::Webhook(_) => &[],
+        };
+        self.op
+            .servers
+            .as_deref()
+            .or(self.item.servers.as_deref())
+            .unwrap_or(global)
+    }
+
     fn compile_body(
         &self,
         op_type: &'static OperationType,
@@ -186,7 +426,7 @@ impl<'a, 'b> CompileData<'a, 'b> {
             request_bodies: self.request_bodies,
         };
         request_body::compile_body(cdata, sbody)
-            .map_err(|err| Error::RequestBodyCompile(self.path, op_type, err))
+            .map_err(|err| Error::RequestBodyCompile(self.location, op_type, err))
     }
 
     fn compile_response(
@@ -200,6 +440,6 @@ impl<'a, 'b> CompileData<'a, 'b> {
             response_bodies: self.response_bodies,
         };
         response_body::compile_response(cdata, sresp)
-            .map_err(|err| Error::ResponseBodyCompile(self.path, op_type, err))
+            .map_err(|err| Error::ResponseBodyCompile(self.location, op_type, err))
     }
 }
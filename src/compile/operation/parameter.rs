@@ -3,6 +3,10 @@
 // Compiled parameter
 //
 
+use crate::compile::data_type::TypeOrSchemaRef;
+use crate::compile::schema_compiler;
+use crate::compile::schema_compiler::Error as SchemaCompileError;
+use crate::compile::stack::Stack;
 use crate::schema::components::Components;
 use crate::schema::parameter::Name as SchemaParameterName;
 use crate::schema::parameter::Parameter as SchemaParameter;
@@ -14,6 +18,32 @@ use std::collections::HashSet;
 #[derive(Debug)]
 pub struct Parameter<'a> {
     pub schema_param: &'a SchemaParameter,
+    // Compiled schema of the value (see SchemaParameter::schema)
+    pub type_or_ref: Option<TypeOrSchemaRef<'a>>,
+}
+
+impl<'a> Parameter<'a> {
+    fn new(schema_param: &'a SchemaParameter) -> Self {
+        Self {
+            schema_param,
+            type_or_ref: None,
+        }
+    }
+
+    // Compile schema of the parameter. Referenced schemas are added to
+    // the chain.
+    pub fn compile_schema<'b>(
+        &mut self,
+        components: Option<&'a Components>,
+        chain: &mut Stack<'a, 'b>,
+    ) -> Result<(), SchemaCompileError<'a>> {
+        if let Some(schema) = self.schema_param.schema() {
+            let compiled = schema_compiler::compile(schema, components, chain, 0)?;
+            chain.merge(compiled.schemas);
+            self.type_or_ref = Some(compiled.type_or_ref);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -40,7 +70,7 @@ impl<'a> CompileData<'a> {
                 SchemaParameterPlace::Path(_) => Ok(p),
                 _ => Err(Error::NotDefinedAsPathParameter),
             })
-            .map(|v| (&v.name, Parameter { schema_param: v }))
+            .map(|v| (&v.name, Parameter::new(v)))
     }
     pub fn find_param_by_ref(&self, r: &SchemaReference) -> Option<&'a SchemaParameter> {
         r.sref.parameter_sref().as_ref().and_then(|sref| {
@@ -57,7 +87,7 @@ impl<'a> CompileData<'a> {
         let find_param = |ps: &'a Vec<ParameterOrReference>| {
             for p in ps.iter() {
                 let candidate = match p {
-                    ParameterOrReference::Parameter(p) => Some(p),
+                    ParameterOrReference::Parameter(p) => Some(p.as_ref()),
                     ParameterOrReference::Reference(r) => self.find_param_by_ref(r),
                 };
                 if let Some(candidate) = candidate {
@@ -80,7 +110,7 @@ impl<'a> CompileData<'a> {
         filter: fn(&SchemaParameter) -> bool,
     ) -> Result<Vec<Parameter<'a>>, Error<'a>> {
         let resolve_param = |p: &'a ParameterOrReference| match p {
-            ParameterOrReference::Parameter(p) => Ok(p),
+            ParameterOrReference::Parameter(p) => Ok(p.as_ref()),
             ParameterOrReference::Reference(r) => self
                 .find_param_by_ref(r)
                 .ok_or(Error::WrongParameterReference(r)),
@@ -120,7 +150,7 @@ impl<'a> CompileData<'a> {
         Ok([op_params, path_params]
             .concat()
             .into_iter()
-            .map(|p| Parameter { schema_param: p })
+            .map(Parameter::new)
             .collect::<Vec<_>>())
     }
 }
@@ -11,6 +11,7 @@ use crate::compile::stack::Stack;
 use crate::compile::RequestBodies;
 use crate::compile::Schemas;
 use crate::schema::components::Components;
+use crate::schema::extensions::Extensions;
 use crate::schema::reference::Reference as SchemaReference;
 use crate::schema::request_body::RequestBody as SchemaRequestBody;
 use crate::schema::request_body::RequestBodyOrReference as SchemaRequestBodyOrReference;
@@ -19,6 +20,10 @@ use crate::schema::sref::SRefRequestBody;
 #[derive(Debug)]
 pub struct RequestBody<'a> {
     pub json_type_or_ref: Option<TypeOrSchemaRef<'a>>,
+    // Media types (ranges) of the content
+    pub content_types: Vec<&'a str>,
+    pub required: bool,
+    pub extensions: &'a Extensions,
 }
 
 #[derive(Debug)]
@@ -82,7 +87,12 @@ pub fn compile_body<'a, 'b>(
                     chain.merge(v.schemas);
                     v.type_or_ref
                 });
-            let request_body = RequestBody { json_type_or_ref };
+            let request_body = RequestBody {
+                json_type_or_ref,
+                content_types: b.content.keys().map(String::as_str).collect(),
+                required: b.required.unwrap_or(false),
+                extensions: &b.extensions,
+            };
             Ok(CompileResult::DataType((request_body, chain.done())))
         }
         SchemaRequestBodyOrReference::Reference(r) => {
@@ -102,7 +112,12 @@ pub fn compile_body<'a, 'b>(
                         chain.merge(v.schemas);
                         v.type_or_ref
                     });
-                let request_body = RequestBody { json_type_or_ref };
+                let request_body = RequestBody {
+                    json_type_or_ref,
+                    content_types: body_schema.content.keys().map(String::as_str).collect(),
+                    required: body_schema.required.unwrap_or(false),
+                    extensions: &body_schema.extensions,
+                };
                 Ok(CompileResult::New((body_sref, request_body, chain.done())))
             }
         }
@@ -5,20 +5,42 @@
 
 use crate::compile::data_type::DataTypeWithSchema;
 use crate::compile::data_type::TypeOrSchemaRef;
+use crate::compile::operation::link;
+use crate::compile::operation::link::Link;
 use crate::compile::schema_compiler;
 use crate::compile::schema_compiler::Error as SchemaCompileError;
 use crate::compile::stack::Stack;
 use crate::compile::ResponseBodies;
 use crate::compile::Schemas;
 use crate::schema::components::Components;
+use crate::schema::extensions::Extensions;
+use crate::schema::header::ContentSchema;
+use crate::schema::header::Header as SchemaHeader;
+use crate::schema::header::HeaderOrReference;
 use crate::schema::reference::Reference as SchemaReference;
 use crate::schema::response::Response as SchemaResponse;
 use crate::schema::responses::ResponseOrReference as SchemaResponseOrReference;
 use crate::schema::sref::SRefResponsesName;
+use crate::schema::HeaderName;
 
 #[derive(Debug)]
 pub struct ResponseBody<'a> {
     pub json_type_or_ref: Option<TypeOrSchemaRef<'a>>,
+    // Media types (ranges) of the content
+    pub content_types: Vec<&'a str>,
+    pub links: Vec<Link<'a>>,
+    // Headers except Content-Type (it is described by content)
+    pub headers: Vec<Header<'a>>,
+    pub extensions: &'a Extensions,
+}
+
+#[derive(Debug)]
+pub struct Header<'a> {
+    pub name: &'a HeaderName,
+    // Referenced header is resolved
+    pub header: &'a SchemaHeader,
+    // Type of the header schema or of its JSON content
+    pub type_or_ref: Option<TypeOrSchemaRef<'a>>,
 }
 
 #[derive(Debug)]
@@ -67,6 +89,9 @@ impl<'a> CompileResult<'a> {
 pub enum Error<'a> {
     JsonCompile(SchemaCompileError<'a>),
     WrongReference(&'a SchemaReference),
+    Link(link::Error<'a>),
+    WrongHeaderReference(&'a HeaderName, &'a SchemaReference),
+    HeaderCompile(&'a HeaderName, SchemaCompileError<'a>),
 }
 
 pub fn compile_response<'a, 'b>(
@@ -82,7 +107,14 @@ pub fn compile_response<'a, 'b>(
                     chain.merge(v.schemas);
                     v.type_or_ref
                 });
-            let reps = ResponseBody { json_type_or_ref };
+            let headers = compile_headers(b, cdata.components.as_ref(), &mut chain)?;
+            let reps = ResponseBody {
+                json_type_or_ref,
+                content_types: content_types(b),
+                links: link::compile_links(&b.links, cdata.components).map_err(Error::Link)?,
+                headers,
+                extensions: &b.extensions,
+            };
             Ok(CompileResult::DataType((reps, chain.done())))
         }
         SchemaResponseOrReference::Reference(r) => {
@@ -102,13 +134,71 @@ pub fn compile_response<'a, 'b>(
                         chain.merge(v.schemas);
                         v.type_or_ref
                     });
-                let response = ResponseBody { json_type_or_ref };
+                let headers = compile_headers(resp_schema, Some(components), &mut chain)?;
+                let response = ResponseBody {
+                    json_type_or_ref,
+                    content_types: content_types(resp_schema),
+                    links: link::compile_links(&resp_schema.links, cdata.components)
+                        .map_err(Error::Link)?,
+                    headers,
+                    extensions: &resp_schema.extensions,
+                };
                 Ok(CompileResult::New((resp_sref, response, chain.done())))
             }
         }
     }
 }
 
+fn content_types(resp: &SchemaResponse) -> Vec<&str> {
+    resp.content
+        .iter()
+        .flat_map(|content| content.keys().map(String::as_str))
+        .collect()
+}
+
+fn compile_headers<'a, 'b>(
+    resp: &'a SchemaResponse,
+    components: Option<&'a Components>,
+    chain: &mut Stack<'a, 'b>,
+) -> Result<Vec<Header<'a>>, Error<'a>> {
+    let mut headers = vec![];
+    for (name, hor) in resp.headers.iter().flatten() {
+        if name.as_str().eq_ignore_ascii_case("content-type") {
+            continue;
+        }
+        let header = match hor {
+            HeaderOrReference::Header(header) => header.as_ref(),
+            HeaderOrReference::Reference(r) => r
+                .sref
+                .header_sref()
+                .and_then(|sref| components?.find_header(&sref))
+                .ok_or(Error::WrongHeaderReference(name, r))?,
+        };
+        let schema = match &header.content_schema {
+            ContentSchema::SchemaAndStyle(v) => Some(&v.schema),
+            ContentSchema::Content(v) => v
+                .content
+                .get("application/json")
+                .and_then(|json| json.schema.as_ref()),
+        };
+        let type_or_ref = match schema {
+            Some(schema) => {
+                let compiled = schema_compiler::compile(schema, components, chain, 0)
+                    .map_err(|err| Error::HeaderCompile(name, err))?;
+                chain.merge(compiled.schemas);
+                Some(compiled.type_or_ref)
+            }
+            None => None,
+        };
+        headers.push(Header {
+            name,
+            header,
+            type_or_ref,
+        });
+    }
+    Ok(headers)
+}
+
 fn compile_json<'a, 'b>(
     resp: &'a SchemaResponse,
     components: Option<&'a Components>,
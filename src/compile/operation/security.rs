@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT
+//
+// Compiled security requirements of the operation
+//
+
+use crate::schema::components::Components;
+use crate::schema::security_requirement::SecurityRequirement as SchemaSecurityRequirement;
+use crate::schema::security_scheme::Scope;
+use crate::schema::security_scheme::SecurityScheme;
+use crate::schema::sref::SRefSecuritySchemeName;
+
+// Effective security of the operation: it is enough to satisfy any
+// of the requirements. Empty list means that no security is
+// required.
+pub type Security<'a> = Vec<SecurityRequirement<'a>>;
+
+// All schemes must be satisfied. Requirement without schemes means
+// anonymous access.
+#[derive(Debug)]
+pub struct SecurityRequirement<'a> {
+    pub schemes: Vec<SchemeRequirement<'a>>,
+}
+
+#[derive(Debug)]
+pub struct SchemeRequirement<'a> {
+    pub name: &'a SRefSecuritySchemeName,
+    pub scheme: &'a SecurityScheme,
+    pub scopes: &'a [Scope],
+}
+
+#[derive(Debug)]
+pub enum Error<'a> {
+    SchemeNotDefined(&'a SRefSecuritySchemeName),
+    ScopeNotDefined(&'a SRefSecuritySchemeName, &'a Scope),
+}
+
+pub struct CompileData<'a> {
+    pub op_security: &'a Option<Vec<SchemaSecurityRequirement>>,
+    pub global_security: &'a Option<Vec<SchemaSecurityRequirement>>,
+    pub components: &'a Option<Components>,
+}
+
+impl<'a> CompileData<'a> {
+    // Operation security overrides global one. Explicit empty list
+    // removes global security.
+    pub fn compile_security(&self) -> Result<Security<'a>, Error<'a>> {
+        self.op_security
+            .as_ref()
+            .or(self.global_security.as_ref())
+            .map(|reqs| {
+                reqs.iter()
+                    .map(|req| self.compile_requirement(req))
+                    .collect()
+            })
+            .unwrap_or(Ok(vec![]))
+    }
+
+    fn compile_requirement(
+        &self,
+        req: &'a SchemaSecurityRequirement,
+    ) -> Result<SecurityRequirement<'a>, Error<'a>> {
+        let schemes = req
+            .iter()
+            .map(|(name, scopes)| {
+                let scheme = self
+                    .components
+                    .as_ref()
+                    .and_then(|c| c.find_security_scheme(name))
+                    .ok_or(Error::SchemeNotDefined(name))?;
+                // Scopes of other schemes are roles (3.1.X) and
+                // aren't defined by the description.
+                if let SecurityScheme::OAuth2(oauth2) = scheme {
+                    if let Some(scope) = scopes.iter().find(|s| !oauth2.flows.has_scope(s)) {
+                        return Err(Error::ScopeNotDefined(name, scope));
+                    }
+                }
+                Ok(SchemeRequirement {
+                    name,
+                    scheme,
+                    scopes,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(SecurityRequirement { schemes })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::schema;
+    use crate::schema::path_item::OperationType;
+
+    fn security<'a>(
+        compiled: &'a compile::Compiled<'a>,
+        path: &str,
+        op_type: &OperationType,
+    ) -> &'a Security<'a> {
+        compiled
+            .operations
+            .iter()
+            .find(|op| op.location.to_string() == path && op.op_type == op_type)
+            .map(|op| &op.security)
+            .unwrap()
+    }
+
+    fn names<'a>(req: &'a SecurityRequirement<'a>) -> Vec<&'a str> {
+        req.schemes.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn effective_security() {
+        let spec = include_str!("../../../test/test-vectors/security.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+
+        let global = security(&compiled, "/pets", &OperationType::Get);
+        assert_eq!(global.len(), 2);
+        assert_eq!(names(&global[0]), ["ApiKeyHeader"]);
+        assert_eq!(names(&global[1]), ["Basic"]);
+
+        let overridden = security(&compiled, "/pets", &OperationType::Post);
+        assert_eq!(overridden.len(), 1);
+        assert_eq!(names(&overridden[0]), ["PetStore", "ApiKeyCookie"]);
+        assert_eq!(overridden[0].schemes[0].scopes[0].as_str(), "write:pets");
+
+        assert!(security(&compiled, "/health", &OperationType::Get).is_empty());
+
+        let optional = security(&compiled, "/me", &OperationType::Get);
+        assert!(matches!(
+            optional[0].schemes[0].scheme,
+            SecurityScheme::OpenIdConnect(_)
+        ));
+        assert!(optional[1].schemes.is_empty());
+    }
+
+    #[test]
+    fn undefined_scope() {
+        let spec = include_str!("../../../test/test-vectors/security.yaml")
+            .replace("[write:pets]", "[delete:pets]");
+        let description: schema::Description = serde_yaml::from_str(&spec).unwrap();
+        assert!(matches!(
+            compile::compile(&description),
+            Err(compile::operation::Error::Security(
+                _,
+                _,
+                Error::ScopeNotDefined(_, _)
+            ))
+        ));
+    }
+}
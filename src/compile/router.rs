@@ -0,0 +1,410 @@
+// SPDX-License-Identifier: MIT
+//
+// Router of concrete URL paths to path templates.
+//
+// Every template is parsed into segments: literal ones, whole
+// segment parameters (/pets/{id}) and mixed ones (/files/{name}.{ext}).
+// Templates more specific from left to right take precedence: literal
+// segment over mixed one and mixed one over parameter. Templates of
+// the same precedence a path may match both are ambiguous.
+//
+
+use crate::compile::operation::Location;
+use crate::compile::operation::Operation;
+use crate::schema::path::Path;
+use crate::schema::path_item::OperationType;
+use crate::schema::template;
+use crate::schema::template::Token;
+use crate::transform::pointer;
+
+#[derive(Debug)]
+pub struct Router<'a> {
+    routes: Vec<Route<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Route<'a> {
+    pub path: &'a Path,
+    pub segments: Vec<Segment<'a>>,
+    pub operations: Vec<&'a Operation<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Literal(&'a str),
+    Param(&'a str),
+    // Literals and parameters of the single segment
+    Mixed(Vec<Part<'a>>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part<'a> {
+    Literal(&'a str),
+    Param(&'a str),
+}
+
+// Matched route and parameters of the path
+#[derive(Debug)]
+pub struct Match<'a, 'r, 'p> {
+    pub route: &'r Route<'a>,
+    pub params: Vec<Param<'a, 'p>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Param<'a, 'p> {
+    pub name: &'a str,
+    // Percent-encoded value
+    pub raw: &'p str,
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error<'a> {
+    UnclosedBrace(&'a Path),
+    EmptyParam(&'a Path),
+    // Parameters can't be split, e.g. {a}{b}
+    AdjacentParams(&'a Path),
+    DuplicateParam(&'a Path, &'a str),
+    Ambiguous(&'a Path, &'a Path),
+}
+
+impl<'a> Router<'a> {
+    // Router of operations at paths. Callbacks and webhooks are
+    // skipped.
+    pub fn new(operations: &'a [Operation<'a>]) -> Result<Self, Error<'a>> {
+        let mut routes: Vec<Route> = vec![];
+        for op in operations {
+            let Location::Path(path) = op.location else {
+                continue;
+            };
+            match routes.iter_mut().find(|r| r.path == path) {
+                Some(route) => route.operations.push(op),
+                None => routes.push(Route {
+                    path,
+                    segments: parse(path)?,
+                    operations: vec![op],
+                }),
+            }
+        }
+        for (i, a) in routes.iter().enumerate() {
+            if let Some(b) = routes[i + 1..].iter().find(|b| a.is_ambiguous(b)) {
+                return Err(Error::Ambiguous(a.path, b.path));
+            }
+        }
+        Ok(Self { routes })
+    }
+
+    pub fn routes(&self) -> &[Route<'a>] {
+        &self.routes
+    }
+
+    // Most specific route of the path (without query)
+    pub fn find<'r, 'p>(&'r self, path: &'p str) -> Option<Match<'a, 'r, 'p>> {
+        self.find_all(path).into_iter().next()
+    }
+
+    // All routes of the path from the most specific one
+    pub fn find_all<'r, 'p>(&'r self, path: &'p str) -> Vec<Match<'a, 'r, 'p>> {
+        let segments: Vec<_> = path.split('/').collect();
+        let mut found: Vec<_> = self
+            .routes
+            .iter()
+            .filter_map(|route| {
+                let params = route.matches(&segments)?;
+                Some((route.rank(), Match { route, params }))
+            })
+            .collect();
+        found.sort_by(|(a, _), (b, _)| b.cmp(a));
+        found.into_iter().map(|(_, m)| m).collect()
+    }
+}
+
+impl<'a> Route<'a> {
+    pub fn operation(&self, op_type: &OperationType) -> Option<&'a Operation<'a>> {
+        self.operations
+            .iter()
+            .find(|op| op.op_type == op_type)
+            .copied()
+    }
+
+    fn matches<'p>(&self, segments: &[&'p str]) -> Option<Vec<Param<'a, 'p>>> {
+        if self.segments.len() != segments.len() {
+            return None;
+        }
+        let mut params = vec![];
+        for (segment, s) in self.segments.iter().zip(segments) {
+            match segment {
+                Segment::Literal(l) if l == s => {}
+                Segment::Literal(_) => return None,
+                Segment::Param(name) if !s.is_empty() => params.push(Param::new(name, s)),
+                Segment::Param(_) => return None,
+                Segment::Mixed(parts) => {
+                    let start = params.len();
+                    if !match_parts(parts, s, &mut params) {
+                        return None;
+                    }
+                    // Parts are matched from the end
+                    params[start..].reverse();
+                }
+            }
+        }
+        Some(params)
+    }
+
+    fn rank(&self) -> Vec<(u8, usize)> {
+        rank(&self.segments)
+    }
+
+    // Routes of the same precedence matching the same path
+    fn is_ambiguous(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+            && self
+                .segments
+                .iter()
+                .zip(&other.segments)
+                .all(|(a, b)| match (a, b) {
+                    (Segment::Literal(a), Segment::Literal(b)) => a == b,
+                    (Segment::Mixed(a), Segment::Mixed(b)) => may_overlap(a, b),
+                    _ => true,
+                })
+    }
+}
+
+impl<'a, 'p> Param<'a, 'p> {
+    fn new(name: &'a str, raw: &'p str) -> Self {
+        Self {
+            name,
+            raw,
+            value: pointer::percent_decode(raw),
+        }
+    }
+}
+
+// Match parts against the segment. Parameters are greedy and
+// non-empty, e.g. {name}.{ext} splits "a.b.json" into "a.b" and
+// "json". Parameters are pushed in reverse order.
+fn match_parts<'a, 'p>(parts: &[Part<'a>], s: &'p str, params: &mut Vec<Param<'a, 'p>>) -> bool {
+    match parts {
+        [] => s.is_empty(),
+        [Part::Literal(l), rest @ ..] => s
+            .strip_prefix(l)
+            .is_some_and(|s| match_parts(rest, s, params)),
+        [Part::Param(name), rest @ ..] => {
+            for end in (1..=s.len()).rev().filter(|end| s.is_char_boundary(*end)) {
+                if match_parts(rest, &s[end..], params) {
+                    params.push(Param::new(name, &s[..end]));
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+// Precedence of segments from left to right (greater is more
+// specific). Mixed segments with longer literals are more specific.
+pub fn rank(segments: &[Segment]) -> Vec<(u8, usize)> {
+    segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(_) => (2, 0),
+            Segment::Mixed(parts) => (1, literal_len(parts)),
+            Segment::Param(_) => (0, 0),
+        })
+        .collect()
+}
+
+// Mixed segments may match the same value if their leading literals
+// are prefixes of each other and trailing literals are suffixes of
+// each other. Literals between parameters are assumed to overlap.
+fn may_overlap(a: &[Part], b: &[Part]) -> bool {
+    fn literal<'a>(part: Option<&Part<'a>>) -> &'a str {
+        match part {
+            Some(Part::Literal(l)) => l,
+            _ => "",
+        }
+    }
+    let (a_first, b_first) = (literal(a.first()), literal(b.first()));
+    let (a_last, b_last) = (literal(a.last()), literal(b.last()));
+    (a_first.starts_with(b_first) || b_first.starts_with(a_first))
+        && (a_last.ends_with(b_last) || b_last.ends_with(a_last))
+}
+
+fn literal_len(parts: &[Part]) -> usize {
+    parts
+        .iter()
+        .map(|part| match part {
+            Part::Literal(l) => l.len(),
+            Part::Param(_) => 0,
+        })
+        .sum()
+}
+
+pub fn parse(path: &Path) -> Result<Vec<Segment<'_>>, Error<'_>> {
+    let mut names = vec![];
+    let mut segments = vec![];
+    for segment in path.as_str().split('/') {
+        let mut parts = vec![];
+        let tokens = template::tokenize(segment).map_err(|_| Error::UnclosedBrace(path))?;
+        for token in tokens {
+            match token {
+                Token::Literal(l) => parts.push(Part::Literal(l)),
+                Token::Name("") => return Err(Error::EmptyParam(path)),
+                Token::Name(name) => {
+                    if matches!(parts.last(), Some(Part::Param(_))) {
+                        return Err(Error::AdjacentParams(path));
+                    }
+                    if names.contains(&name) {
+                        return Err(Error::DuplicateParam(path, name));
+                    }
+                    names.push(name);
+                    parts.push(Part::Param(name));
+                }
+            }
+        }
+        segments.push(match parts.as_slice() {
+            [] => Segment::Literal(""),
+            [Part::Literal(l)] => Segment::Literal(l),
+            [Part::Param(name)] => Segment::Param(name),
+            _ => Segment::Mixed(parts),
+        });
+    }
+    Ok(segments)
+}
+
+impl Error<'_> {
+    // JSON pointer to the path item of the error
+    pub fn pointer(&self) -> String {
+        match self {
+            Self::UnclosedBrace(path)
+            | Self::EmptyParam(path)
+            | Self::AdjacentParams(path)
+            | Self::DuplicateParam(path, _)
+            | Self::Ambiguous(_, path) => Location::Path(path).pointer(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnclosedBrace(path) => write!(f, "{path}: parameter is not closed"),
+            Self::EmptyParam(path) => write!(f, "{path}: parameter name is empty"),
+            Self::AdjacentParams(path) => {
+                write!(f, "{path}: parameters must be separated by literal")
+            }
+            Self::DuplicateParam(path, name) => write!(f, "{path}: duplicate parameter {name}"),
+            Self::Ambiguous(a, b) => write!(f, "paths are ambiguous: {a} and {b}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile::compile;
+    use crate::schema::Description;
+
+    fn path(s: &str) -> Path {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_segments() {
+        let p = path("/files/{name}.{ext}/v{version}");
+        assert_eq!(
+            parse(&p),
+            Ok(vec![
+                Segment::Literal(""),
+                Segment::Literal("files"),
+                Segment::Mixed(vec![
+                    Part::Param("name"),
+                    Part::Literal("."),
+                    Part::Param("ext")
+                ]),
+                Segment::Mixed(vec![Part::Literal("v"), Part::Param("version")]),
+            ])
+        );
+        let p = path("/files/{name");
+        assert_eq!(parse(&p), Err(Error::UnclosedBrace(&p)));
+        let p = path("/files/{}");
+        assert_eq!(parse(&p), Err(Error::EmptyParam(&p)));
+        let p = path("/files/{name}{ext}");
+        assert_eq!(parse(&p), Err(Error::AdjacentParams(&p)));
+        let p = path("/{id}/{id}");
+        assert_eq!(parse(&p), Err(Error::DuplicateParam(&p, "id")));
+    }
+
+    #[test]
+    fn match_and_ambiguity() {
+        let spec = include_str!("../../test/test-vectors/router.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let router = Router::new(&compiled.operations).unwrap();
+        let find = |s| {
+            let m = router.find(s)?;
+            let params: Vec<_> = m
+                .params
+                .iter()
+                .map(|p| (p.name, p.value.as_str()))
+                .collect();
+            Some((m.route.path.as_str(), format!("{params:?}")))
+        };
+        assert_eq!(
+            find("/files/latest.txt"),
+            Some(("/files/latest.txt", "[]".into()))
+        );
+        assert_eq!(
+            find("/files/old.txt"),
+            Some(("/files/{name}.txt", r#"[("name", "old")]"#.into()))
+        );
+        assert_eq!(
+            find("/files/old.csv"),
+            Some(("/files/{name}.csv", r#"[("name", "old")]"#.into()))
+        );
+        assert_eq!(
+            find("/files/report.2024.json"),
+            Some((
+                "/files/{name}.{ext}",
+                r#"[("name", "report.2024"), ("ext", "json")]"#.into()
+            ))
+        );
+        assert_eq!(
+            find("/files/a%20b"),
+            Some(("/files/{id}", r#"[("id", "a b")]"#.into()))
+        );
+        assert_eq!(
+            find("/pets/1"),
+            Some(("/{kind}/{id}", r#"[("kind", "pets"), ("id", "1")]"#.into()))
+        );
+        assert_eq!(find("/files/"), None);
+        assert_eq!(find("/files/a/b"), None);
+        let route = router.find("/files/latest.txt").unwrap().route;
+        assert!(route.operation(&OperationType::Get).is_some());
+        assert!(route.operation(&OperationType::Put).is_none());
+
+        let ambiguous = |a: &str, b: &str| {
+            let (pa, pb): (Path, Path) = (a.parse().unwrap(), b.parse().unwrap());
+            let ra = Route {
+                path: &pa,
+                segments: parse(&pa).unwrap(),
+                operations: vec![],
+            };
+            let rb = Route {
+                path: &pb,
+                segments: parse(&pb).unwrap(),
+                operations: vec![],
+            };
+            ra.is_ambiguous(&rb)
+        };
+        assert!(ambiguous("/pets/{id}", "/pets/{name}"));
+        assert!(ambiguous("/files/{a}-x", "/files/x-{b}"));
+        assert!(ambiguous("/files/{a}.txt", "/files/{b}.txt"));
+        assert!(ambiguous("/files/v{a}.txt", "/files/{b}x.txt"));
+        assert!(!ambiguous("/files/{name}.txt", "/files/{name}.csv"));
+        assert!(!ambiguous("/files/v{a}", "/files/w{b}"));
+        assert!(!ambiguous("/pets/{id}", "/pets/mine"));
+        assert!(!ambiguous("/pets/mine", "/{kind}/mine"));
+        assert!(!ambiguous("/pets/{id}", "/{kind}/mine"));
+    }
+}
@@ -3,9 +3,12 @@
 // Compilation of schema objects
 //
 
+use crate::compile::constraints;
+use crate::compile::constraints::Constraints;
 use crate::compile::data_type::ActualType;
 use crate::compile::data_type::AllOfType;
 use crate::compile::data_type::AnyOfType;
+use crate::compile::data_type::CompiledAdditionalProperties;
 use crate::compile::data_type::CompiledArray;
 use crate::compile::data_type::CompiledObject;
 use crate::compile::data_type::CompiledType;
@@ -19,6 +22,7 @@ use crate::compile::stack::Stack;
 use crate::compile::Schemas;
 use crate::schema::components::Components;
 use crate::schema::data_type::array::Array as SchemaArray;
+use crate::schema::data_type::object::AdditionalProperties;
 use crate::schema::data_type::object::Object as SchemaObject;
 use crate::schema::data_type::ActualType as SchemaActualType;
 use crate::schema::data_type::DataType as SchemaDataType;
@@ -30,7 +34,7 @@ use crate::schema::sref::SRef;
 use crate::schema::sref::SRefSchemas;
 use crate::schema::sref::SRefSchemasObjectName;
 use crate::schema::PropertyName;
-use indexmap::indexmap;
+use crate::transform::pointer;
 
 const MAX_DEPTH: u32 = 1024;
 
@@ -46,10 +50,35 @@ pub enum Error<'a> {
     CompileReference(&'a Reference, Box<Error<'a>>),
     NoItemsInArray,
     ArrayItemCompilation(Box<Error<'a>>),
+    AdditionalPropertiesCompilation(Box<Error<'a>>),
     ReferenceToUncompatibleObject(SRefSchemas),
     PropertiesNotFoundInReferencedObject(SRefSchemasObjectName),
     PropertyNotFoundInReferencedObject((SRefSchemasObjectName, PropertyName)),
     NotImplemented(&'static str),
+    Constraints(constraints::Error),
+}
+
+impl Error<'_> {
+    // JSON pointer to the node that caused the error. Pointer of the
+    // compiled schema is given as at. Errors of referenced schemas
+    // point inside components.
+    pub fn pointer(&self, at: &str) -> String {
+        match self {
+            Self::SchemaCompilation(name, err) => {
+                err.pointer(&pointer::join(["components", "schemas", name.as_str()]))
+            }
+            Self::PropertyCompilation(name, err) => err.pointer(&format!(
+                "{at}/properties/{}",
+                pointer::escape(name.as_str())
+            )),
+            Self::CompileReference(_, err) => err.pointer(&format!("{at}/$ref")),
+            Self::ArrayItemCompilation(err) => err.pointer(&format!("{at}/items")),
+            Self::AdditionalPropertiesCompilation(err) => {
+                err.pointer(&format!("{at}/additionalProperties"))
+            }
+            _ => at.into(),
+        }
+    }
 }
 
 pub fn compile<'a, 'b>(
@@ -64,18 +93,29 @@ pub fn compile<'a, 'b>(
     match sdt {
         SchemaDataType::Reference(r) => compile_ref(r, components, parent_stack, depth)
             .map_err(|err| Error::CompileReference(r, Box::new(err))),
-        SchemaDataType::ActualType(at) => match &at.type_schema {
-            MaybeNullableTypeSchema::Nullable(dt) => {
-                compile_nullable_actual_type(at, &dt.schema, components, parent_stack, depth + 1)
-            }
-            MaybeNullableTypeSchema::Normal(dt) => compile_normal_actual_type(at, dt),
-            MaybeNullableTypeSchema::Object(obj) => {
-                compile_normal_object(obj, components, parent_stack, depth + 1)
-            }
-            MaybeNullableTypeSchema::Array(_) => {
-                Err(Error::NotImplemented("MaybeNullableTypeSchema::Array"))
+        SchemaDataType::ActualType(at) => {
+            Constraints::new(&at.type_schema)
+                .check()
+                .map_err(Error::Constraints)?;
+            match &at.type_schema {
+                MaybeNullableTypeSchema::Nullable(dt) => compile_nullable_actual_type(
+                    at,
+                    &dt.schema,
+                    components,
+                    parent_stack,
+                    depth + 1,
+                ),
+                MaybeNullableTypeSchema::Normal(dt) => {
+                    compile_normal_actual_type(at, dt, components, parent_stack, depth + 1)
+                }
+                MaybeNullableTypeSchema::Object(obj) => {
+                    compile_normal_object(at, obj, components, parent_stack, depth + 1)
+                }
+                MaybeNullableTypeSchema::Array(arr) => {
+                    compile_normal_array(at, arr, components, parent_stack, depth + 1)
+                }
             }
-        },
+        }
         SchemaDataType::OneOf(oneof) => {
             let mut stack = Stack::new(parent_stack);
             let one_of = oneof
@@ -88,7 +128,11 @@ pub fn compile<'a, 'b>(
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(DataTypeWithSchema {
-                type_or_ref: TypeOrSchemaRef::DataType(DataType::OneOf(OneOfType { one_of })),
+                type_or_ref: TypeOrSchemaRef::DataType(DataType::OneOf(OneOfType {
+                    one_of,
+                    discriminator: oneof.discriminator.as_ref(),
+                    annotations: &oneof.annotations,
+                })),
                 schemas: stack.done(),
             })
         }
@@ -104,7 +148,10 @@ pub fn compile<'a, 'b>(
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(DataTypeWithSchema {
-                type_or_ref: TypeOrSchemaRef::DataType(DataType::AllOf(AllOfType { all_of })),
+                type_or_ref: TypeOrSchemaRef::DataType(DataType::AllOf(AllOfType {
+                    all_of,
+                    annotations: &allof.annotations,
+                })),
                 schemas: stack.done(),
             })
         }
@@ -120,7 +167,11 @@ pub fn compile<'a, 'b>(
                 })
                 .collect::<Result<Vec<_>, _>>()?;
             Ok(DataTypeWithSchema {
-                type_or_ref: TypeOrSchemaRef::DataType(DataType::AnyOf(AnyOfType { any_of })),
+                type_or_ref: TypeOrSchemaRef::DataType(DataType::AnyOf(AnyOfType {
+                    any_of,
+                    discriminator: anyof.discriminator.as_ref(),
+                    annotations: &anyof.annotations,
+                })),
                 schemas: stack.done(),
             })
         }
@@ -158,12 +209,15 @@ pub fn compile_ref<'a, 'b>(
                 let compiled_schema = compile(schema, components, &stack, depth + 1)
                     .map_err(|err| Error::SchemaCompilation(schemas_name.clone(), Box::new(err)))?;
                 match compiled_schema.type_or_ref {
-                    TypeOrSchemaRef::DataType(dt) => Ok(DataTypeWithSchema {
-                        type_or_ref: TypeOrSchemaRef::Reference(schemas_name.clone()),
-                        schemas: indexmap! {
-                            schemas_name => dt
-                        },
-                    }),
+                    TypeOrSchemaRef::DataType(dt) => {
+                        // Schemas referenced by compiled one are kept too
+                        let mut schemas = compiled_schema.schemas;
+                        schemas.insert(schemas_name.clone(), dt);
+                        Ok(DataTypeWithSchema {
+                            type_or_ref: TypeOrSchemaRef::Reference(schemas_name),
+                            schemas,
+                        })
+                    }
                     TypeOrSchemaRef::Reference(sref) => {
                         // reference to reference. In this case we just
                         // follow further reference
@@ -188,9 +242,10 @@ pub fn compile_ref<'a, 'b>(
                             NullableTypeSchema::Object(ref obj) => Ok(obj),
                             _ => Err(Error::ReferenceToUncompatibleObject(schemas_ref.clone())),
                         },
-                        MaybeNullableTypeSchema::Normal(_) => {
-                            Err(Error::ReferenceToUncompatibleObject(schemas_ref.clone()))
-                        }
+                        MaybeNullableTypeSchema::Normal(ref dt) => match dt {
+                            TypeSchema::Object(ref obj) => Ok(obj),
+                            _ => Err(Error::ReferenceToUncompatibleObject(schemas_ref.clone())),
+                        },
                         MaybeNullableTypeSchema::Object(ref obj) => Ok(obj),
                         MaybeNullableTypeSchema::Array(_) => {
                             Err(Error::ReferenceToUncompatibleObject(schemas_ref.clone()))
@@ -245,29 +300,39 @@ pub fn compile_nullable_actual_type<'a, 'b>(
             CompiledType::Nullable(NullableCompiledType::Number(v)),
         ),
         NullableTypeSchema::Object(v) => {
-            compile_nullable_object(v, components, parent_stack, depth)?
+            compile_nullable_object(at, v, components, parent_stack, depth)?
+        }
+        NullableTypeSchema::Array(v) => {
+            compile_nullable_array(at, v, components, parent_stack, depth)?
         }
-        NullableTypeSchema::Array(v) => compile_nullable_array(v, components, parent_stack, depth)?,
     })
 }
 
-pub fn compile_normal_actual_type<'a>(
+pub fn compile_normal_actual_type<'a, 'b>(
     at: &'a SchemaActualType,
     dt: &'a TypeSchema,
+    components: Option<&'a Components>,
+    parent_stack: &'b Stack<'a, 'b>,
+    depth: u32,
 ) -> Result<DataTypeWithSchema<'a>, Error<'a>> {
-    Ok(DataTypeWithSchema {
-        schemas: Schemas::default(),
-        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(ActualType {
-            readonly: at.readonly,
-            writeonly: at.writeonly,
-            compiled_type: match dt {
-                TypeSchema::Null => Err(Error::NotImplemented("TypeSchema::Null"))?,
-                TypeSchema::Boolean(v) => CompiledType::Normal(NormalCompiledType::Boolean(v)),
-                TypeSchema::Integer(v) => CompiledType::Normal(NormalCompiledType::Integer(v)),
-                TypeSchema::String(v) => CompiledType::Normal(NormalCompiledType::String(v)),
-                TypeSchema::Number(v) => CompiledType::Normal(NormalCompiledType::Number(v)),
-            },
-        })),
+    Ok(match dt {
+        TypeSchema::Null => Err(Error::NotImplemented("TypeSchema::Null"))?,
+        TypeSchema::Boolean(v) => DataTypeWithSchema::actual_type(
+            at,
+            CompiledType::Normal(NormalCompiledType::Boolean(v)),
+        ),
+        TypeSchema::Integer(v) => DataTypeWithSchema::actual_type(
+            at,
+            CompiledType::Normal(NormalCompiledType::Integer(v)),
+        ),
+        TypeSchema::String(v) => {
+            DataTypeWithSchema::actual_type(at, CompiledType::Normal(NormalCompiledType::String(v)))
+        }
+        TypeSchema::Number(v) => {
+            DataTypeWithSchema::actual_type(at, CompiledType::Normal(NormalCompiledType::Number(v)))
+        }
+        TypeSchema::Object(v) => compile_normal_object(at, v, components, parent_stack, depth)?,
+        TypeSchema::Array(v) => compile_normal_array(at, v, components, parent_stack, depth)?,
     })
 }
 
@@ -289,10 +354,29 @@ fn compile_object<'a, 'b>(
                 .insert(propname.clone(), cresult.type_or_ref);
         }
     }
+    result.required = sobj.required.iter().flatten().collect();
+    result.additional_properties = match sobj.additional_properties.as_deref() {
+        None => None,
+        Some(AdditionalProperties::Bool(v)) => Some(CompiledAdditionalProperties::Bool(*v)),
+        // Schema without keywords allows any value
+        Some(AdditionalProperties::Schema(SchemaDataType::Empty(_)))
+        | Some(AdditionalProperties::Schema(SchemaDataType::UnknownType(_))) => {
+            Some(CompiledAdditionalProperties::Bool(true))
+        }
+        Some(AdditionalProperties::Schema(sdt)) => {
+            let cresult = compile(sdt, components, &stack, depth + 1)
+                .map_err(|err| Error::AdditionalPropertiesCompilation(Box::new(err)))?;
+            stack.merge(cresult.schemas);
+            Some(CompiledAdditionalProperties::Schema(Box::new(
+                cresult.type_or_ref,
+            )))
+        }
+    };
     Ok((result, stack.done()))
 }
 
 pub fn compile_normal_object<'a, 'b>(
+    at: &'a SchemaActualType,
     sobj: &'a SchemaObject,
     components: Option<&'a Components>,
     parent_stack: &'b Stack<'a, 'b>,
@@ -301,15 +385,15 @@ pub fn compile_normal_object<'a, 'b>(
     let (obj, schemas) = compile_object(sobj, components, parent_stack, depth)?;
     Ok(DataTypeWithSchema {
         schemas,
-        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(ActualType {
-            compiled_type: CompiledType::Normal(NormalCompiledType::Object(obj)),
-            readonly: false,
-            writeonly: false,
-        })),
+        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(Box::new(ActualType::new(
+            at,
+            CompiledType::Normal(NormalCompiledType::Object(obj)),
+        )))),
     })
 }
 
 pub fn compile_nullable_object<'a, 'b>(
+    at: &'a SchemaActualType,
     sobj: &'a SchemaObject,
     components: Option<&'a Components>,
     parent_stack: &'b Stack<'a, 'b>,
@@ -318,11 +402,10 @@ pub fn compile_nullable_object<'a, 'b>(
     let (obj, schemas) = compile_object(sobj, components, parent_stack, depth)?;
     Ok(DataTypeWithSchema {
         schemas,
-        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(ActualType {
-            compiled_type: CompiledType::Nullable(NullableCompiledType::Object(obj)),
-            readonly: false,
-            writeonly: false,
-        })),
+        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(Box::new(ActualType::new(
+            at,
+            CompiledType::Nullable(NullableCompiledType::Object(obj)),
+        )))),
     })
 }
 
@@ -346,6 +429,7 @@ fn compile_array<'a, 'b>(
 }
 
 pub fn compile_nullable_array<'a, 'b>(
+    at: &'a SchemaActualType,
     sarr: &'a SchemaArray,
     components: Option<&'a Components>,
     parent_stack: &'b Stack<'a, 'b>,
@@ -354,10 +438,26 @@ pub fn compile_nullable_array<'a, 'b>(
     let (arr, schemas) = compile_array(sarr, components, parent_stack, depth)?;
     Ok(DataTypeWithSchema {
         schemas,
-        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(ActualType {
-            compiled_type: CompiledType::Nullable(NullableCompiledType::Array(arr)),
-            readonly: false,
-            writeonly: false,
-        })),
+        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(Box::new(ActualType::new(
+            at,
+            CompiledType::Nullable(NullableCompiledType::Array(arr)),
+        )))),
+    })
+}
+
+pub fn compile_normal_array<'a, 'b>(
+    at: &'a SchemaActualType,
+    sarr: &'a SchemaArray,
+    components: Option<&'a Components>,
+    parent_stack: &'b Stack<'a, 'b>,
+    depth: u32,
+) -> Result<DataTypeWithSchema<'a>, Error<'a>> {
+    let (arr, schemas) = compile_array(sarr, components, parent_stack, depth)?;
+    Ok(DataTypeWithSchema {
+        schemas,
+        type_or_ref: TypeOrSchemaRef::DataType(DataType::ActualType(Box::new(ActualType::new(
+            at,
+            CompiledType::Normal(NormalCompiledType::Array(arr)),
+        )))),
     })
 }
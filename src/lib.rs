@@ -3,7 +3,11 @@
 // OpenAPI schema parser / code generator
 //
 
+pub mod check;
+pub mod codegen;
 pub mod compile;
 pub mod model;
 pub mod schema;
+pub mod transform;
 pub mod typing;
+pub mod validate;
@@ -5,134 +5,531 @@
 //
 
 pub mod name;
+pub mod operation;
+pub mod split;
 
+use crate::compile::constraints::Constraints;
 use crate::compile::data_type::ActualType;
 use crate::compile::data_type::CompiledObject;
 use crate::compile::data_type::CompiledType;
 use crate::compile::data_type::DataType;
 use crate::compile::data_type::NormalCompiledType;
+use crate::compile::data_type::NullableCompiledType;
 use crate::compile::data_type::TypeOrSchemaRef;
 use crate::compile::Compiled;
 use crate::schema::data_type::default::NonNullableDefault;
 use crate::schema::data_type::default::NullableDefault;
 use crate::schema::data_type::numerical;
+use crate::schema::data_type::Annotations;
 use crate::schema::data_type::BooleanType;
 use crate::schema::data_type::StringType;
+use crate::schema::discriminator::Discriminator;
+use crate::schema::extensions;
+use crate::schema::extensions::Extensions;
+use crate::schema::external_doc::ExternalDoc;
 use crate::schema::PropertyName;
 use name::Name;
+use serde_json::Value;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Model<'a> {
     Object(Object<'a>),
     Enum(Enum<'a>),
+    // Existing Rust type is used instead of generated one (x-rust-type)
+    RustType(&'a str, Doc<'a>),
+    // Other name of the type (e.g. array or string schema)
+    Alias(SimpleType<'a>, Doc<'a>),
+    // Value matches exactly one (oneOf) or at least one (anyOf) of
+    // variants
+    OneOf(Union<'a>),
+    AnyOf(Union<'a>),
+    // Value matches all of parts
+    AllOf(AllOf<'a>),
 }
 
-#[derive(Debug)]
+// Documentation of the model or property by annotations of its schema
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Doc<'a> {
+    pub title: Option<&'a str>,
+    pub description: Option<&'a str>,
+    pub deprecated: bool,
+    pub external_docs: Option<&'a ExternalDoc>,
+}
+
+#[derive(Debug, Clone)]
 pub struct Object<'a> {
     pub properties: Vec<(&'a PropertyName, PropertyType<'a>)>,
+    pub rust_name: Option<&'a str>,
+    pub doc: Doc<'a>,
+    pub constraints: Constraints<'a>,
+    pub extensions: &'a Extensions,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Enum<'a> {
-    pub members: Vec<Name<'a>>,
+    pub members: Vec<EnumMember<'a>>,
+    pub rust_name: Option<&'a str>,
+    pub doc: Doc<'a>,
+    pub extensions: &'a Extensions,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub struct EnumMember<'a> {
+    pub value: &'a str,
+    // x-enum-varnames
+    pub rust_name: Option<&'a str>,
+    // x-enum-descriptions
+    pub description: Option<&'a str>,
+    // Member is default value of the enumeration
+    pub default: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Union<'a> {
+    pub variants: Vec<SimpleType<'a>>,
+    pub discriminator: Option<&'a Discriminator>,
+    pub doc: Doc<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AllOf<'a> {
+    pub parts: Vec<SimpleType<'a>>,
+    pub doc: Doc<'a>,
+}
+
+#[derive(Debug, Clone)]
 pub struct PropertyType<'a> {
     pub simple_type: SimpleType<'a>,
+    pub required: bool,
     pub readonly: bool,
     pub writeonly: bool,
+    // Default of the inline schema of the property
+    pub default: Option<Value>,
+    // Documentation of the inline schema of the property
+    pub doc: Doc<'a>,
+    pub rust_name: Option<&'a str>,
+    pub constraints: Constraints<'a>,
+    // Extensions of the property schema. Referenced schemas and
+    // compositions have no extensions of the property.
+    pub extensions: Option<&'a Extensions>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum SimpleType<'a> {
     Nullable(NullableType<'a>),
     Normal(NormalType<'a>),
+    RustType(&'a str),
+    // Type of the other model (referenced schema or composition)
+    Named(Name<'a>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NullableType<'a> {
     Null,
     Boolean(&'a BooleanType<NullableDefault<bool>>),
     Integer(&'a numerical::NullableIntegerType),
     Number(&'a numerical::NullableNumberType),
     String(&'a StringType<NullableDefault<String>>),
+    Enum(Name<'a>),
     Object(Name<'a>),
-    Array(Name<'a>),
+    Array(Box<SimpleType<'a>>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum NormalType<'a> {
     Boolean(&'a BooleanType<NonNullableDefault<bool>>),
     Integer(&'a numerical::IntegerType),
     Number(&'a numerical::NumberType),
     String(&'a StringType<NonNullableDefault<String>>),
+    Enum(Name<'a>),
     Object(Name<'a>),
-    Array(Name<'a>),
+    Array(Box<SimpleType<'a>>),
 }
 
-pub fn build<'a>(compiled: &'a Compiled<'a>) -> Vec<(Name<'a>, Model<'a>)> {
-    compiled
-        .schemas
+impl<'a> Doc<'a> {
+    pub fn new(annotations: &'a Annotations) -> Self {
+        Self {
+            title: annotations.title.as_ref().map(|v| v.as_str()),
+            description: annotations.description.as_ref().map(|v| v.as_str()),
+            deprecated: annotations.deprecated,
+            external_docs: annotations.external_docs.as_ref(),
+        }
+    }
+
+    // Documentation of the inline schema
+    fn of(dt: &'a DataType<'a>) -> Self {
+        match dt {
+            DataType::ActualType(t) => Self::new(t.annotations),
+            DataType::OneOf(v) => Self::new(v.annotations),
+            DataType::AnyOf(v) => Self::new(v.annotations),
+            DataType::AllOf(v) => Self::new(v.annotations),
+        }
+    }
+}
+
+impl<'a> Model<'a> {
+    pub fn doc(&self) -> &Doc<'a> {
+        match self {
+            Model::Object(Object { doc, .. })
+            | Model::Enum(Enum { doc, .. })
+            | Model::RustType(_, doc)
+            | Model::Alias(_, doc)
+            | Model::OneOf(Union { doc, .. })
+            | Model::AnyOf(Union { doc, .. })
+            | Model::AllOf(AllOf { doc, .. }) => doc,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error<'a> {
+    Extension(Name<'a>, extensions::Error),
+    // Number of enum values and number of x-enum-varnames
+    EnumVarNames(Name<'a>, usize, usize),
+    // Number of enum values and number of x-enum-descriptions
+    EnumDescriptions(Name<'a>, usize, usize),
+}
+
+type MResult<'a> = Result<Vec<(Name<'a>, Model<'a>)>, Error<'a>>;
+
+pub fn build<'a>(compiled: &'a Compiled<'a>) -> MResult<'a> {
+    let mut models = vec![];
+    for (name, v) in compiled.schemas.iter() {
+        models.extend(build_data_type(Name::Schemas(name), v)?);
+    }
+    Ok(models)
+}
+
+// Models of the named data type. Inline objects, enumerations and
+// compositions get their own models named after their location.
+fn build_data_type<'a>(name: Name<'a>, v: &'a DataType<'a>) -> MResult<'a> {
+    let mut models = vec![];
+    let model = match v {
+        DataType::ActualType(t) => {
+            if let Some(rust_type) = rust_type(&name, t)? {
+                return Ok(vec![(
+                    name,
+                    Model::RustType(rust_type, Doc::new(t.annotations)),
+                )]);
+            }
+            match &t.compiled_type {
+                CompiledType::Nullable(NullableCompiledType::String(StringType {
+                    enumeration: Some(values),
+                    ..
+                }))
+                | CompiledType::Normal(NormalCompiledType::String(StringType {
+                    enumeration: Some(values),
+                    ..
+                })) => return build_enum(name, t, values),
+                CompiledType::Nullable(NullableCompiledType::Object(obj))
+                | CompiledType::Normal(NormalCompiledType::Object(obj)) => {
+                    return build_object(name, t, obj)
+                }
+                _ => Model::Alias(actual_type(&name, t, &mut models)?, Doc::new(t.annotations)),
+            }
+        }
+        DataType::OneOf(v) => Model::OneOf(Union {
+            variants: variants(&name, &v.one_of, &mut models)?,
+            discriminator: v.discriminator,
+            doc: Doc::new(v.annotations),
+        }),
+        DataType::AnyOf(v) => Model::AnyOf(Union {
+            variants: variants(&name, &v.any_of, &mut models)?,
+            discriminator: v.discriminator,
+            doc: Doc::new(v.annotations),
+        }),
+        DataType::AllOf(v) => Model::AllOf(AllOf {
+            parts: variants(&name, &v.all_of, &mut models)?,
+            doc: Doc::new(v.annotations),
+        }),
+    };
+    models.insert(0, (name, model));
+    Ok(models)
+}
+
+fn variants<'a>(
+    name: &Name<'a>,
+    types: &'a [TypeOrSchemaRef<'a>],
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Vec<SimpleType<'a>>, Error<'a>> {
+    types
         .iter()
-        .flat_map(|(name, v)| match v {
-            DataType::ActualType(t) => match &t.compiled_type {
-                CompiledType::Nullable(_) => todo!(),
-                CompiledType::Normal(t) => match t {
-                    NormalCompiledType::Boolean(_) => todo!(),
-                    NormalCompiledType::Object(obj) => build_object(Name::Schemas(name), obj),
-                    NormalCompiledType::Array(_) => todo!(),
-                    NormalCompiledType::Integer(_) => todo!(),
-                    NormalCompiledType::Number(_) => todo!(),
-                    NormalCompiledType::String(_) => todo!(),
-                },
-            },
-            DataType::OneOf(_) => todo!(),
-            DataType::AllOf(_) => todo!(),
-            DataType::AnyOf(_) => todo!(),
-        })
+        .enumerate()
+        .map(|(i, t)| simple_type(Name::Variant(i, Box::new(name.clone())), t, models))
         .collect()
 }
 
-pub fn build_object<'a>(name: Name<'a>, obj: &'a CompiledObject<'a>) -> Vec<(Name<'a>, Model<'a>)> {
+// Type of the value at name. Models of inline types are added to
+// models.
+fn simple_type<'a>(
+    name: Name<'a>,
+    t: &'a TypeOrSchemaRef<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<SimpleType<'a>, Error<'a>> {
+    match t {
+        TypeOrSchemaRef::Reference(sref) => Ok(SimpleType::Named(Name::Schemas(sref))),
+        TypeOrSchemaRef::DataType(DataType::ActualType(t)) => match rust_type(&name, t)? {
+            Some(rust_type) => Ok(SimpleType::RustType(rust_type)),
+            None => actual_type(&name, t, models),
+        },
+        TypeOrSchemaRef::DataType(dt) => {
+            models.extend(build_data_type(name.clone(), dt)?);
+            Ok(SimpleType::Named(name))
+        }
+    }
+}
+
+fn actual_type<'a>(
+    name: &Name<'a>,
+    t: &'a ActualType<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<SimpleType<'a>, Error<'a>> {
+    let item = || Name::Item(Box::new(name.clone()));
+    Ok(match &t.compiled_type {
+        CompiledType::Nullable(tn) => SimpleType::Nullable(match tn {
+            NullableCompiledType::Null => NullableType::Null,
+            NullableCompiledType::Boolean(v) => NullableType::Boolean(v),
+            NullableCompiledType::Integer(v) => NullableType::Integer(v),
+            NullableCompiledType::Number(v) => NullableType::Number(v),
+            NullableCompiledType::String(StringType {
+                enumeration: Some(values),
+                ..
+            }) => {
+                models.extend(build_enum(name.clone(), t, values)?);
+                NullableType::Enum(name.clone())
+            }
+            NullableCompiledType::String(v) => NullableType::String(v),
+            NullableCompiledType::Object(obj) => {
+                models.extend(build_object(name.clone(), t, obj)?);
+                NullableType::Object(name.clone())
+            }
+            NullableCompiledType::Array(arr) => {
+                NullableType::Array(Box::new(simple_type(item(), &arr.items, models)?))
+            }
+        }),
+        CompiledType::Normal(tn) => SimpleType::Normal(match tn {
+            NormalCompiledType::Boolean(v) => NormalType::Boolean(v),
+            NormalCompiledType::Integer(v) => NormalType::Integer(v),
+            NormalCompiledType::Number(v) => NormalType::Number(v),
+            NormalCompiledType::String(StringType {
+                enumeration: Some(values),
+                ..
+            }) => {
+                models.extend(build_enum(name.clone(), t, values)?);
+                NormalType::Enum(name.clone())
+            }
+            NormalCompiledType::String(v) => NormalType::String(v),
+            NormalCompiledType::Object(obj) => {
+                models.extend(build_object(name.clone(), t, obj)?);
+                NormalType::Object(name.clone())
+            }
+            NormalCompiledType::Array(arr) => {
+                NormalType::Array(Box::new(simple_type(item(), &arr.items, models)?))
+            }
+        }),
+    })
+}
+
+pub fn build_object<'a>(
+    name: Name<'a>,
+    t: &'a ActualType<'a>,
+    obj: &'a CompiledObject<'a>,
+) -> MResult<'a> {
+    let mut models = vec![];
     let properties = obj
         .properties
         .iter()
-        .map(|(name, t)| match t {
-            TypeOrSchemaRef::DataType(t) => match &t {
-                DataType::ActualType(t) => match &t.compiled_type {
-                    CompiledType::Nullable(_) => todo!(),
-                    CompiledType::Normal(tn) => match tn {
-                        NormalCompiledType::Boolean(v) => {
-                            (name, build_npt(t, NormalType::Boolean(v)))
-                        }
-                        NormalCompiledType::Object(_) => todo!(),
-                        NormalCompiledType::Array(_) => todo!(),
-                        NormalCompiledType::Integer(v) => {
-                            (name, build_npt(t, NormalType::Integer(v)))
-                        }
-                        NormalCompiledType::Number(v) => {
-                            (name, build_npt(t, NormalType::Number(v)))
-                        }
-                        NormalCompiledType::String(v) => {
-                            (name, build_npt(t, NormalType::String(v)))
-                        }
+        .map(|(pname, pt)| {
+            let pmodel = Name::Property(pname, Box::new(name.clone()));
+            let required = obj.required.contains(&pname);
+            let simple_type = simple_type(pmodel.clone(), pt, &mut models)?;
+            let property = match pt {
+                TypeOrSchemaRef::DataType(DataType::ActualType(t)) => {
+                    build_pt(pmodel, t, simple_type, required)?
+                }
+                TypeOrSchemaRef::DataType(_) | TypeOrSchemaRef::Reference(_) => PropertyType {
+                    simple_type,
+                    required,
+                    readonly: false,
+                    writeonly: false,
+                    default: None,
+                    doc: match pt {
+                        TypeOrSchemaRef::DataType(dt) => Doc::of(dt),
+                        TypeOrSchemaRef::Reference(_) => Doc::default(),
                     },
+                    rust_name: None,
+                    constraints: Constraints::default(),
+                    extensions: None,
                 },
-                DataType::OneOf(_) => todo!(),
-                DataType::AllOf(_) => todo!(),
-                DataType::AnyOf(_) => todo!(),
-            },
-            TypeOrSchemaRef::Reference(_) => todo!(),
+            };
+            Ok((pname, property))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    models.insert(
+        0,
+        (
+            name.clone(),
+            Model::Object(Object {
+                properties,
+                rust_name: rust_name(&name, t)?,
+                doc: Doc::new(t.annotations),
+                constraints: t.constraints,
+                extensions: t.extensions,
+            }),
+        ),
+    );
+    Ok(models)
+}
+
+// Enumeration of string values. Null value of nullable enumeration
+// is not a member.
+fn build_enum<'a>(
+    name: Name<'a>,
+    t: &'a ActualType<'a>,
+    values: &'a [Option<String>],
+) -> MResult<'a> {
+    let values: Vec<&str> = values.iter().flatten().map(String::as_str).collect();
+    let varnames = t
+        .extensions
+        .enum_varnames()
+        .map_err(|err| Error::Extension(name.clone(), err))?;
+    let descriptions = t
+        .extensions
+        .enum_descriptions()
+        .map_err(|err| Error::Extension(name.clone(), err))?;
+    if let Some(varnames) = &varnames {
+        if varnames.len() != values.len() {
+            return Err(Error::EnumVarNames(name, values.len(), varnames.len()));
+        }
+    }
+    if let Some(descriptions) = &descriptions {
+        if descriptions.len() != values.len() {
+            return Err(Error::EnumDescriptions(
+                name,
+                values.len(),
+                descriptions.len(),
+            ));
+        }
+    }
+    let members = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| EnumMember {
+            value,
+            rust_name: varnames.as_ref().map(|v| v[i]),
+            description: descriptions.as_ref().map(|v| v[i]),
+            default: t.default.as_ref().and_then(Value::as_str) == Some(value),
         })
         .collect();
-    vec![(name, Model::Object(Object { properties }))]
+    let rust_name = rust_name(&name, t)?;
+    Ok(vec![(
+        name,
+        Model::Enum(Enum {
+            members,
+            rust_name,
+            doc: Doc::new(t.annotations),
+            extensions: t.extensions,
+        }),
+    )])
 }
 
-fn build_npt<'a>(t: &'a ActualType<'a>, nt: NormalType<'a>) -> PropertyType<'a> {
-    PropertyType {
-        simple_type: SimpleType::Normal(nt),
+fn build_pt<'a>(
+    name: Name<'a>,
+    t: &'a ActualType<'a>,
+    simple_type: SimpleType<'a>,
+    required: bool,
+) -> Result<PropertyType<'a>, Error<'a>> {
+    Ok(PropertyType {
+        simple_type,
+        required,
         readonly: t.readonly,
         writeonly: t.writeonly,
+        default: t.default.clone(),
+        doc: Doc::new(t.annotations),
+        rust_name: rust_name(&name, t)?,
+        constraints: t.constraints,
+        extensions: Some(t.extensions),
+    })
+}
+
+fn rust_type<'a>(name: &Name<'a>, t: &'a ActualType<'a>) -> Result<Option<&'a str>, Error<'a>> {
+    t.extensions
+        .rust_type()
+        .map_err(|err| Error::Extension(name.clone(), err))
+}
+
+fn rust_name<'a>(name: &Name<'a>, t: &'a ActualType<'a>) -> Result<Option<&'a str>, Error<'a>> {
+    t.extensions
+        .rust_name()
+        .map_err(|err| Error::Extension(name.clone(), err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::schema;
+
+    #[test]
+    fn codegen_extensions() {
+        let spec = include_str!("../../test/test-vectors/codegen-extensions.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        assert_eq!(compiled.operations.len(), 2);
+        assert!(!compiled.schemas.keys().any(|v| v.as_str() == "Ignored"));
+
+        let models = build(&compiled).unwrap();
+        let find = |name: &str| {
+            models
+                .iter()
+                .find(|(n, _)| matches!(n, Name::Schemas(v) if v.as_str() == name))
+                .map(|(_, m)| m)
+                .unwrap()
+        };
+        assert!(matches!(
+            find("Money"),
+            Model::RustType("rust_decimal::Decimal", _)
+        ));
+
+        let Model::Enum(status) = find("Status") else {
+            panic!("Status is not enum")
+        };
+        assert_eq!(status.rust_name, Some("PetStatus"));
+        assert_eq!(status.members[1].value, "sold");
+        assert_eq!(status.members[1].rust_name, Some("Sold"));
+        assert_eq!(status.members[1].description, Some("Already has an owner"));
+
+        let Model::Object(pet) = find("Pet") else {
+            panic!("Pet is not object")
+        };
+        assert_eq!(pet.rust_name, Some("Animal"));
+        let (_, name) = &pet.properties[0];
+        assert_eq!(name.rust_name, Some("nickname"));
+        let (_, price) = &pet.properties[1];
+        assert!(matches!(
+            price.simple_type,
+            SimpleType::RustType("rust_decimal::Decimal")
+        ));
+        let (_, kind) = &pet.properties[2];
+        let SimpleType::Normal(NormalType::Enum(kind)) = &kind.simple_type else {
+            panic!("kind is not enum")
+        };
+        let kind = models.iter().find(|(n, _)| n == kind).map(|(_, m)| m);
+        let Some(Model::Enum(kind)) = kind else {
+            panic!("kind enum is not built")
+        };
+        assert_eq!(kind.members[0].rust_name, Some("Feline"));
+    }
+
+    #[test]
+    fn enum_varnames_mismatch() {
+        let spec = include_str!("../../test/test-vectors/codegen-extensions.yaml")
+            .replace("[Available, Sold]", "[Available]");
+        let description: schema::Description = serde_yaml::from_str(&spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        assert!(matches!(
+            build(&compiled),
+            Err(Error::EnumVarNames(_, 2, 1))
+        ));
     }
 }
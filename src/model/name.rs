@@ -3,13 +3,40 @@
 // Name of the model
 //
 
+use crate::model::operation::Place;
+use crate::model::split::Usage;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::operation::OperationId;
+use crate::schema::parameter::Name as ParameterName;
 use crate::schema::path::Path;
+use crate::schema::path_item::OperationType;
+use crate::schema::sref::SRefRequestBody;
+use crate::schema::sref::SRefResponsesName;
 use crate::schema::sref::SRefSchemasObjectName;
+use crate::schema::HeaderName;
 use crate::schema::PropertyName;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Name<'a> {
     Schemas(&'a SRefSchemasObjectName),
-    Property(&'a PropertyName, Box<&'a Name<'a>>),
-    RequestBody(&'a Path),
+    Property(&'a PropertyName, Box<Name<'a>>),
+    // Operation at path. It is named by operationId if any.
+    Operation(&'static OperationType, &'a Path, Option<&'a OperationId>),
+    Parameter(&'a ParameterName, Box<Name<'a>>),
+    // Parameters of the operation at the location
+    Parameters(Place, Box<Name<'a>>),
+    // Request body of the operation
+    RequestBody(Box<Name<'a>>),
+    RequestBodies(&'a SRefRequestBody),
+    // Response of the operation (None is default one)
+    Response(Option<&'a HttpStatusCode>, Box<Name<'a>>),
+    Responses(&'a SRefResponsesName),
+    // Header of the response
+    Header(&'a HeaderName, Box<Name<'a>>),
+    // Items of the array
+    Item(Box<Name<'a>>),
+    // Inline variant of oneOf, anyOf or allOf
+    Variant(usize, Box<Name<'a>>),
+    // Variant of the model for request or response (see split)
+    Usage(Usage, Box<Name<'a>>),
 }
@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: MIT
+//
+// Model of the operation: types of its parameters, request body and
+// responses (result of the operation with variant per status code,
+// status range and default response). Operations at paths are
+// modeled, callbacks and webhooks are not. Inline schemas get models
+// named after the operation (see Name::Operation).
+//
+
+use crate::compile::data_type::TypeOrSchemaRef;
+use crate::compile::operation::parameter::Parameter as CompiledParameter;
+use crate::compile::operation::request_body::RequestBodyOrReference;
+use crate::compile::operation::response_body::Header as CompiledHeader;
+use crate::compile::operation::response_body::ResponseBodyOrReference;
+use crate::compile::operation::Operation as CompiledOperation;
+use crate::compile::Compiled;
+use crate::model::name::Name;
+use crate::model::simple_type;
+use crate::model::Error;
+use crate::model::Model;
+use crate::model::NormalType;
+use crate::model::NullableType;
+use crate::model::SimpleType;
+use crate::schema::header::ContentSchema;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::parameter::Name as ParameterName;
+use crate::schema::parameter::Place as SchemaPlace;
+use crate::schema::parameter::SerializeStyle;
+use crate::schema::HeaderName;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub struct Operation<'a> {
+    // Name::Operation
+    pub name: Name<'a>,
+    pub operation: &'a CompiledOperation<'a>,
+    // Path parameters (in order of the path template), query, header
+    // and cookie ones. Locations without parameters are omitted.
+    pub parameters: Vec<Parameters<'a>>,
+    pub request_body: Option<Body<'a>>,
+    // Specific codes, patterns and default response
+    pub responses: Vec<Response<'a>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Place {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+// Parameters of the operation at the location. They are generated as
+// struct with field per parameter (e.g. ListPetsQuery).
+#[derive(Debug)]
+pub struct Parameters<'a> {
+    // Name::Parameters
+    pub name: Name<'a>,
+    pub place: Place,
+    pub parameters: Vec<Parameter<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Parameter<'a> {
+    pub place: Place,
+    pub name: &'a ParameterName,
+    // String if schema of the parameter is not known
+    pub simple_type: SimpleType<'a>,
+    pub required: bool,
+    // Value of the absent parameter: default of the schema or empty
+    // array. Parameter with default is not optional.
+    pub default: Option<Value>,
+    pub deprecated: bool,
+    pub style: SerializeStyle,
+    pub explode: bool,
+    // Value is serialized as JSON (content of the parameter)
+    pub json: bool,
+}
+
+#[derive(Debug)]
+pub struct Body<'a> {
+    // Type of the JSON content. None if content is not JSON.
+    pub simple_type: Option<SimpleType<'a>>,
+    // Media types (ranges) of the content
+    pub content_types: Vec<&'a str>,
+    pub required: bool,
+}
+
+#[derive(Debug)]
+pub struct Response<'a> {
+    // None is default response
+    pub status: Option<&'a HttpStatusCode>,
+    // Status is 2XX. Default response covers statuses not described
+    // (usually errors), so it is not success.
+    pub success: bool,
+    // None if response has no content
+    pub body: Option<Body<'a>>,
+    pub headers: Vec<Header<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Header<'a> {
+    pub name: &'a HeaderName,
+    // String if schema of the header is not known
+    pub simple_type: SimpleType<'a>,
+    pub required: bool,
+    pub deprecated: bool,
+    // Value is serialized as JSON (content of the header)
+    pub json: bool,
+}
+
+pub fn build<'a>(
+    compiled: &'a Compiled<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Vec<Operation<'a>>, Error<'a>> {
+    compiled
+        .operations
+        .iter()
+        .filter_map(|op| {
+            let path = op.location.path()?;
+            let name = Name::Operation(op.op_type, path, op.operation_id);
+            Some(build_operation(compiled, name, op, models))
+        })
+        .collect()
+}
+
+fn build_operation<'a>(
+    compiled: &'a Compiled<'a>,
+    name: Name<'a>,
+    op: &'a CompiledOperation<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Operation<'a>, Error<'a>> {
+    let places = [
+        (Place::Path, op.path_params.values().collect::<Vec<_>>()),
+        (Place::Query, op.query_params.iter().collect()),
+        (Place::Header, op.header_params.iter().collect()),
+        (Place::Cookie, op.cookie_params.iter().collect()),
+    ];
+    let mut parameters = vec![];
+    for (place, params) in places {
+        if params.is_empty() {
+            continue;
+        }
+        parameters.push(Parameters {
+            name: Name::Parameters(place, Box::new(name.clone())),
+            place,
+            parameters: params
+                .into_iter()
+                .map(|p| parameter(&name, p, models))
+                .collect::<Result<_, _>>()?,
+        });
+    }
+
+    let request_body = match &op.request_body_or_ref {
+        None => None,
+        Some(RequestBodyOrReference::Body(b)) => Some(Body {
+            simple_type: shared_type(
+                Name::RequestBody(Box::new(name.clone())),
+                &b.json_type_or_ref,
+                models,
+            )?,
+            content_types: b.content_types.clone(),
+            required: b.required,
+        }),
+        Some(RequestBodyOrReference::Reference(sref)) => match compiled.request_bodies.get(sref) {
+            Some(b) => Some(Body {
+                simple_type: shared_type(Name::RequestBodies(sref), &b.json_type_or_ref, models)?,
+                content_types: b.content_types.clone(),
+                required: b.required,
+            }),
+            None => None,
+        },
+    };
+
+    let responses = &op.request_responses;
+    let codes = responses
+        .codes
+        .iter()
+        .map(|(code, resp)| (Some(*code), resp));
+    let mut responses = codes
+        .chain(responses.default.iter().map(|resp| (None, resp)))
+        .map(|(status, resp)| {
+            let (resp_name, body) = match resp {
+                ResponseBodyOrReference::Body(b) => {
+                    (Name::Response(status, Box::new(name.clone())), Some(b))
+                }
+                ResponseBodyOrReference::Reference(sref) => {
+                    (Name::Responses(sref), compiled.response_bodies.get(sref))
+                }
+            };
+            let headers = body
+                .iter()
+                .flat_map(|b| &b.headers)
+                .map(|h| header(&resp_name, h, models))
+                .collect::<Result<_, _>>()?;
+            let body = match body {
+                Some(b) if !b.content_types.is_empty() => Some(Body {
+                    simple_type: shared_type(resp_name, &b.json_type_or_ref, models)?,
+                    content_types: b.content_types.clone(),
+                    required: true,
+                }),
+                _ => None,
+            };
+            let success = match status {
+                Some(HttpStatusCode::Specific(code)) => code.code() / 100 == 2,
+                Some(HttpStatusCode::Pattern(p)) => p.class() == 2,
+                None => false,
+            };
+            Ok(Response {
+                status,
+                success,
+                body,
+                headers,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Specific codes are matched before patterns
+    responses.sort_by_key(|resp| match resp.status {
+        Some(HttpStatusCode::Specific(_)) => 0,
+        Some(HttpStatusCode::Pattern(_)) => 1,
+        None => 2,
+    });
+
+    Ok(Operation {
+        name,
+        operation: op,
+        parameters,
+        request_body,
+        responses,
+    })
+}
+
+fn parameter<'a>(
+    op: &Name<'a>,
+    p: &'a CompiledParameter<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Parameter<'a>, Error<'a>> {
+    let sp = p.schema_param;
+    let simple_type = match &p.type_or_ref {
+        Some(t) => simple_type(Name::Parameter(&sp.name, Box::new(op.clone())), t, models)?,
+        None => SimpleType::RustType("String"),
+    };
+    let place = match sp.place {
+        SchemaPlace::Path(_) => Place::Path,
+        SchemaPlace::Query(_) => Place::Query,
+        SchemaPlace::Header(_) => Place::Header,
+        SchemaPlace::Cookie(_) => Place::Cookie,
+    };
+    let style = sp.style();
+    let is_array = matches!(
+        simple_type,
+        SimpleType::Normal(NormalType::Array(_)) | SimpleType::Nullable(NullableType::Array(_))
+    );
+    let default = match sp.schema().and_then(|v| v.default_value()) {
+        Some(v) => Some(v),
+        None if is_array && !sp.is_required() => Some(Value::Array(vec![])),
+        None => None,
+    };
+    Ok(Parameter {
+        place,
+        name: &sp.name,
+        simple_type,
+        required: sp.is_required(),
+        default,
+        deprecated: sp.is_deprecated(),
+        style: style.unwrap_or(SerializeStyle::Simple),
+        explode: sp.explode(),
+        json: style.is_none() && p.type_or_ref.is_some(),
+    })
+}
+
+fn header<'a>(
+    response: &Name<'a>,
+    h: &'a CompiledHeader<'a>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Header<'a>, Error<'a>> {
+    let name = Name::Header(h.name, Box::new(response.clone()));
+    let simple_type = shared_type(name, &h.type_or_ref, models)?;
+    Ok(Header {
+        name: h.name,
+        json: simple_type.is_some() && matches!(h.header.content_schema, ContentSchema::Content(_)),
+        simple_type: simple_type.unwrap_or(SimpleType::RustType("String")),
+        required: h.header.required,
+        deprecated: h.header.deprecated,
+    })
+}
+
+// Type of the JSON body or header. Models of the component response
+// or request body shared by operations are added once.
+fn shared_type<'a>(
+    name: Name<'a>,
+    t: &'a Option<TypeOrSchemaRef<'a>>,
+    models: &mut Vec<(Name<'a>, Model<'a>)>,
+) -> Result<Option<SimpleType<'a>>, Error<'a>> {
+    let Some(t) = t else {
+        return Ok(None);
+    };
+    let mut built = vec![];
+    let simple_type = simple_type(name, t, &mut built)?;
+    let exists = built
+        .first()
+        .is_some_and(|(name, _)| models.iter().any(|(n, _)| n == name));
+    if !exists {
+        models.extend(built);
+    }
+    Ok(Some(simple_type))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    #[test]
+    fn responses() {
+        let spec = include_str!("../../test/test-vectors/client.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = build(&compiled, &mut models).unwrap();
+
+        let statuses = |op: &Operation| {
+            op.responses
+                .iter()
+                .map(|resp| (resp.status.map(|v| v.to_string()), resp.success))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            statuses(&operations[0]),
+            [(Some("200".into()), true), (Some("4XX".into()), false)]
+        );
+        assert_eq!(
+            statuses(&operations[1]),
+            [(Some("201".into()), true), (None, false)]
+        );
+
+        // Content-Type header is described by content
+        let headers = &operations[0].responses[0].headers;
+        let names: Vec<_> = headers.iter().map(|h| h.name.as_str()).collect();
+        assert_eq!(names, ["X-Total-Count", "X-Rate-Limit"]);
+        assert!(headers[0].required && !headers[0].json);
+        assert!(!headers[1].required && headers[1].json);
+        let SimpleType::Normal(NormalType::Object(name)) = &headers[1].simple_type else {
+            panic!("header has model")
+        };
+        assert!(matches!(name, Name::Header(..)));
+        assert!(models.iter().any(|(n, _)| n == name));
+    }
+
+    #[test]
+    fn parameters() {
+        let spec = include_str!("../../test/test-vectors/client.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let operations = build(&compiled, &mut models).unwrap();
+
+        let places: Vec<_> = operations[0].parameters.iter().map(|v| v.place).collect();
+        assert_eq!(places, [Place::Query, Place::Header]);
+        let query = &operations[0].parameters[0];
+        assert!(matches!(query.name, Name::Parameters(Place::Query, _)));
+        let defaults: Vec<_> = query
+            .parameters
+            .iter()
+            .map(|p| (p.name.as_str(), p.default.clone()))
+            .collect();
+        assert_eq!(
+            defaults,
+            [
+                ("limit", None),
+                ("tags", Some(serde_json::json!([]))),
+                ("filter", None),
+                ("order", Some(serde_json::json!("asc"))),
+                ("page", None),
+            ]
+        );
+        assert!(query.parameters[4].deprecated);
+
+        // Path parameters of path item and operation are merged
+        let places: Vec<_> = operations[2].parameters.iter().map(|v| v.place).collect();
+        assert_eq!(places, [Place::Path, Place::Cookie]);
+        assert_eq!(operations[2].parameters[0].parameters.len(), 3);
+    }
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: MIT
+//
+// Split of the models with read-only or write-only properties.
+//
+// Object with such properties (or model referencing one) reached from
+// a request body gets variant without read-only properties (e.g.
+// PetRequest) and reached from a response gets variant without
+// write-only properties (e.g. PetResponse). Other models are shared.
+//
+
+use crate::model::name::Name;
+use crate::model::operation::Operation;
+use crate::model::Model;
+use crate::model::NormalType;
+use crate::model::NullableType;
+use crate::model::SimpleType;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Usage {
+    Request,
+    Response,
+}
+
+pub fn split<'a>(models: &mut Vec<(Name<'a>, Model<'a>)>, operations: &mut [Operation<'a>]) {
+    let split = to_split(models);
+    if split.is_empty() {
+        return;
+    }
+    let mut used = vec![];
+    for op in operations.iter_mut() {
+        if let Some(t) = op
+            .request_body
+            .as_mut()
+            .and_then(|b| b.simple_type.as_mut())
+        {
+            rename(t, Usage::Request, &split, &mut used);
+        }
+        for resp in op.responses.iter_mut() {
+            if let Some(t) = resp.body.as_mut().and_then(|b| b.simple_type.as_mut()) {
+                rename(t, Usage::Response, &split, &mut used);
+            }
+        }
+    }
+
+    // Variants are added next to the model and may use other variants
+    let mut i = 0;
+    while i < used.len() {
+        let (usage, name) = used[i].clone();
+        i += 1;
+        let Some(position) = models.iter().position(|(n, _)| *n == name) else {
+            continue;
+        };
+        let mut model = models[position].1.clone();
+        if let Model::Object(obj) = &mut model {
+            obj.properties.retain(|(_, p)| match usage {
+                Usage::Request => !p.readonly,
+                Usage::Response => !p.writeonly,
+            });
+        }
+        for t in simple_types_mut(&mut model) {
+            rename(t, usage, &split, &mut used);
+        }
+        let position = models
+            .iter()
+            .rposition(|(n, _)| matches!(n, Name::Usage(_, parent) if **parent == name))
+            .unwrap_or(position);
+        models.insert(position + 1, (Name::Usage(usage, Box::new(name)), model));
+    }
+}
+
+// Names of the models with read-only or write-only properties and of
+// the models referencing them
+fn to_split<'a>(models: &[(Name<'a>, Model<'a>)]) -> HashSet<Name<'a>> {
+    let mut split: HashSet<Name> = models
+        .iter()
+        .filter(|(_, model)| match model {
+            Model::Object(obj) => obj
+                .properties
+                .iter()
+                .any(|(_, p)| p.readonly || p.writeonly),
+            _ => false,
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    loop {
+        let referencing: Vec<Name> = models
+            .iter()
+            .filter(|(name, model)| {
+                !split.contains(name)
+                    && simple_types(model)
+                        .into_iter()
+                        .any(|t| names(t).into_iter().any(|n| split.contains(n)))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if referencing.is_empty() {
+            return split;
+        }
+        split.extend(referencing);
+    }
+}
+
+// Use variants of the split models in the type
+fn rename<'a>(
+    t: &mut SimpleType<'a>,
+    usage: Usage,
+    split: &HashSet<Name<'a>>,
+    used: &mut Vec<(Usage, Name<'a>)>,
+) {
+    let name = match t {
+        SimpleType::Named(name)
+        | SimpleType::Normal(NormalType::Object(name))
+        | SimpleType::Nullable(NullableType::Object(name)) => name,
+        SimpleType::Normal(NormalType::Array(items))
+        | SimpleType::Nullable(NullableType::Array(items)) => {
+            return rename(items, usage, split, used)
+        }
+        _ => return,
+    };
+    if !split.contains(name) {
+        return;
+    }
+    if !used.iter().any(|(u, n)| *u == usage && n == name) {
+        used.push((usage, name.clone()));
+    }
+    *name = Name::Usage(usage, Box::new(name.clone()));
+}
+
+// Names of the models used by the type
+fn names<'t, 'a>(t: &'t SimpleType<'a>) -> Vec<&'t Name<'a>> {
+    match t {
+        SimpleType::Named(name)
+        | SimpleType::Normal(NormalType::Object(name))
+        | SimpleType::Nullable(NullableType::Object(name)) => vec![name],
+        SimpleType::Normal(NormalType::Array(items))
+        | SimpleType::Nullable(NullableType::Array(items)) => names(items),
+        _ => vec![],
+    }
+}
+
+fn simple_types<'m, 'a>(model: &'m Model<'a>) -> Vec<&'m SimpleType<'a>> {
+    match model {
+        Model::Object(obj) => obj.properties.iter().map(|(_, p)| &p.simple_type).collect(),
+        Model::Alias(t, _) => vec![t],
+        Model::OneOf(u) | Model::AnyOf(u) => u.variants.iter().collect(),
+        Model::AllOf(all_of) => all_of.parts.iter().collect(),
+        Model::Enum(_) | Model::RustType(..) => vec![],
+    }
+}
+
+fn simple_types_mut<'m, 'a>(model: &'m mut Model<'a>) -> Vec<&'m mut SimpleType<'a>> {
+    match model {
+        Model::Object(obj) => obj
+            .properties
+            .iter_mut()
+            .map(|(_, p)| &mut p.simple_type)
+            .collect(),
+        Model::Alias(t, _) => vec![t],
+        Model::OneOf(u) | Model::AnyOf(u) => u.variants.iter_mut().collect(),
+        Model::AllOf(all_of) => all_of.parts.iter_mut().collect(),
+        Model::Enum(_) | Model::RustType(..) => vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::model;
+    use crate::schema;
+
+    fn body<'a>(t: &Option<SimpleType<'a>>) -> Name<'a> {
+        let Some(SimpleType::Named(name)) = t else {
+            panic!("body is not named")
+        };
+        name.clone()
+    }
+
+    #[test]
+    fn read_write() {
+        let spec = include_str!("../../test/test-vectors/read-write.yaml");
+        let description: schema::Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile::compile(&description).unwrap();
+        let mut models = model::build(&compiled).unwrap();
+        let mut operations = model::operation::build(&compiled, &mut models).unwrap();
+        split(&mut models, &mut operations);
+
+        let properties = |name: &Name| {
+            let Some((_, Model::Object(obj))) = models.iter().find(|(n, _)| n == name) else {
+                panic!("{name:?} is not object")
+            };
+            obj.properties
+                .iter()
+                .map(|(pname, _)| pname.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        // Owner is split as it references Pet
+        let request = body(&operations[0].request_body.as_ref().unwrap().simple_type);
+        let response = body(
+            &operations[0].responses[0]
+                .body
+                .as_ref()
+                .unwrap()
+                .simple_type,
+        );
+        assert!(matches!(request, Name::Usage(Usage::Request, _)));
+        assert!(matches!(response, Name::Usage(Usage::Response, _)));
+        assert_eq!(properties(&request), ["name", "pets", "tag"]);
+        assert_eq!(properties(&response), ["id", "name", "pets", "tag"]);
+        let Some((_, Model::Object(owner))) = models.iter().find(|(n, _)| *n == request) else {
+            panic!("owner request is not object")
+        };
+        let SimpleType::Normal(NormalType::Array(pet)) = &owner.properties[1].1.simple_type else {
+            panic!("pets is not array")
+        };
+        let SimpleType::Named(pet) = &**pet else {
+            panic!("pet is not named")
+        };
+        assert!(matches!(pet, Name::Usage(Usage::Request, _)));
+        assert_eq!(properties(pet), ["name", "password"]);
+        let SimpleType::Named(tag) = &owner.properties[2].1.simple_type else {
+            panic!("tag is not named")
+        };
+        assert!(matches!(tag, Name::Schemas(_)));
+
+        // Tag has no variants
+        let variants: Vec<_> = models
+            .iter()
+            .filter(|(n, _)| matches!(n, Name::Usage(..)))
+            .collect();
+        assert_eq!(variants.len(), 4);
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Callback Object
+//
+
+use crate::schema::extensions::Extensions;
+use crate::schema::path_item::PathItem;
+use crate::schema::reference::Reference;
+use crate::typing::TaggedString;
+use serde::de::Deserializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Callback {
+    #[serde(flatten)]
+    pub expressions: indexmap::IndexMap<Expression, PathItem>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum CallbackOrReference {
+    Reference(Reference),
+    Callback(Callback),
+}
+
+// Runtime expression that identifies URL of the callback, e.g.
+// {$request.body#/callbackUrl}
+pub type Expression = TaggedString<CallbackExpressionTag>;
+pub enum CallbackExpressionTag {}
+
+// Name of the callback inside operation or components
+pub type Name = TaggedString<CallbackNameTag>;
+pub enum CallbackNameTag {}
+
+// Expressions and extensions share the same keys namespace so
+// Callback cannot be derived.
+impl<'de> Deserialize<'de> for Callback {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LocalVisitor;
+
+        impl<'de> Visitor<'de> for LocalVisitor {
+            type Value = Callback;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("map of runtime expressions to path items")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut result = Callback {
+                    expressions: indexmap::IndexMap::default(),
+                    extensions: Extensions::default(),
+                };
+                while let Some(key) = map.next_key::<String>()? {
+                    if Extensions::is_extension(&key) {
+                        result.extensions.insert(key, map.next_value()?);
+                    } else {
+                        result
+                            .expressions
+                            .insert(Expression::new(key), map.next_value()?);
+                    }
+                }
+                Ok(result)
+            }
+        }
+
+        de.deserialize_map(LocalVisitor)
+    }
+}
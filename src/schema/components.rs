@@ -4,21 +4,37 @@
 // Components object
 //
 
+use crate::schema::callback::Callback;
+use crate::schema::callback::CallbackOrReference;
 use crate::schema::data_type::DataType;
+use crate::schema::example::Example;
+use crate::schema::example::ExampleOrReference;
+use crate::schema::extensions::Extensions;
+use crate::schema::header::Header;
 use crate::schema::header::HeaderOrReference;
+use crate::schema::link::Link;
+use crate::schema::link::LinkOrReference;
 use crate::schema::parameter::Parameter;
 use crate::schema::parameter::ParameterOrReference;
 use crate::schema::request_body::RequestBody;
 use crate::schema::request_body::RequestBodyOrReference;
 use crate::schema::response::Response;
 use crate::schema::responses::ResponseOrReference;
+use crate::schema::security_scheme::SecurityScheme;
+use crate::schema::security_scheme::SecuritySchemeOrReference;
+use crate::schema::sref::SRefCallbackName;
+use crate::schema::sref::SRefExampleName;
+use crate::schema::sref::SRefHeaderName;
+use crate::schema::sref::SRefLinkName;
 use crate::schema::sref::SRefParameter;
 use crate::schema::sref::SRefRequestBody;
 use crate::schema::sref::SRefResponsesName;
 use crate::schema::sref::SRefSchemasObjectName;
+use crate::schema::sref::SRefSecuritySchemeName;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Components {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schemas: Option<indexmap::IndexMap<SRefSchemasObjectName, DataType>>,
@@ -30,11 +46,17 @@ pub struct Components {
     pub request_bodies: Option<indexmap::IndexMap<SRefRequestBody, RequestBodyOrReference>>,
     #[serde(rename = "headers", skip_serializing_if = "Option::is_none")]
     pub headers: Option<indexmap::IndexMap<String, HeaderOrReference>>,
-    // TODO:
-    // examples
-    // securitySchemes
-    // links
-    // callbacks
+    #[serde(rename = "securitySchemes", skip_serializing_if = "Option::is_none")]
+    pub security_schemes:
+        Option<indexmap::IndexMap<SRefSecuritySchemeName, SecuritySchemeOrReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<indexmap::IndexMap<SRefCallbackName, CallbackOrReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<indexmap::IndexMap<SRefLinkName, LinkOrReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<indexmap::IndexMap<SRefExampleName, ExampleOrReference>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 const MAX_DEPTH: u32 = 1024;
@@ -52,7 +74,7 @@ impl Components {
                 .as_ref()
                 .and_then(|ps| ps.get(sref))
                 .and_then(|por| match por {
-                    ParameterOrReference::Parameter(p) => Some(p),
+                    ParameterOrReference::Parameter(p) => Some(p.as_ref()),
                     ParameterOrReference::Reference(sref) => sref
                         .sref
                         .parameter_sref()
@@ -94,7 +116,7 @@ impl Components {
                 .as_ref()
                 .and_then(|responses| responses.get(sref))
                 .and_then(|por| match por {
-                    ResponseOrReference::Response(x) => Some(x),
+                    ResponseOrReference::Response(x) => Some(x.as_ref()),
                     ResponseOrReference::Reference(sref) => sref
                         .sref
                         .responses_sref()
@@ -103,6 +125,115 @@ impl Components {
         }
     }
 
+    pub fn find_header(&self, sref: &SRefHeaderName) -> Option<&Header> {
+        self.do_find_header(sref, 0)
+    }
+
+    fn do_find_header(&self, sref: &SRefHeaderName, depth: u32) -> Option<&Header> {
+        if depth > MAX_DEPTH {
+            None
+        } else {
+            self.headers
+                .as_ref()
+                .and_then(|headers| headers.get(sref.as_str()))
+                .and_then(|hor| match hor {
+                    HeaderOrReference::Header(x) => Some(x.as_ref()),
+                    HeaderOrReference::Reference(sref) => sref
+                        .sref
+                        .header_sref()
+                        .and_then(|sref| self.do_find_header(&sref, depth + 1)),
+                })
+        }
+    }
+
+    pub fn find_security_scheme(&self, sref: &SRefSecuritySchemeName) -> Option<&SecurityScheme> {
+        self.do_find_security_scheme(sref, 0)
+    }
+
+    fn do_find_security_scheme(
+        &self,
+        sref: &SRefSecuritySchemeName,
+        depth: u32,
+    ) -> Option<&SecurityScheme> {
+        if depth > MAX_DEPTH {
+            None
+        } else {
+            self.security_schemes
+                .as_ref()
+                .and_then(|schemes| schemes.get(sref))
+                .and_then(|sor| match sor {
+                    SecuritySchemeOrReference::SecurityScheme(x) => Some(x),
+                    SecuritySchemeOrReference::Reference(sref) => sref
+                        .sref
+                        .security_scheme_sref()
+                        .and_then(|sref| self.do_find_security_scheme(&sref, depth + 1)),
+                })
+        }
+    }
+
+    pub fn find_callback(&self, sref: &SRefCallbackName) -> Option<&Callback> {
+        self.do_find_callback(sref, 0)
+    }
+
+    fn do_find_callback(&self, sref: &SRefCallbackName, depth: u32) -> Option<&Callback> {
+        if depth > MAX_DEPTH {
+            None
+        } else {
+            self.callbacks
+                .as_ref()
+                .and_then(|callbacks| callbacks.get(sref))
+                .and_then(|cor| match cor {
+                    CallbackOrReference::Callback(x) => Some(x),
+                    CallbackOrReference::Reference(sref) => sref
+                        .sref
+                        .callback_sref()
+                        .and_then(|sref| self.do_find_callback(&sref, depth + 1)),
+                })
+        }
+    }
+
+    pub fn find_example(&self, sref: &SRefExampleName) -> Option<&Example> {
+        self.do_find_example(sref, 0)
+    }
+
+    fn do_find_example(&self, sref: &SRefExampleName, depth: u32) -> Option<&Example> {
+        if depth > MAX_DEPTH {
+            None
+        } else {
+            self.examples
+                .as_ref()
+                .and_then(|examples| examples.get(sref))
+                .and_then(|eor| match eor {
+                    ExampleOrReference::Example(x) => Some(x),
+                    ExampleOrReference::Reference(sref) => sref
+                        .sref
+                        .example_sref()
+                        .and_then(|sref| self.do_find_example(&sref, depth + 1)),
+                })
+        }
+    }
+
+    pub fn find_link(&self, sref: &SRefLinkName) -> Option<&Link> {
+        self.do_find_link(sref, 0)
+    }
+
+    fn do_find_link(&self, sref: &SRefLinkName, depth: u32) -> Option<&Link> {
+        if depth > MAX_DEPTH {
+            None
+        } else {
+            self.links
+                .as_ref()
+                .and_then(|links| links.get(sref))
+                .and_then(|lor| match lor {
+                    LinkOrReference::Link(x) => Some(x.as_ref()),
+                    LinkOrReference::Reference(sref) => sref
+                        .sref
+                        .link_sref()
+                        .and_then(|sref| self.do_find_link(&sref, depth + 1)),
+                })
+        }
+    }
+
     pub fn find_schema_by_name(&self, sref: &SRefSchemasObjectName) -> Option<&DataType> {
         self.schemas.as_ref().and_then(|schemas| schemas.get(sref))
     }
@@ -9,8 +9,9 @@
 
 use crate::schema::data_type::DataType;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Array {
     #[serde(rename = "prefixItems", skip_serializing_if = "Option::is_none")]
     pub prefix_items: Option<Vec<DataType>>,
@@ -18,4 +19,17 @@ pub struct Array {
     pub items: Option<Box<DataType>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contains: Option<Box<DataType>>,
+    #[serde(rename = "minItems", skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<u64>,
+    #[serde(rename = "maxItems", skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<u64>,
+    #[serde(
+        rename = "uniqueItems",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
+    pub unique_items: bool,
+    // Null default of nullable array is the same as no default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
 }
@@ -4,9 +4,25 @@
 // Nullable and Non-nullable default values
 //
 
+use crate::schema::data_type::numerical::IntegerType;
+use crate::schema::data_type::numerical::IntegerWithFormat;
+use crate::schema::data_type::numerical::NullableIntegerType;
+use crate::schema::data_type::numerical::NullableIntegerWithFormat;
+use crate::schema::data_type::numerical::NullableNumberType;
+use crate::schema::data_type::numerical::NullableNumberWithFormat;
+use crate::schema::data_type::numerical::NumberType;
+use crate::schema::data_type::numerical::NumberWithFormat;
+use crate::schema::data_type::ActualType;
+use crate::schema::data_type::DataType;
+use crate::schema::data_type::MaybeNullableTypeSchema;
+use crate::schema::data_type::NullableTypeSchema;
+use crate::schema::data_type::TypeSchema;
 use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(bound(serialize = "T: Serialize"))]
 pub struct NullableDefault<T: for<'a> Deserialize<'a>> {
     #[serde(
         rename = "default",
@@ -17,8 +33,81 @@ pub struct NullableDefault<T: for<'a> Deserialize<'a>> {
     pub value: Option<Option<T>>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NonNullableDefault<T> {
     #[serde(rename = "default", skip_serializing_if = "Option::is_none")]
     pub value: Option<T>,
 }
+
+impl DataType {
+    // Default value of the schema (if any). Defaults of referenced
+    // schemas are not resolved.
+    pub fn default_value(&self) -> Option<Value> {
+        match self {
+            DataType::ActualType(t) => t.default_value(),
+            _ => None,
+        }
+    }
+}
+
+impl ActualType {
+    pub fn default_value(&self) -> Option<Value> {
+        match &self.type_schema {
+            MaybeNullableTypeSchema::Normal(t) => match t {
+                TypeSchema::Boolean(t) => t.default.value.map(Value::from),
+                TypeSchema::Integer(t) => match t {
+                    IntegerType::WithFormat(IntegerWithFormat::Int32(t))
+                    | IntegerType::WithOutFormat(t) => t.default_info.value.map(Value::from),
+                    IntegerType::WithFormat(IntegerWithFormat::Int64(t)) => {
+                        t.default_info.value.map(Value::from)
+                    }
+                },
+                TypeSchema::Number(t) => match t {
+                    NumberType::WithFormat(NumberWithFormat::Float(t))
+                    | NumberType::WithOutFormat(t) => {
+                        t.default_info.value.map(|v| Value::from(f64::from(v)))
+                    }
+                    NumberType::WithFormat(NumberWithFormat::Double(t)) => {
+                        t.default_info.value.map(Value::from)
+                    }
+                },
+                TypeSchema::String(t) => t.default.value.clone().map(Value::from),
+                TypeSchema::Object(t) => t.default.clone(),
+                TypeSchema::Array(t) => t.default.clone(),
+                TypeSchema::Null => None,
+            },
+            MaybeNullableTypeSchema::Nullable(t) => match &t.schema {
+                NullableTypeSchema::Boolean(t) => nullable(t.default.value, Value::from),
+                NullableTypeSchema::Integer(t) => match t {
+                    NullableIntegerType::WithFormat(NullableIntegerWithFormat::Int32(t))
+                    | NullableIntegerType::WithOutFormat(t) => {
+                        nullable(t.default_info.value, Value::from)
+                    }
+                    NullableIntegerType::WithFormat(NullableIntegerWithFormat::Int64(t)) => {
+                        nullable(t.default_info.value, Value::from)
+                    }
+                },
+                NullableTypeSchema::Number(t) => match t {
+                    NullableNumberType::WithFormat(NullableNumberWithFormat::Float(t))
+                    | NullableNumberType::WithOutFormat(t) => {
+                        nullable(t.default_info.value, |v| Value::from(f64::from(v)))
+                    }
+                    NullableNumberType::WithFormat(NullableNumberWithFormat::Double(t)) => {
+                        nullable(t.default_info.value, Value::from)
+                    }
+                },
+                NullableTypeSchema::String(t) => nullable(t.default.value.clone(), Value::from),
+                NullableTypeSchema::Object(t) => t.default.clone(),
+                NullableTypeSchema::Array(t) => t.default.clone(),
+                NullableTypeSchema::Null => None,
+            },
+            MaybeNullableTypeSchema::Object(t) => t.default.clone(),
+            MaybeNullableTypeSchema::Array(t) => t.default.clone(),
+        }
+    }
+}
+
+// Default of the nullable type: explicit null is Value::Null
+fn nullable<T>(value: Option<Option<T>>, f: impl Fn(T) -> Value) -> Option<Value> {
+    value.map(|v| v.map_or(Value::Null, f))
+}
@@ -11,44 +11,84 @@ pub mod object;
 
 use crate::schema::data_type::default::NonNullableDefault;
 use crate::schema::data_type::default::NullableDefault;
+use crate::schema::data_type::object::Ecma262RegEx;
 use crate::schema::discriminator::Discriminator;
+use crate::schema::extensions::Extensions;
 use crate::schema::external_doc::ExternalDoc;
 use crate::schema::reference::Reference;
 use crate::typing::AlwaysTrue;
+use crate::typing::TaggedString;
 use serde::de;
 use serde::de::Deserializer;
 use serde::de::MapAccess;
 use serde::de::Visitor;
+use serde::ser::SerializeMap;
+use serde::ser::Serializer;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum DataType {
     Reference(Reference),
-    OneOf(OneOfType),
-    AllOf(AllOfType),
-    AnyOf(AnyOfType),
+    OneOf(Box<OneOfType>),
+    AllOf(Box<AllOfType>),
+    AnyOf(Box<AnyOfType>),
     ActualType(Box<ActualType>),
     Empty(EmptyType),
     UnknownType(UnknownType),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ActualType {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<Discriminator>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub external_docs: Option<ExternalDoc>,
+    #[serde(flatten)]
+    pub annotations: Annotations,
     #[serde(flatten)]
     pub type_schema: MaybeNullableTypeSchema,
     // 3.0.X specification (removed by 3.1.X)
-    #[serde(rename = "readOnly", default)]
+    #[serde(
+        rename = "readOnly",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
     pub readonly: bool,
-    #[serde(rename = "writeOnly", default)]
+    #[serde(
+        rename = "writeOnly",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
     pub writeonly: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_yaml::Value>,
+    // 3.1.X specification (JSON Schema keyword)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<serde_yaml::Value>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
-#[derive(Deserialize, Debug)]
+// Annotations of the schema those document the value
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+pub struct Annotations {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<Title>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
+    pub deprecated: bool,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+}
+
+pub type Title = TaggedString<SchemaTitleTag>;
+pub enum SchemaTitleTag {}
+
+pub type Description = TaggedString<SchemaDescriptionTag>;
+pub enum SchemaDescriptionTag {}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum MaybeNullableTypeSchema {
     Nullable(NullableTypeSchemaProxy),
@@ -58,20 +98,24 @@ pub enum MaybeNullableTypeSchema {
     Array(array::Array),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct NullableTypeSchemaProxy {
     pub nullable: AlwaysTrue,
     #[serde(flatten)]
     pub schema: NullableTypeSchema,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum TypeSchema {
     #[serde(rename = "null")]
     Null,
     #[serde(rename = "boolean")]
     Boolean(BooleanType<NonNullableDefault<bool>>),
+    #[serde(rename = "object")]
+    Object(object::Object),
+    #[serde(rename = "array")]
+    Array(array::Array),
     #[serde(rename = "integer")]
     Integer(numerical::IntegerType),
     #[serde(rename = "number")]
@@ -80,7 +124,7 @@ pub enum TypeSchema {
     String(StringType<NonNullableDefault<String>>),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum NullableTypeSchema {
     #[serde(rename = "null")]
@@ -99,46 +143,70 @@ pub enum NullableTypeSchema {
     String(StringType<NullableDefault<String>>),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct StringType<D> {
     #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
     pub min_length: Option<u64>,
     #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
     pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Ecma262RegEx>,
+    // null is allowed as a member of the nullable enumeration
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enumeration: Option<Vec<Option<String>>>,
     #[serde(flatten)]
     pub default: D,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct BooleanType<D> {
     #[serde(flatten)]
     pub default: D,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct OneOfType {
     #[serde(rename = "oneOf")]
     pub one_of: Vec<DataType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+    #[serde(flatten)]
+    pub annotations: Annotations,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct AllOfType {
     #[serde(rename = "allOf")]
     pub all_of: Vec<DataType>,
+    #[serde(flatten)]
+    pub annotations: Annotations,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct AnyOfType {
     #[serde(rename = "anyOf")]
     pub any_of: Vec<DataType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminator: Option<Discriminator>,
+    #[serde(flatten)]
+    pub annotations: Annotations,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct UnknownType {}
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct EmptyType {}
 
+impl Serialize for EmptyType {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_map(Some(0))?.end()
+    }
+}
+
 impl<'de> Deserialize<'de> for EmptyType {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
@@ -7,22 +7,23 @@
 use crate::schema::data_type::default::NonNullableDefault;
 use crate::schema::data_type::default::NullableDefault;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum NumberType {
     WithFormat(NumberWithFormat),
     WithOutFormat(Float),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum NullableNumberType {
     WithFormat(NullableNumberWithFormat),
     WithOutFormat(NullableFloat),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "format")]
 pub enum NumberWithFormat {
     #[serde(rename = "float")]
@@ -31,7 +32,7 @@ pub enum NumberWithFormat {
     Double(Double),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "format")]
 pub enum NullableNumberWithFormat {
     #[serde(rename = "float")]
@@ -45,21 +46,21 @@ pub type Double = Numerical<f64, NonNullableDefault<f64>>;
 pub type NullableFloat = Numerical<f32, NullableDefault<f32>>;
 pub type NullableDouble = Numerical<f64, NullableDefault<f64>>;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum IntegerType {
     WithFormat(IntegerWithFormat),
     WithOutFormat(Integer32),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum NullableIntegerType {
     WithFormat(NullableIntegerWithFormat),
     WithOutFormat(NullableInteger32),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "format")]
 pub enum IntegerWithFormat {
     #[serde(rename = "int32")]
@@ -68,7 +69,7 @@ pub enum IntegerWithFormat {
     Int64(Integer64),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "format")]
 pub enum NullableIntegerWithFormat {
     #[serde(rename = "int32")]
@@ -82,16 +83,26 @@ pub type Integer64 = Numerical<i64, NonNullableDefault<i64>>;
 pub type NullableInteger32 = Numerical<i32, NullableDefault<i32>>;
 pub type NullableInteger64 = Numerical<i64, NullableDefault<i64>>;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Numerical<T, DefaultV> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub minimum: Option<T>,
-    #[serde(rename = "exclusiveMinimum", default)]
+    #[serde(
+        rename = "exclusiveMinimum",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
     pub exclisive_minimum: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<T>,
-    #[serde(rename = "exclusiveMaximum", default)]
+    #[serde(
+        rename = "exclusiveMaximum",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )]
     pub exclisive_maximum: bool,
+    #[serde(rename = "multipleOf", skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<T>,
     #[serde(flatten)]
     pub default_info: DefaultV,
 }
@@ -11,11 +11,14 @@ use crate::schema::data_type::DataType;
 use crate::schema::PropertyName;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Object {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<indexmap::IndexMap<PropertyName, DataType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<PropertyName>>,
     #[serde(rename = "patternProperties", skip_serializing_if = "Option::is_none")]
     pub pattern_properties: Option<indexmap::IndexMap<Ecma262RegEx, DataType>>,
     #[serde(
@@ -25,15 +28,22 @@ pub struct Object {
     pub additional_properties: Option<Box<AdditionalProperties>>,
     #[serde(rename = "propertyNames", skip_serializing_if = "Option::is_none")]
     pub property_names: Option<Box<DataType>>,
+    #[serde(rename = "minProperties", skip_serializing_if = "Option::is_none")]
+    pub min_properties: Option<u64>,
+    #[serde(rename = "maxProperties", skip_serializing_if = "Option::is_none")]
+    pub max_properties: Option<u64>,
+    // Null default of nullable object is the same as no default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum AdditionalProperties {
     Bool(bool),
     Schema(DataType),
 }
 
-// TODO:
+// TODO: check syntax of the regular expression
 pub type Ecma262RegEx = TaggedString<Ecma262RegExTag>;
 pub enum Ecma262RegExTag {}
@@ -4,14 +4,19 @@
 // Discriminator Object
 //
 
+use crate::schema::extensions::Extensions;
 use crate::schema::PropertyName;
 use crate::schema::PropertyStringValue;
 use crate::schema::SRef;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Discriminator {
     #[serde(rename = "propertyName")]
     pub property_name: PropertyName,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub mapping: Option<indexmap::IndexMap<PropertyStringValue, SRef>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
@@ -5,13 +5,18 @@
 //
 
 use crate::schema::content_type::ContentType;
+use crate::schema::extensions::Extensions;
 use crate::schema::header::Header;
 use crate::schema::HeaderName;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Encoding {
-    #[serde(rename = "contentType")]
+    #[serde(rename = "contentType", skip_serializing_if = "Option::is_none")]
     pub content_type: Option<ContentType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<indexmap::IndexMap<HeaderName, Header>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
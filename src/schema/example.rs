@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Example Object
+//
+
+use crate::schema::extensions::Extensions;
+use crate::schema::reference::Reference;
+use crate::typing::TaggedString;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Example {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Summary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    // value and externalValue are mutually exclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    #[serde(rename = "externalValue", skip_serializing_if = "Option::is_none")]
+    pub external_value: Option<ExternalValue>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ExampleOrReference {
+    Reference(Reference),
+    Example(Example),
+}
+
+pub type Examples = indexmap::IndexMap<Name, ExampleOrReference>;
+
+// Name of the example inside media type, parameter or header
+pub type Name = TaggedString<ExampleNameTag>;
+pub enum ExampleNameTag {}
+
+pub type Summary = TaggedString<ExampleSummaryTag>;
+pub enum ExampleSummaryTag {}
+
+pub type Description = TaggedString<ExampleDescriptionTag>;
+pub enum ExampleDescriptionTag {}
+
+// URI of the literal example
+pub type ExternalValue = TaggedString<ExternalValueTag>;
+pub enum ExternalValueTag {}
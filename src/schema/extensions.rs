@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Specification Extensions (x-* fields)
+//
+
+use serde::de;
+use serde::de::Deserializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
+
+// Collects all fields those start with "x-" and ignores others. Is
+// intended to be used with #[serde(flatten)].
+#[derive(Serialize, Debug, Default, PartialEq, Clone)]
+pub struct Extensions(indexmap::IndexMap<String, Value>);
+
+const PREFIX: &str = "x-";
+
+// Extensions those are interpreted by code generation
+pub const RUST_TYPE: &str = "x-rust-type";
+pub const RUST_NAME: &str = "x-rust-name";
+pub const ENUM_VARNAMES: &str = "x-enum-varnames";
+pub const ENUM_DESCRIPTIONS: &str = "x-enum-descriptions";
+pub const IGNORE: &str = "x-ignore";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // Extension (key) has value of unexpected type (expected)
+    WrongType(&'static str, &'static str),
+}
+
+impl Extensions {
+    pub fn is_extension(key: &str) -> bool {
+        key.starts_with(PREFIX)
+    }
+
+    // Existing Rust type (path) to be used instead of generated one
+    pub fn rust_type(&self) -> Result<Option<&str>, Error> {
+        self.get_str(RUST_TYPE)
+    }
+
+    // Identifier of generated type or property
+    pub fn rust_name(&self) -> Result<Option<&str>, Error> {
+        self.get_str(RUST_NAME)
+    }
+
+    pub fn enum_varnames(&self) -> Result<Option<Vec<&str>>, Error> {
+        self.get_str_list(ENUM_VARNAMES)
+    }
+
+    pub fn enum_descriptions(&self) -> Result<Option<Vec<&str>>, Error> {
+        self.get_str_list(ENUM_DESCRIPTIONS)
+    }
+
+    // Operation must not be generated
+    pub fn ignore(&self) -> Result<bool, Error> {
+        match self.get(IGNORE) {
+            None => Ok(false),
+            Some(Value::Bool(v)) => Ok(*v),
+            Some(_) => Err(Error::WrongType(IGNORE, "boolean")),
+        }
+    }
+
+    fn get_str(&self, key: &'static str) -> Result<Option<&str>, Error> {
+        self.get(key)
+            .map(|v| v.as_str().ok_or(Error::WrongType(key, "string")))
+            .transpose()
+    }
+
+    fn get_str_list(&self, key: &'static str) -> Result<Option<Vec<&str>>, Error> {
+        self.get(key)
+            .map(|v| {
+                v.as_sequence()
+                    .and_then(|items| items.iter().map(Value::as_str).collect::<Option<_>>())
+                    .ok_or(Error::WrongType(key, "list of strings"))
+            })
+            .transpose()
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongType(key, expected) => write!(f, "{key} must be {expected}"),
+        }
+    }
+}
+
+impl std::ops::Deref for Extensions {
+    type Target = indexmap::IndexMap<String, Value>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for Extensions {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(String, Value)> for Extensions {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for Extensions {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LocalVisitor;
+
+        impl<'de> Visitor<'de> for LocalVisitor {
+            type Value = Extensions;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("object with specification extensions")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut result = Extensions::default();
+                // Keys are not always strings (e.g. HTTP status codes)
+                while let Some(key) = map.next_key::<Value>()? {
+                    match key {
+                        Value::String(key) if Extensions::is_extension(&key) => {
+                            result.0.insert(key, map.next_value()?);
+                        }
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                Ok(result)
+            }
+        }
+
+        de.deserialize_map(LocalVisitor)
+    }
+}
@@ -4,15 +4,19 @@
 // External documnent
 //
 
+use crate::schema::extensions::Extensions;
 use crate::typing::TaggedString;
 use crate::typing::TaggedURI;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ExternalDoc {
     pub url: ExternalDocURI,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type ExternalDocURI = TaggedURI<ExternalDocURITag>;
@@ -5,34 +5,44 @@
 //
 
 use crate::schema::data_type::DataType;
+use crate::schema::example::Examples;
+use crate::schema::extensions::Extensions;
 use crate::schema::media_type::MediaType;
 use crate::schema::reference::Reference;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Header {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Description>,
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub required: bool,
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub deprecated: bool,
     #[serde(flatten)]
     pub content_schema: ContentSchema,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Examples>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type Description = TaggedString<HeaderDescriptionTag>;
 pub enum HeaderDescriptionTag {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ContentSchema {
     SchemaAndStyle(SchemaAndStyle),
     Content(Content),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SchemaAndStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<SerializeStyle>,
@@ -41,20 +51,20 @@ pub struct SchemaAndStyle {
     pub schema: DataType,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub enum SerializeStyle {
     #[serde(rename = "simple")]
     Simple,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Content {
     pub content: indexmap::IndexMap<String, MediaType>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum HeaderOrReference {
-    Header(Header),
+    Header(Box<Header>),
     Reference(Reference),
 }
@@ -6,7 +6,7 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -20,6 +20,29 @@ pub enum Error {
     InvalidPattern(String),
 }
 
+impl HttpStatusCode {
+    // Check whether response code is described by this one
+    pub fn matches(&self, code: u16) -> bool {
+        match self {
+            Self::Specific(v) => v.0 == code,
+            Self::Pattern(v) => u16::from(v.code_class) == code / 100,
+        }
+    }
+}
+
+impl Specific {
+    pub fn code(&self) -> u16 {
+        self.0
+    }
+}
+
+impl Pattern {
+    // Class of the codes, e.g. 4 for 4XX
+    pub fn class(&self) -> u8 {
+        self.code_class
+    }
+}
+
 impl std::str::FromStr for HttpStatusCode {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -32,6 +55,24 @@ impl std::str::FromStr for HttpStatusCode {
     }
 }
 
+impl std::fmt::Display for HttpStatusCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Specific(v) => v.fmt(f),
+            Self::Pattern(v) => v.fmt(f),
+        }
+    }
+}
+
+impl Serialize for HttpStatusCode {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_str(self)
+    }
+}
+
 impl<'de> Deserialize<'de> for HttpStatusCode {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
@@ -78,6 +119,12 @@ impl std::fmt::Debug for Specific {
     }
 }
 
+impl std::fmt::Display for Specific {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 #[derive(PartialEq, Eq, Hash)]
 pub struct Pattern {
     // 1 == 1XX, 2 = 2XX, ..., 5 == 5XX
@@ -91,6 +138,12 @@ impl std::fmt::Debug for Pattern {
     }
 }
 
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}XX", self.code_class)
+    }
+}
+
 impl std::str::FromStr for Pattern {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -3,10 +3,12 @@
 // OpenAPI Info Contact Object
 //
 
+use crate::schema::extensions::Extensions;
 use crate::typing::{TaggedString, TaggedURI};
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Contact {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<ContactName>,
@@ -14,6 +16,8 @@ pub struct Contact {
     pub url: Option<ContactURI>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<ContactEmail>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type ContactName = TaggedString<ContactNameTag>;
@@ -3,15 +3,18 @@
 // OpenAPI Info License Object
 //
 
+use crate::schema::extensions::Extensions;
 use crate::typing::{TaggedString, TaggedURI};
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct License {
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: LicenseName,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<LicenseURI>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type LicenseName = TaggedString<LicenseNameTag>;
@@ -6,10 +6,12 @@
 pub mod contact;
 pub mod license;
 
+use crate::schema::extensions::Extensions;
 use crate::typing::{TaggedString, TaggedURI};
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Info {
     pub title: ApiTitle,
     pub version: ApiVersion,
@@ -21,6 +23,8 @@ pub struct Info {
     pub contact: Option<contact::Contact>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub license: Option<license::License>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type ApiTitle = TaggedString<ApiTitleTag>;
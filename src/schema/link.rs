@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Link Object
+//
+
+use crate::schema::extensions::Extensions;
+use crate::schema::operation::OperationId;
+use crate::schema::reference::Reference;
+use crate::schema::server::Server;
+use crate::typing::TaggedString;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Link {
+    #[serde(rename = "operationRef", skip_serializing_if = "Option::is_none")]
+    pub operation_ref: Option<OperationRef>,
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<OperationId>,
+    // Values are constants or runtime expressions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<indexmap::IndexMap<ParameterName, Value>>,
+    #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<Server>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum LinkOrReference {
+    Reference(Reference),
+    Link(Box<Link>),
+}
+
+// Name of the link inside response or components
+pub type Name = TaggedString<LinkNameTag>;
+pub enum LinkNameTag {}
+
+// URI reference to the operation, e.g. #/paths/~1pets/get
+pub type OperationRef = TaggedString<OperationRefTag>;
+pub enum OperationRefTag {}
+
+// Parameter name optionally qualified by location (e.g. path.id)
+pub type ParameterName = TaggedString<LinkParameterNameTag>;
+pub enum LinkParameterNameTag {}
+
+pub type Description = TaggedString<LinkDescriptionTag>;
+pub enum LinkDescriptionTag {}
@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: MIT
+//
+// Loading of the description from YAML or JSON.
+//
+// Format is detected by file extension (.json, .yaml, .yml) or by
+// content: JSON document starts with '{'. Source locations of the
+// nodes are collected on request, see span::Spans. Raw document
+// trees (see crate::transform) are loaded the same way.
+//
+
+use crate::schema::span::Spans;
+use crate::schema::Description;
+use serde::de::DeserializeOwned;
+use std::io::Read;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Yaml,
+    Json,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub format: Format,
+    pub location: Option<Location>,
+    pub message: String,
+}
+
+// Line and column (both start from 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Format {
+    pub fn from_path(path: &std::path::Path) -> Option<Self> {
+        match path.extension().and_then(|v| v.to_str()) {
+            Some("json") => Some(Self::Json),
+            Some("yaml" | "yml") => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn detect(s: &str) -> Self {
+        match s
+            .trim_start_matches('\u{feff}')
+            .trim_start()
+            .starts_with('{')
+        {
+            true => Self::Json,
+            false => Self::Yaml,
+        }
+    }
+}
+
+pub fn from_path(path: &std::path::Path) -> Result<Description, Error> {
+    let contents = std::fs::read_to_string(path).map_err(Error::Io)?;
+    from_str_as(&contents, Format::from_path(path))
+}
+
+pub fn from_reader(mut r: impl Read) -> Result<Description, Error> {
+    let mut contents = String::new();
+    r.read_to_string(&mut contents).map_err(Error::Io)?;
+    from_str(&contents)
+}
+
+pub fn from_str(s: &str) -> Result<Description, Error> {
+    from_str_as(s, None)
+}
+
+// Parse description of the format. Format is detected by content
+// if it is not known.
+pub fn from_str_as(s: &str, format: Option<Format>) -> Result<Description, Error> {
+    parse(s, format)
+}
+
+// Document tree of the file
+pub fn value_from_path(path: &std::path::Path) -> Result<serde_yaml::Value, Error> {
+    let contents = std::fs::read_to_string(path).map_err(Error::Io)?;
+    parse(&contents, Format::from_path(path))
+}
+
+fn parse<T: DeserializeOwned>(s: &str, format: Option<Format>) -> Result<T, Error> {
+    let s = s.trim_start_matches('\u{feff}');
+    match format.unwrap_or_else(|| Format::detect(s)) {
+        Format::Yaml => serde_yaml::from_str(s).map_err(|err| {
+            let location = err.location().map(|v| Location {
+                line: v.line(),
+                column: v.column(),
+            });
+            Error::Parse(ParseError::new(Format::Yaml, location, err.to_string()))
+        }),
+        Format::Json => serde_json::from_str(s).map_err(|err| {
+            // Line is 0 if error is not related to the position
+            let location = (err.line() > 0).then(|| Location {
+                line: err.line(),
+                column: err.column(),
+            });
+            Error::Parse(ParseError::new(Format::Json, location, err.to_string()))
+        }),
+    }
+}
+
+// Parse description and locate its nodes. File name is kept in
+// spans to be reported with locations.
+pub fn from_path_spanned(path: &std::path::Path) -> Result<(Description, Spans), Error> {
+    let contents = std::fs::read_to_string(path).map_err(Error::Io)?;
+    let file = path.display().to_string();
+    from_str_spanned(&contents, Format::from_path(path), Some(&file))
+}
+
+pub fn from_str_spanned(
+    s: &str,
+    format: Option<Format>,
+    file: Option<&str>,
+) -> Result<(Description, Spans), Error> {
+    let format = format.unwrap_or_else(|| Format::detect(s));
+    let description = from_str_as(s, Some(format))?;
+    // JSON is parsed as YAML flow mapping. Locations stay unknown if
+    // the source is not accepted by YAML parser (e.g. JSON indented
+    // with tabs).
+    let spans = Spans::from_str(s.trim_start_matches('\u{feff}'), file)
+        .unwrap_or_else(|_| Spans::new(file));
+    Ok((description, spans))
+}
+
+impl ParseError {
+    // Message is stored without location both parsers append
+    fn new(format: Format, location: Option<Location>, message: String) -> Self {
+        let message = match location {
+            Some(Location { line, column }) => {
+                let suffix = format!(" at line {line} column {column}");
+                message
+                    .strip_suffix(&suffix)
+                    .map(String::from)
+                    .unwrap_or(message)
+            }
+            None => message,
+        };
+        Self {
+            format,
+            location,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Yaml => "YAML".fmt(f),
+            Self::Json => "JSON".fmt(f),
+        }
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.location {
+            Some(location) => write!(f, "{location}: {} error: {}", self.format, self.message),
+            None => write!(f, "{} error: {}", self.format, self.message),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn yaml_and_json() {
+        let yaml = include_str!("../../test/test-vectors/servers.yaml");
+        let description = from_str(yaml).unwrap();
+        let json = serde_json::to_string_pretty(&description).unwrap();
+        assert_eq!(Format::detect(&json), Format::Json);
+        assert_eq!(from_str(&json).unwrap(), description);
+        assert_eq!(from_reader(json.as_bytes()).unwrap(), description);
+
+        let path = std::path::Path::new("test/test-vectors/servers.yaml");
+        assert_eq!(from_path(path).unwrap(), description);
+    }
+
+    #[test]
+    fn error_location() {
+        let Err(Error::Parse(err)) = from_str("{\"openapi\": \"3.0.3\",\n  \"info\": 1}") else {
+            panic!("JSON is parsed")
+        };
+        assert_eq!(err.format, Format::Json);
+        assert_eq!(
+            err.location,
+            Some(Location {
+                line: 2,
+                column: 11
+            })
+        );
+        assert_eq!(
+            err.to_string(),
+            "2:11: JSON error: invalid type: integer `1`, expected struct Info"
+        );
+
+        let Err(Error::Parse(err)) = from_str("openapi: 3.0.3\ninfo: 1\n") else {
+            panic!("YAML is parsed")
+        };
+        assert_eq!(err.format, Format::Yaml);
+        assert_eq!(
+            err.to_string(),
+            "2:7: YAML error: info: invalid type: integer `1`, expected struct Info"
+        );
+    }
+}
@@ -5,13 +5,23 @@
 
 use crate::schema::data_type::DataType;
 use crate::schema::encoding::Encoding;
+use crate::schema::example::Examples;
+use crate::schema::extensions::Extensions;
 use crate::schema::PropertyName;
 use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct MediaType {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<DataType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<indexmap::IndexMap<PropertyName, Encoding>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Examples>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
@@ -3,15 +3,20 @@
 // OpenAPI Schema
 //
 
+pub mod callback;
 pub mod components;
 pub mod content_type;
 pub mod data_type;
 pub mod discriminator;
 pub mod encoding;
+pub mod example;
+pub mod extensions;
 pub mod external_doc;
 pub mod header;
 pub mod http_status_code;
 pub mod info;
+pub mod link;
+pub mod load;
 pub mod media_type;
 pub mod operation;
 pub mod parameter;
@@ -21,32 +26,63 @@ pub mod reference;
 pub mod request_body;
 pub mod response;
 pub mod responses;
+pub mod runtime_expression;
+pub mod security_requirement;
+pub mod security_scheme;
 pub mod server;
+pub mod span;
 pub mod sref;
+pub mod tag;
+pub mod template;
 pub mod version;
 
+use crate::schema::extensions::Extensions;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
 pub type SRef = sref::SRef;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Description {
     pub openapi: version::Version,
     pub info: info::Info,
+    // 3.1.X specification
+    #[serde(rename = "jsonSchemaDialect", skip_serializing_if = "Option::is_none")]
+    pub json_schema_dialect: Option<JsonSchemaDialect>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub servers: Vec<server::Server>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub paths: Option<indexmap::IndexMap<path::Path, path_item::PathItem>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<components::Components>,
+    // 3.1.X specification
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<indexmap::IndexMap<WebhookName, path_item::PathItem>>,
+    // Default security of all operations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<security_requirement::SecurityRequirement>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<tag::Tag>>,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<external_doc::ExternalDoc>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
+// Default $schema of Schema Objects
+pub type JsonSchemaDialect = TaggedString<JsonSchemaDialectTag>;
+pub enum JsonSchemaDialectTag {}
+
 // Note: ApiDocTagTag is kind of conflict of the naming conventions.
 // ApiDocTag defines tags inside OpenAPI
 pub type ApiDocTag = TaggedString<ApiDocTagTag>;
 pub enum ApiDocTagTag {}
 
+// Name of the incoming request that is initiated by API provider
+pub type WebhookName = TaggedString<WebhookNameTag>;
+pub enum WebhookNameTag {}
+
 // Name of the property inside the object
 pub type PropertyName = TaggedString<PropertyNameTag>;
 pub enum PropertyNameTag {}
@@ -58,3 +94,83 @@ pub enum PropertyStringValueTag {}
 // Name of the header. TODO: any restrictions on header names?
 pub type HeaderName = TaggedString<HeaderNameTag>;
 pub enum HeaderNameTag {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(spec: &str) {
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let yaml = serde_yaml::to_string(&description).unwrap();
+        assert_eq!(description, serde_yaml::from_str(&yaml).unwrap());
+        let json = serde_json::to_string(&description).unwrap();
+        assert_eq!(description, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn extensions_are_preserved() {
+        let spec = include_str!("../../test/test-vectors/round-trip.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        assert!(description.extensions.contains_key("x-generator"));
+        assert_eq!(
+            description.info.extensions.get("x-audience"),
+            Some(&serde_yaml::Value::String("external".into()))
+        );
+        let paths = description.paths.unwrap();
+        let item = paths.values().next().unwrap();
+        assert!(item.extensions.contains_key("x-internal"));
+        let get = item.get.as_ref().unwrap();
+        assert!(get.extensions.contains_key("x-go-name"));
+        let responses = get.responses.as_ref().unwrap();
+        assert_eq!(responses.codes.len(), 2);
+        assert!(responses.extensions.contains_key("x-codes-comment"));
+        let schemas = description.components.unwrap().schemas.unwrap();
+        let Some(data_type::DataType::ActualType(pet)) = schemas.values().next() else {
+            panic!("Pet must be actual type");
+        };
+        assert!(pet.extensions.contains_key("x-rust-type"));
+    }
+
+    #[test]
+    fn uris_are_kept_as_written() {
+        let spec = include_str!("../../test/test-vectors/round-trip.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let yaml = serde_yaml::to_string(&description).unwrap();
+        assert!(yaml.contains("url: https://example.com\n"));
+        assert!(yaml.contains("url: https://example.com/docs\n"));
+    }
+
+    #[test]
+    fn round_trip_test_vectors() {
+        round_trip(include_str!("../../test/test-vectors/round-trip.yaml"));
+        round_trip(include_str!(
+            "../../test/test-vectors/request-body-with-all-of-schema.yaml"
+        ));
+        round_trip(include_str!(
+            "../../test/test-vectors/request-body-with-any-of-schema.yaml"
+        ));
+        round_trip(include_str!(
+            "../../test/test-vectors/request-body-with-one-of-schema.yaml"
+        ));
+        round_trip(include_str!(
+            "../../test/test-vectors/multiple-types-for-field.yaml"
+        ));
+        round_trip(include_str!(
+            "../../test/test-vectors/codegen-extensions.yaml"
+        ));
+        round_trip(include_str!("../../test/test-vectors/security.yaml"));
+        round_trip(include_str!("../../test/test-vectors/callbacks.yaml"));
+        round_trip(include_str!("../../test/test-vectors/links.yaml"));
+        round_trip(include_str!("../../test/test-vectors/examples.yaml"));
+        round_trip(include_str!("../../test/test-vectors/metadata.yaml"));
+        round_trip(include_str!("../../test/test-vectors/servers.yaml"));
+        round_trip(include_str!("../../test/test-vectors/constraints.yaml"));
+        round_trip(include_str!("../../test/test-vectors/validate.yaml"));
+        round_trip(include_str!("../../test/test-vectors/conformance.yaml"));
+        round_trip(include_str!("../../test/test-vectors/router.yaml"));
+        round_trip(include_str!("../../test/test-vectors/codegen.yaml"));
+        round_trip(include_str!("../../test/test-vectors/spans.yaml"));
+        round_trip(include_str!("../../test/test-vectors/client.yaml"));
+        round_trip(include_str!("../../test/test-vectors/read-write.yaml"));
+    }
+}
@@ -3,15 +3,21 @@
 // OpenAPI Operation Object
 //
 
+use crate::schema::callback::CallbackOrReference;
+use crate::schema::callback::Name as CallbackName;
+use crate::schema::extensions::Extensions;
 use crate::schema::external_doc::ExternalDoc;
 use crate::schema::parameter::ParameterOrReference;
 use crate::schema::request_body::RequestBodyOrReference;
 use crate::schema::responses::Responses;
+use crate::schema::security_requirement::SecurityRequirement;
+use crate::schema::server::Server;
 use crate::schema::ApiDocTag;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<ApiDocTag>>,
@@ -29,6 +35,18 @@ pub struct Operation {
     pub request_body: Option<RequestBodyOrReference>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub responses: Option<Responses>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callbacks: Option<indexmap::IndexMap<CallbackName, CallbackOrReference>>,
+    // Overrides global security. Empty list removes global security.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<SecurityRequirement>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
+    pub deprecated: bool,
+    // Overrides servers of path item and description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub servers: Option<Vec<Server>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type Summary = TaggedString<OperationSummaryTag>;
@@ -4,13 +4,17 @@
 //
 
 use crate::schema::data_type::DataType;
+use crate::schema::example::Examples;
+use crate::schema::extensions::Extensions;
 use crate::schema::media_type::MediaType;
 use crate::schema::reference::Reference;
 use crate::typing::AlwaysTrue;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Value;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Parameter {
     pub name: Name,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,6 +23,12 @@ pub struct Parameter {
     pub place: Place,
     #[serde(flatten)]
     pub content_schema: ContentSchema,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub example: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Examples>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 impl Parameter {
@@ -42,6 +52,52 @@ impl Parameter {
         )
     }
 
+    // Effective style of the parameter described by schema
+    pub fn style(&self) -> Option<SerializeStyle> {
+        let ContentSchema::SchemaAndStyle(s) = &self.content_schema else {
+            return None;
+        };
+        Some(s.style.unwrap_or(match self.place {
+            Place::Query(_) | Place::Cookie(_) => SerializeStyle::Form,
+            Place::Path(_) | Place::Header(_) => SerializeStyle::Simple,
+        }))
+    }
+
+    pub fn explode(&self) -> bool {
+        match &self.content_schema {
+            ContentSchema::SchemaAndStyle(s) => s
+                .explode
+                .unwrap_or(self.style() == Some(SerializeStyle::Form)),
+            ContentSchema::Content(_) | ContentSchema::None(_) => false,
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        match &self.place {
+            Place::Query(flags) => flags.required,
+            Place::Path(_) => true,
+            Place::Header(flags) | Place::Cookie(flags) => flags.required,
+        }
+    }
+
+    // Schema of the value: schema of the parameter or schema of its
+    // JSON content
+    pub fn schema(&self) -> Option<&DataType> {
+        match &self.content_schema {
+            ContentSchema::SchemaAndStyle(s) => Some(&s.schema),
+            ContentSchema::Content(c) => c.content.get("application/json")?.schema.as_ref(),
+            ContentSchema::None(_) => None,
+        }
+    }
+
+    pub fn is_deprecated(&self) -> bool {
+        match &self.place {
+            Place::Query(flags) => flags.deprecated,
+            Place::Path(flags) => flags.deprecated,
+            Place::Header(flags) | Place::Cookie(flags) => flags.deprecated,
+        }
+    }
+
     pub fn is_cookie(&self) -> bool {
         matches!(
             self,
@@ -59,7 +115,7 @@ pub enum ParameterNameTag {}
 pub type Description = TaggedString<ParameterDescriptionTag>;
 pub enum ParameterDescriptionTag {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(tag = "in")]
 pub enum Place {
     #[serde(rename = "query")]
@@ -72,32 +128,36 @@ pub enum Place {
     Cookie(OtherFlags),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct QueryFlags {
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub required: bool,
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub deprecated: bool,
-    #[serde(rename = "allowEmptyValue", default)] // default is false
+    #[serde(
+        rename = "allowEmptyValue",
+        default,
+        skip_serializing_if = "std::ops::Not::not"
+    )] // default is false
     pub allow_empty_value: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct PathFlags {
     pub required: AlwaysTrue,
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub deprecated: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct OtherFlags {
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub required: bool,
-    #[serde(default)] // default is false
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")] // default is false
     pub deprecated: bool,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ContentSchema {
     SchemaAndStyle(SchemaAndStyle),
@@ -105,31 +165,46 @@ pub enum ContentSchema {
     None(None),
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct SchemaAndStyle {
-    pub style: SerializeStyle,
+    // Default depends on the place (see Parameter::style)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<SerializeStyle>,
+    // Default is true for form style and false for others
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explode: Option<bool>,
     pub schema: DataType,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SerializeStyle {
+    #[serde(rename = "matrix")]
+    Matrix,
+    #[serde(rename = "label")]
+    Label,
     #[serde(rename = "form")]
     Form,
     #[serde(rename = "simple")]
     Simple,
+    #[serde(rename = "spaceDelimited")]
+    SpaceDelimited,
+    #[serde(rename = "pipeDelimited")]
+    PipeDelimited,
+    #[serde(rename = "deepObject")]
+    DeepObject,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Content {
     pub content: indexmap::IndexMap<String, MediaType>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct None {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ParameterOrReference {
-    Parameter(Parameter),
+    Parameter(Box<Parameter>),
     Reference(Reference),
 }
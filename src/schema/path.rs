@@ -5,7 +5,7 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone)]
@@ -18,7 +18,11 @@ pub enum Error {
 }
 
 impl Path {
-    pub fn path_params_iter(&self) -> PathParamTryIter {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn path_params_iter(&self) -> PathParamTryIter<'_> {
         PathParamTryIter { data: self, pos: 0 }
     }
 }
@@ -45,6 +49,21 @@ impl std::fmt::Display for Error {
     }
 }
 
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for Path {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.0)
+    }
+}
+
 impl<'de> Deserialize<'de> for Path {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
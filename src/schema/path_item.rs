@@ -3,14 +3,16 @@
 // OpenAPI Schema Path objects
 //
 
+use crate::schema::extensions::Extensions;
 use crate::schema::operation::Operation;
 use crate::schema::parameter::ParameterOrReference;
 use crate::schema::server::Server;
 use crate::schema::SRef;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct PathItem {
     #[serde(rename = "$ref", skip_serializing_if = "Option::is_none")]
     pub sref: Option<SRef>,
@@ -36,6 +38,8 @@ pub struct PathItem {
     pub servers: Option<Vec<Server>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parameters: Option<Vec<ParameterOrReference>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type Summary = TaggedString<SummaryTag>;
@@ -45,7 +49,7 @@ pub type Description = TaggedString<DescriptionTag>;
 pub enum DescriptionTag {}
 
 impl PathItem {
-    pub fn operations_iter(&self) -> OperationIter {
+    pub fn operations_iter(&self) -> OperationIter<'_> {
         OperationIter {
             path_item: self,
             op_type: Some(&GET),
@@ -58,7 +62,7 @@ pub struct OperationIter<'a> {
     op_type: Option<&'static OperationType>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq, Hash)]
 pub enum OperationType {
     Get,
     Put,
@@ -79,6 +83,37 @@ static HEAD: OperationType = OperationType::Head;
 static PATCH: OperationType = OperationType::Patch;
 static TRACE: OperationType = OperationType::Trace;
 
+impl OperationType {
+    // Operation type by the field name inside Path Item
+    pub fn from_method(method: &str) -> Option<&'static OperationType> {
+        match method {
+            "get" => Some(&GET),
+            "put" => Some(&PUT),
+            "post" => Some(&POST),
+            "delete" => Some(&DELETE),
+            "options" => Some(&OPTIONS),
+            "head" => Some(&HEAD),
+            "patch" => Some(&PATCH),
+            "trace" => Some(&TRACE),
+            _ => None,
+        }
+    }
+
+    // Field name inside Path Item
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Get => "get",
+            Self::Put => "put",
+            Self::Post => "post",
+            Self::Delete => "delete",
+            Self::Options => "options",
+            Self::Head => "head",
+            Self::Patch => "patch",
+            Self::Trace => "trace",
+        }
+    }
+}
+
 impl<'a> Iterator for OperationIter<'a> {
     type Item = (&'static OperationType, &'a Operation);
 
@@ -103,7 +138,7 @@ impl<'a> Iterator for OperationIter<'a> {
                 }
                 OperationType::Options => {
                     self.op_type = Some(&HEAD);
-                    &self.path_item.delete
+                    &self.path_item.options
                 }
                 OperationType::Head => {
                     self.op_type = Some(&PATCH);
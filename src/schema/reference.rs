@@ -6,8 +6,9 @@
 
 use crate::schema::SRef;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Reference {
     #[serde(rename = "$ref")]
     pub sref: SRef,
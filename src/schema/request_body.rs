@@ -3,24 +3,28 @@
 // Request body
 //
 
+use crate::schema::extensions::Extensions;
 use crate::schema::media_type::MediaType;
 use crate::schema::reference::Reference;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct RequestBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Description>,
     pub content: indexmap::IndexMap<String, MediaType>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub required: Option<bool>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type Description = TaggedString<RequestBodyDescriptionTag>;
 pub enum RequestBodyDescriptionTag {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum RequestBodyOrReference {
     RequestBody(RequestBody),
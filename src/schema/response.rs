@@ -4,13 +4,17 @@
 // Response Object
 //
 
+use crate::schema::extensions::Extensions;
 use crate::schema::header::HeaderOrReference;
+use crate::schema::link::LinkOrReference;
+use crate::schema::link::Name as LinkName;
 use crate::schema::media_type::MediaType;
 use crate::schema::HeaderName;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<Description>,
@@ -18,6 +22,10 @@ pub struct Response {
     pub content: Option<indexmap::IndexMap<String, MediaType>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<indexmap::IndexMap<HeaderName, HeaderOrReference>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<indexmap::IndexMap<LinkName, LinkOrReference>>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type Description = TaggedString<ResponseDescriptionTag>;
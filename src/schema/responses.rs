@@ -4,22 +4,78 @@
 // Responses Object
 //
 
+use crate::schema::extensions::Extensions;
 use crate::schema::http_status_code::HttpStatusCode;
 use crate::schema::reference::Reference;
 use crate::schema::response::Response;
+use serde::de;
+use serde::de::Deserializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
 use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Debug, PartialEq)]
 pub struct Responses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<ResponseOrReference>,
     #[serde(flatten)]
     pub codes: indexmap::IndexMap<HttpStatusCode, ResponseOrReference>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 #[serde(untagged)]
 pub enum ResponseOrReference {
     Reference(Reference),
-    Response(Response),
+    Response(Box<Response>),
+}
+
+// Status codes and extensions share the same keys namespace so
+// Responses cannot be derived.
+impl<'de> Deserialize<'de> for Responses {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LocalVisitor;
+
+        impl<'de> Visitor<'de> for LocalVisitor {
+            type Value = Responses;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("map of HTTP status codes to responses")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut result = Responses {
+                    default: None,
+                    codes: indexmap::IndexMap::default(),
+                    extensions: Extensions::default(),
+                };
+                while let Some(key) = map.next_key::<serde_yaml::Value>()? {
+                    let key = match key {
+                        serde_yaml::Value::String(s) => s,
+                        serde_yaml::Value::Number(n) => n.to_string(),
+                        _ => Err(de::Error::custom("HTTP status code must be string"))?,
+                    };
+                    if key == "default" {
+                        result.default = Some(map.next_value()?);
+                    } else if Extensions::is_extension(&key) {
+                        result.extensions.insert(key, map.next_value()?);
+                    } else {
+                        let code = key.parse().map_err(de::Error::custom)?;
+                        result.codes.insert(code, map.next_value()?);
+                    }
+                }
+                Ok(result)
+            }
+        }
+
+        de.deserialize_map(LocalVisitor)
+    }
 }
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Runtime Expressions
+//
+// expression = "$url" / "$method" / "$statusCode"
+//            / "$request." source / "$response." source
+// source = header-reference / query-reference / path-reference / body-reference
+// header-reference = "header." token
+// query-reference = "query." name
+// path-reference = "path." name
+// body-reference = "body" ["#" json-pointer ]
+//
+
+use crate::schema::template;
+use crate::schema::template::Token;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expression {
+    Url,
+    Method,
+    StatusCode,
+    Request(Source),
+    Response(Source),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Header(String),
+    Query(String),
+    Path(String),
+    // JSON pointer inside the body. Empty pointer is the whole body.
+    Body(String),
+}
+
+// Part of the string with embedded expressions,
+// e.g. https://example.com/pets?id={$request.path.id}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Part {
+    Literal(String),
+    Expression(Expression),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    NotExpression(String),
+    UnknownExpression(String),
+    UnknownSource(String),
+    EmptyName(String),
+    InvalidHeaderName(String),
+    InvalidPointer(String),
+    UnclosedBrace(String),
+}
+
+impl Expression {
+    // Check whether string is an expression and not a constant
+    pub fn is_expression(s: &str) -> bool {
+        s.starts_with('$')
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Expression, Self::Err> {
+        match s {
+            "$url" => Ok(Self::Url),
+            "$method" => Ok(Self::Method),
+            "$statusCode" => Ok(Self::StatusCode),
+            _ => {
+                if let Some(source) = s.strip_prefix("$request.") {
+                    parse_source(s, source).map(Self::Request)
+                } else if let Some(source) = s.strip_prefix("$response.") {
+                    parse_source(s, source).map(Self::Response)
+                } else if Self::is_expression(s) {
+                    Err(Error::UnknownExpression(s.into()))
+                } else {
+                    Err(Error::NotExpression(s.into()))
+                }
+            }
+        }
+    }
+}
+
+fn parse_source(s: &str, source: &str) -> Result<Source, Error> {
+    let name = |name: &str| {
+        if name.is_empty() {
+            Err(Error::EmptyName(s.into()))
+        } else {
+            Ok(name.to_string())
+        }
+    };
+    if let Some(header) = source.strip_prefix("header.") {
+        let header = name(header)?;
+        if header.bytes().all(is_tchar) {
+            Ok(Source::Header(header))
+        } else {
+            Err(Error::InvalidHeaderName(s.into()))
+        }
+    } else if let Some(query) = source.strip_prefix("query.") {
+        name(query).map(Source::Query)
+    } else if let Some(path) = source.strip_prefix("path.") {
+        name(path).map(Source::Path)
+    } else if source == "body" {
+        Ok(Source::Body(String::new()))
+    } else if let Some(pointer) = source.strip_prefix("body#") {
+        if pointer.is_empty() || pointer.starts_with('/') {
+            Ok(Source::Body(pointer.into()))
+        } else {
+            Err(Error::InvalidPointer(s.into()))
+        }
+    } else {
+        Err(Error::UnknownSource(s.into()))
+    }
+}
+
+// RFC 7230 token characters
+fn is_tchar(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c)
+}
+
+// Parse string with expressions embedded in curly braces
+pub fn parse_template(s: &str) -> Result<Vec<Part>, Error> {
+    template::tokenize(s)
+        .map_err(|_| Error::UnclosedBrace(s.into()))?
+        .into_iter()
+        .map(|token| match token {
+            Token::Literal(s) => Ok(Part::Literal(s.into())),
+            Token::Name(expression) => expression.parse().map(Part::Expression),
+        })
+        .collect()
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url => "$url".fmt(f),
+            Self::Method => "$method".fmt(f),
+            Self::StatusCode => "$statusCode".fmt(f),
+            Self::Request(source) => write!(f, "$request.{source}"),
+            Self::Response(source) => write!(f, "$response.{source}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Header(name) => write!(f, "header.{name}"),
+            Self::Query(name) => write!(f, "query.{name}"),
+            Self::Path(name) => write!(f, "path.{name}"),
+            Self::Body(pointer) if pointer.is_empty() => "body".fmt(f),
+            Self::Body(pointer) => write!(f, "body#{pointer}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotExpression(s) => write!(f, "runtime expression must start with $: {s}"),
+            Self::UnknownExpression(s) => write!(f, "unknown runtime expression: {s}"),
+            Self::UnknownSource(s) => write!(f, "unknown source of runtime expression: {s}"),
+            Self::EmptyName(s) => write!(f, "empty name in runtime expression: {s}"),
+            Self::InvalidHeaderName(s) => write!(f, "invalid header name: {s}"),
+            Self::InvalidPointer(s) => write!(f, "invalid JSON pointer: {s}"),
+            Self::UnclosedBrace(s) => write!(f, "expression is not closed by curly brace: {s}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_expressions() {
+        assert_eq!("$url".parse(), Ok(Expression::Url));
+        assert_eq!("$statusCode".parse(), Ok(Expression::StatusCode));
+        assert_eq!(
+            "$request.path.id".parse(),
+            Ok(Expression::Request(Source::Path("id".into())))
+        );
+        assert_eq!(
+            "$request.header.X-Request-Id".parse(),
+            Ok(Expression::Request(Source::Header("X-Request-Id".into())))
+        );
+        assert_eq!(
+            "$response.body#/id".parse(),
+            Ok(Expression::Response(Source::Body("/id".into())))
+        );
+        assert_eq!(
+            "$response.body".parse(),
+            Ok(Expression::Response(Source::Body("".into())))
+        );
+        assert!(matches!(
+            "$request.header.bad name".parse::<Expression>(),
+            Err(Error::InvalidHeaderName(_))
+        ));
+        assert!(matches!(
+            "$response.cookie.id".parse::<Expression>(),
+            Err(Error::UnknownSource(_))
+        ));
+        assert!(matches!(
+            "$request.query.".parse::<Expression>(),
+            Err(Error::EmptyName(_))
+        ));
+        assert!(matches!(
+            "id".parse::<Expression>(),
+            Err(Error::NotExpression(_))
+        ));
+    }
+
+    #[test]
+    fn expression_to_string() {
+        for s in [
+            "$method",
+            "$request.query.q",
+            "$response.body#/a~1b",
+            "$request.body",
+        ] {
+            assert_eq!(s.parse::<Expression>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_templates() {
+        assert_eq!(
+            parse_template("http://example.com?id={$request.path.id}&all"),
+            Ok(vec![
+                Part::Literal("http://example.com?id=".into()),
+                Part::Expression(Expression::Request(Source::Path("id".into()))),
+                Part::Literal("&all".into()),
+            ])
+        );
+        assert!(matches!(
+            parse_template("{$url"),
+            Err(Error::UnclosedBrace(_))
+        ));
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Security Requirement Object
+//
+
+use crate::schema::security_scheme::Scope;
+use crate::schema::sref::SRefSecuritySchemeName;
+
+// All schemes of the requirement must be satisfied. Empty
+// requirement means that authorization is optional. Values are
+// scopes for OAuth2 / OpenID Connect and roles for other schemes
+// (3.1.X specification).
+pub type SecurityRequirement = indexmap::IndexMap<SRefSecuritySchemeName, Vec<Scope>>;
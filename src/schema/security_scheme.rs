@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Security Scheme Object
+//
+
+use crate::schema::extensions::Extensions;
+use crate::schema::reference::Reference;
+use crate::typing::TaggedString;
+use crate::typing::TaggedURI;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum SecurityScheme {
+    #[serde(rename = "apiKey")]
+    ApiKey(ApiKey),
+    #[serde(rename = "http")]
+    Http(Http),
+    #[serde(rename = "oauth2")]
+    OAuth2(Box<OAuth2>),
+    #[serde(rename = "openIdConnect")]
+    OpenIdConnect(OpenIdConnect),
+    // 3.1.X specification
+    #[serde(rename = "mutualTLS")]
+    MutualTls(MutualTls),
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ApiKey {
+    pub name: ApiKeyName,
+    #[serde(rename = "in")]
+    pub place: ApiKeyPlace,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub enum ApiKeyPlace {
+    #[serde(rename = "query")]
+    Query,
+    #[serde(rename = "header")]
+    Header,
+    #[serde(rename = "cookie")]
+    Cookie,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Http {
+    pub scheme: HttpScheme,
+    #[serde(rename = "bearerFormat", skip_serializing_if = "Option::is_none")]
+    pub bearer_format: Option<BearerFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+// Schemes are registered by IANA (RFC 7235) and are case-insensitive
+#[derive(Serialize, Debug, PartialEq)]
+pub enum HttpScheme {
+    #[serde(rename = "basic")]
+    Basic,
+    #[serde(rename = "bearer")]
+    Bearer,
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl<'de> Deserialize<'de> for HttpScheme {
+    fn deserialize<D>(de: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = String::deserialize(de)?;
+        Ok(if v.eq_ignore_ascii_case("basic") {
+            Self::Basic
+        } else if v.eq_ignore_ascii_case("bearer") {
+            Self::Bearer
+        } else {
+            Self::Other(v)
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct OAuth2 {
+    pub flows: OAuthFlows,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct OAuthFlows {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit: Option<ImplicitFlow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<TokenFlow>,
+    #[serde(rename = "clientCredentials", skip_serializing_if = "Option::is_none")]
+    pub client_credentials: Option<TokenFlow>,
+    #[serde(rename = "authorizationCode", skip_serializing_if = "Option::is_none")]
+    pub authorization_code: Option<AuthorizationCodeFlow>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ImplicitFlow {
+    #[serde(rename = "authorizationUrl")]
+    pub authorization_url: FlowURI,
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<FlowURI>,
+    pub scopes: Scopes,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+// Password and client credentials flows
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct TokenFlow {
+    #[serde(rename = "tokenUrl")]
+    pub token_url: FlowURI,
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<FlowURI>,
+    pub scopes: Scopes,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct AuthorizationCodeFlow {
+    #[serde(rename = "authorizationUrl")]
+    pub authorization_url: FlowURI,
+    #[serde(rename = "tokenUrl")]
+    pub token_url: FlowURI,
+    #[serde(rename = "refreshUrl", skip_serializing_if = "Option::is_none")]
+    pub refresh_url: Option<FlowURI>,
+    pub scopes: Scopes,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct OpenIdConnect {
+    #[serde(rename = "openIdConnectUrl")]
+    pub open_id_connect_url: OpenIdConnectURI,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MutualTls {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum SecuritySchemeOrReference {
+    SecurityScheme(SecurityScheme),
+    Reference(Reference),
+}
+
+impl OAuthFlows {
+    // Check whether scope is defined by any of the flows
+    pub fn has_scope(&self, scope: &Scope) -> bool {
+        let implicit = self.implicit.as_ref().map(|v| &v.scopes);
+        let password = self.password.as_ref().map(|v| &v.scopes);
+        let client = self.client_credentials.as_ref().map(|v| &v.scopes);
+        let code = self.authorization_code.as_ref().map(|v| &v.scopes);
+        [implicit, password, client, code]
+            .into_iter()
+            .flatten()
+            .any(|scopes| scopes.contains_key(scope))
+    }
+}
+
+pub type Scopes = indexmap::IndexMap<Scope, ScopeDescription>;
+
+pub type Scope = TaggedString<ScopeTag>;
+pub enum ScopeTag {}
+
+pub type ScopeDescription = TaggedString<ScopeDescriptionTag>;
+pub enum ScopeDescriptionTag {}
+
+pub type ApiKeyName = TaggedString<ApiKeyNameTag>;
+pub enum ApiKeyNameTag {}
+
+pub type BearerFormat = TaggedString<BearerFormatTag>;
+pub enum BearerFormatTag {}
+
+pub type Description = TaggedString<SecuritySchemeDescriptionTag>;
+pub enum SecuritySchemeDescriptionTag {}
+
+pub type FlowURI = TaggedURI<FlowURITag>;
+pub enum FlowURITag {}
+
+pub type OpenIdConnectURI = TaggedURI<OpenIdConnectURITag>;
+pub enum OpenIdConnectURITag {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn http_scheme_is_case_insensitive() {
+        let scheme = |v: &str| serde_yaml::from_str::<HttpScheme>(v).unwrap();
+        assert_eq!(scheme("BEARER"), HttpScheme::Bearer);
+        assert_eq!(scheme("Basic"), HttpScheme::Basic);
+        assert_eq!(scheme("bAsIc"), HttpScheme::Basic);
+        assert_eq!(scheme("Digest"), HttpScheme::Other("Digest".into()));
+    }
+}
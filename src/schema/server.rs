@@ -2,20 +2,33 @@
 //
 // Server Object
 //
+// Server URL is a template with variables in curly braces,
+// e.g. https://{env}.example.com:{port}/v1. Every variable must be
+// defined in variables of the server.
+//
 
+use crate::schema::extensions::Extensions;
+use crate::schema::template;
+use crate::schema::template::Token;
 use crate::typing::TaggedString;
 use serde::Deserialize;
+use serde::Serialize;
+
+// URL of the server when servers are not defined at all
+pub const DEFAULT_URL: &str = "/";
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Server {
     pub url: ServerURI,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub descriptor: Option<ServerDescription>,
+    pub description: Option<ServerDescription>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<ServerVariables>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
-// TODO: String with variable substitution:
+// String with variable substitution (see Server::template)
 pub type ServerURI = TaggedString<ServerURITag>;
 pub enum ServerURITag {}
 
@@ -27,13 +40,15 @@ pub type ServerVariables = indexmap::IndexMap<ServerVariableName, ServerVariable
 pub type ServerVariableName = TaggedString<ServerVariableNameTag>;
 pub enum ServerVariableNameTag {}
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct ServerVariable {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#enum: Option<Vec<ServerVariableValue>>,
     pub default: ServerVariableValue,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<ServerVariableDescription>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
 }
 
 pub type ServerVariableDescription = TaggedString<ServerVariableDescriptionTag>;
@@ -41,3 +56,189 @@ pub enum ServerVariableDescriptionTag {}
 
 pub type ServerVariableValue = TaggedString<ServerVariableValueTag>;
 pub enum ServerVariableValueTag {}
+
+// Values of variables those override defaults
+pub type Overrides<'o> = indexmap::IndexMap<&'o str, &'o str>;
+
+// Part of the server URL template
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Part<'a> {
+    Literal(&'a str),
+    Variable(&'a ServerVariableName, &'a ServerVariable),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    UnclosedBrace(String),
+    EmptyVariable(String),
+    // URL refers to variable (name) that is not defined
+    UndefinedVariable(String, String),
+    EmptyEnum(String),
+    DefaultNotInEnum(String),
+    // Override (variable, value) is not one of enum values
+    ValueNotInEnum(String, String),
+    // Override of variable that is not defined
+    UnknownVariable(String),
+}
+
+impl Server {
+    // Parse URL template and check definitions of variables
+    pub fn template(&self) -> Result<Vec<Part<'_>>, Error> {
+        let url = self.url.as_str();
+        for (name, var) in self.variables.iter().flatten() {
+            if let Some(values) = &var.r#enum {
+                if values.is_empty() {
+                    return Err(Error::EmptyEnum(name.as_str().into()));
+                }
+                if !values.contains(&var.default) {
+                    return Err(Error::DefaultNotInEnum(name.as_str().into()));
+                }
+            }
+        }
+        template::tokenize(url)
+            .map_err(|_| Error::UnclosedBrace(url.into()))?
+            .into_iter()
+            .map(|token| match token {
+                Token::Literal(s) => Ok(Part::Literal(s)),
+                Token::Name("") => Err(Error::EmptyVariable(url.into())),
+                Token::Name(name) => self
+                    .variables
+                    .iter()
+                    .flatten()
+                    .find(|(var_name, _)| var_name.as_str() == name)
+                    .map(|(name, var)| Part::Variable(name, var))
+                    .ok_or_else(|| Error::UndefinedVariable(url.into(), name.into())),
+            })
+            .collect()
+    }
+
+    // Substitute variables by overrides or default values
+    pub fn resolve(&self, overrides: &Overrides) -> Result<String, Error> {
+        let is_defined = |name: &str| {
+            self.variables
+                .iter()
+                .flatten()
+                .any(|(var_name, _)| var_name.as_str() == name)
+        };
+        if let Some(name) = overrides.keys().find(|name| !is_defined(name)) {
+            return Err(Error::UnknownVariable((*name).into()));
+        }
+        let mut result = String::new();
+        for part in self.template()? {
+            match part {
+                Part::Literal(s) => result.push_str(s),
+                Part::Variable(name, var) => {
+                    let value = match overrides.get(name.as_str()) {
+                        None => var.default.as_str(),
+                        Some(value) => {
+                            let allowed = var
+                                .r#enum
+                                .as_ref()
+                                .is_none_or(|values| values.iter().any(|v| v.as_str() == *value));
+                            if !allowed {
+                                return Err(Error::ValueNotInEnum(
+                                    name.as_str().into(),
+                                    (*value).into(),
+                                ));
+                            }
+                            value
+                        }
+                    };
+                    result.push_str(value);
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+// Base URL of the first server of effective servers. Servers of the
+// operation override servers of path item which override servers of
+// the description.
+pub fn base_url(servers: &[Server], overrides: &Overrides) -> Result<String, Error> {
+    servers
+        .first()
+        .map_or(Ok(DEFAULT_URL.into()), |server| server.resolve(overrides))
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnclosedBrace(url) => write!(f, "unclosed brace in server URL {url}"),
+            Self::EmptyVariable(url) => write!(f, "empty variable name in server URL {url}"),
+            Self::UndefinedVariable(url, name) => {
+                write!(f, "variable {name} of server URL {url} is not defined")
+            }
+            Self::EmptyEnum(name) => write!(f, "enum of server variable {name} is empty"),
+            Self::DefaultNotInEnum(name) => {
+                write!(f, "default of server variable {name} is not in enum")
+            }
+            Self::ValueNotInEnum(name, value) => {
+                write!(f, "value {value} of server variable {name} is not in enum")
+            }
+            Self::UnknownVariable(name) => write!(f, "unknown server variable {name}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SERVER: &str = r#"
+url: https://{env}.example.com:{port}/v1
+description: Pet store
+variables:
+  env:
+    enum: [api, staging]
+    default: api
+  port:
+    default: "443"
+"#;
+
+    #[test]
+    fn resolve_url() {
+        let server: Server = serde_yaml::from_str(SERVER).unwrap();
+        assert_eq!(
+            server.description.as_ref().map(|v| v.as_str()),
+            Some("Pet store")
+        );
+        assert_eq!(
+            server.resolve(&Overrides::default()),
+            Ok("https://api.example.com:443/v1".into())
+        );
+        let overrides = Overrides::from([("env", "staging"), ("port", "8443")]);
+        assert_eq!(
+            base_url(std::slice::from_ref(&server), &overrides),
+            Ok("https://staging.example.com:8443/v1".into())
+        );
+        let overrides = Overrides::from([("env", "prod")]);
+        assert_eq!(
+            server.resolve(&overrides),
+            Err(Error::ValueNotInEnum("env".into(), "prod".into()))
+        );
+        let overrides = Overrides::from([("host", "localhost")]);
+        assert_eq!(
+            server.resolve(&overrides),
+            Err(Error::UnknownVariable("host".into()))
+        );
+        assert_eq!(base_url(&[], &Overrides::default()), Ok("/".into()));
+    }
+
+    #[test]
+    fn wrong_templates() {
+        let server: Server = serde_yaml::from_str("url: https://{host}/v1").unwrap();
+        assert!(matches!(
+            server.template(),
+            Err(Error::UndefinedVariable(_, name)) if name == "host"
+        ));
+        let server: Server = serde_yaml::from_str("url: https://{host/v1").unwrap();
+        assert!(matches!(server.template(), Err(Error::UnclosedBrace(_))));
+        let spec = SERVER.replace("default: api", "default: prod");
+        let server: Server = serde_yaml::from_str(&spec).unwrap();
+        assert_eq!(
+            server.template(),
+            Err(Error::DefaultNotInEnum("env".into()))
+        );
+    }
+}
@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: MIT
+//
+// Source locations of the description nodes.
+//
+// Location of every node is kept by JSON pointer to the node. Value
+// of the mapping is located at its key, so location of
+// /paths/~1pets/get is the line with "get:". Pointers of compiled
+// operations, schemas and compilation errors are resolved to source
+// locations this way.
+//
+
+use crate::schema::load::Location;
+use crate::transform::pointer;
+use std::collections::HashMap;
+use yaml_rust2::parser::Event;
+use yaml_rust2::parser::MarkedEventReceiver;
+use yaml_rust2::parser::Parser;
+use yaml_rust2::scanner::Marker;
+
+#[derive(Debug, Default, Clone)]
+pub struct Spans {
+    // Source file (if known)
+    pub file: Option<String>,
+    locations: HashMap<String, Location>,
+}
+
+// Location of the node in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'s> {
+    pub file: Option<&'s str>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    pub location: Location,
+    pub message: String,
+}
+
+impl Spans {
+    // Spans without known locations
+    pub fn new(file: Option<&str>) -> Self {
+        Self {
+            file: file.map(String::from),
+            locations: HashMap::default(),
+        }
+    }
+
+    // Locate nodes of YAML or JSON source
+    pub fn from_str(s: &str, file: Option<&str>) -> Result<Self, Error> {
+        let mut builder = Builder::default();
+        Parser::new_from_str(s)
+            .load(&mut builder, false)
+            .map_err(|err| Error {
+                location: location(err.marker()),
+                message: err.info().into(),
+            })?;
+        Ok(Self {
+            locations: builder.locations,
+            ..Self::new(file)
+        })
+    }
+
+    pub fn get(&self, pointer: &str) -> Option<Location> {
+        self.locations.get(pointer).copied()
+    }
+
+    // Span of the node or of its closest ancestor present in the
+    // source (e.g. pointer to the default value may point to absent
+    // node)
+    pub fn find(&self, pointer: &str) -> Option<Span<'_>> {
+        let mut pointer = pointer;
+        loop {
+            if let Some(location) = self.get(pointer) {
+                return Some(Span {
+                    file: self.file.as_deref(),
+                    location,
+                });
+            }
+            pointer = &pointer[..pointer.rfind('/')?];
+        }
+    }
+}
+
+fn location(mark: &Marker) -> Location {
+    // Columns of the marker start from 0
+    Location {
+        line: mark.line(),
+        column: mark.col() + 1,
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    stack: Vec<Frame>,
+    locations: HashMap<String, Location>,
+}
+
+struct Frame {
+    pointer: String,
+    kind: FrameKind,
+    // Collection is a key of the mapping
+    is_key: bool,
+}
+
+enum FrameKind {
+    // Key of the value that is expected next
+    Mapping(Option<(String, Location)>),
+    // Index of the next item
+    Sequence(usize),
+}
+
+impl Builder {
+    // Pointer and location of the node that starts at mark. None if
+    // node is a key of the mapping.
+    fn node(&mut self, scalar: Option<&str>, mark: &Marker) -> Option<(String, Location)> {
+        let Some(parent) = self.stack.last_mut() else {
+            return Some((String::new(), location(mark)));
+        };
+        match &mut parent.kind {
+            FrameKind::Mapping(key @ None) => {
+                // Complex keys are not located
+                let at = location(mark);
+                *key = Some((scalar.unwrap_or_default().into(), at));
+                // Block mapping starts after its first key is scanned
+                // so mapping is located at the key
+                if !parent.is_key {
+                    if let Some(start) = self.locations.get_mut(&parent.pointer) {
+                        *start = at.min(*start);
+                    }
+                }
+                None
+            }
+            FrameKind::Mapping(key @ Some(_)) => {
+                let (key, at) = key.take()?;
+                Some((format!("{}/{}", parent.pointer, pointer::escape(&key)), at))
+            }
+            FrameKind::Sequence(index) => {
+                *index += 1;
+                let pointer = format!("{}/{}", parent.pointer, *index - 1);
+                Some((pointer, location(mark)))
+            }
+        }
+    }
+
+    fn start(&mut self, kind: FrameKind, mark: &Marker) {
+        let frame = match self.node(None, mark) {
+            Some((pointer, at)) => {
+                self.locations.insert(pointer.clone(), at);
+                Frame {
+                    pointer,
+                    kind,
+                    is_key: false,
+                }
+            }
+            None => Frame {
+                pointer: String::new(),
+                kind,
+                is_key: true,
+            },
+        };
+        self.stack.push(frame);
+    }
+
+    fn end(&mut self) {
+        if let Some(Frame { is_key: true, .. }) = self.stack.pop() {
+            // Value of the complex key is not located
+            if let Some(Frame {
+                kind: FrameKind::Mapping(key),
+                ..
+            }) = self.stack.last_mut()
+            {
+                *key = None;
+            }
+        }
+    }
+}
+
+impl MarkedEventReceiver for Builder {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        match ev {
+            Event::Scalar(value, ..) => {
+                if let Some((pointer, at)) = self.node(Some(&value), &mark) {
+                    self.locations.insert(pointer, at);
+                }
+            }
+            Event::Alias(_) => {
+                if let Some((pointer, at)) = self.node(None, &mark) {
+                    self.locations.insert(pointer, at);
+                }
+            }
+            Event::MappingStart(..) => self.start(FrameKind::Mapping(None), &mark),
+            Event::SequenceStart(..) => self.start(FrameKind::Sequence(0), &mark),
+            Event::MappingEnd | Event::SequenceEnd => self.end(),
+            Event::Nothing
+            | Event::StreamStart
+            | Event::StreamEnd
+            | Event::DocumentStart
+            | Event::DocumentEnd => {}
+        }
+    }
+}
+
+impl std::fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.file {
+            Some(file) => write!(f, "{file}:{}", self.location),
+            None => self.location.fmt(f),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile;
+    use crate::schema::load;
+
+    fn at(line: usize, column: usize) -> Option<Location> {
+        Some(Location { line, column })
+    }
+
+    #[test]
+    fn locations() {
+        let yaml = include_str!("../../test/test-vectors/spans.yaml");
+        let spans = Spans::from_str(yaml, Some("spans.yaml")).unwrap();
+        assert_eq!(spans.get(""), at(1, 1));
+        assert_eq!(spans.get("/paths/~1pets~1{petId}"), at(6, 3));
+        assert_eq!(spans.get("/paths/~1pets~1{petId}/parameters/0"), at(8, 9));
+        assert_eq!(
+            spans.get("/paths/~1pets~1{petId}/parameters/0/in"),
+            at(9, 9)
+        );
+        assert_eq!(spans.get("/paths/~1pets~1{petId}/get"), at(13, 5));
+        assert_eq!(
+            spans.get("/components/schemas/Pet/properties/name"),
+            at(27, 9)
+        );
+        assert_eq!(
+            spans.get("/components/schemas/Pet/properties/owner/$ref"),
+            at(30, 11)
+        );
+        assert_eq!(spans.get("/components/schemas/Owner"), None);
+        assert_eq!(
+            spans
+                .find("/components/schemas/Pet/properties/name/default")
+                .unwrap()
+                .to_string(),
+            "spans.yaml:27:9"
+        );
+
+        let json = serde_json::to_string_pretty(&load::from_str(yaml).unwrap()).unwrap();
+        let spans = Spans::from_str(&json, None).unwrap();
+        assert_eq!(spans.get("/info/title"), at(4, 5));
+        assert_eq!(spans.find("/info/title").unwrap().to_string(), "4:5");
+    }
+
+    #[test]
+    fn compile_error() {
+        let yaml = include_str!("../../test/test-vectors/spans.yaml");
+        let (d, spans) = load::from_str_spanned(yaml, None, Some("spans.yaml")).unwrap();
+        let err = compile::compile(&d).unwrap_err();
+        assert_eq!(
+            err.pointer(),
+            "/components/schemas/Pet/properties/owner/$ref"
+        );
+        assert_eq!(
+            spans.find(&err.pointer()).unwrap().to_string(),
+            "spans.yaml:30:11"
+        );
+    }
+}
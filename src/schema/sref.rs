@@ -9,8 +9,10 @@ use serde::de;
 use serde::de::Visitor;
 use serde::Deserialize;
 use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct SRef(String);
 
 pub type SRefParameter = TaggedString<SRefParameterTag>;
@@ -25,6 +27,21 @@ pub enum SRefSchemasObjectNameTag {}
 pub type SRefResponsesName = TaggedString<SRefResponsesNameTag>;
 pub enum SRefResponsesNameTag {}
 
+pub type SRefCallbackName = TaggedString<SRefCallbackNameTag>;
+pub enum SRefCallbackNameTag {}
+
+pub type SRefExampleName = TaggedString<SRefExampleNameTag>;
+pub enum SRefExampleNameTag {}
+
+pub type SRefHeaderName = TaggedString<SRefHeaderNameTag>;
+pub enum SRefHeaderNameTag {}
+
+pub type SRefLinkName = TaggedString<SRefLinkNameTag>;
+pub enum SRefLinkNameTag {}
+
+pub type SRefSecuritySchemeName = TaggedString<SRefSecuritySchemeNameTag>;
+pub enum SRefSecuritySchemeNameTag {}
+
 #[derive(Debug, Clone)]
 pub enum SRefSchemas {
     // Normal reference
@@ -34,12 +51,57 @@ pub enum SRefSchemas {
 
 pub enum SRefSchemasTag {}
 
+const CALLBACKS_PREFIX: &str = "#/components/callbacks/";
+const EXAMPLES_PREFIX: &str = "#/components/examples/";
+const HEADERS_PREFIX: &str = "#/components/headers/";
+const LINKS_PREFIX: &str = "#/components/links/";
 const PARAMETERS_PREFIX: &str = "#/components/parameters/";
 const REQUEST_BODIES_PREFIX: &str = "#/components/requestBodies/";
 const RESPONSES_PREFIX: &str = "#/components/responses/";
 const SCHEMAS_PREFIX: &str = "#/components/schemas/";
+const SECURITY_SCHEMES_PREFIX: &str = "#/components/securitySchemes/";
 
 impl SRef {
+    pub fn callback_sref(&self) -> Option<SRefCallbackName> {
+        if self.0.starts_with(CALLBACKS_PREFIX) {
+            Some(SRefCallbackName::new(
+                self.0.as_str()[CALLBACKS_PREFIX.len()..].into(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn example_sref(&self) -> Option<SRefExampleName> {
+        if self.0.starts_with(EXAMPLES_PREFIX) {
+            Some(SRefExampleName::new(
+                self.0.as_str()[EXAMPLES_PREFIX.len()..].into(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn header_sref(&self) -> Option<SRefHeaderName> {
+        if self.0.starts_with(HEADERS_PREFIX) {
+            Some(SRefHeaderName::new(
+                self.0.as_str()[HEADERS_PREFIX.len()..].into(),
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub fn link_sref(&self) -> Option<SRefLinkName> {
+        if self.0.starts_with(LINKS_PREFIX) {
+            Some(SRefLinkName::new(
+                self.0.as_str()[LINKS_PREFIX.len()..].into(),
+            ))
+        } else {
+            None
+        }
+    }
+
     pub fn parameter_sref(&self) -> Option<SRefParameter> {
         if self.0.starts_with(PARAMETERS_PREFIX) {
             Some(SRefParameter::new(
@@ -70,6 +132,16 @@ impl SRef {
         }
     }
 
+    pub fn security_scheme_sref(&self) -> Option<SRefSecuritySchemeName> {
+        if self.0.starts_with(SECURITY_SCHEMES_PREFIX) {
+            Some(SRefSecuritySchemeName::new(
+                self.0.as_str()[SECURITY_SCHEMES_PREFIX.len()..].into(),
+            ))
+        } else {
+            None
+        }
+    }
+
     pub fn schemas_sref(&self) -> Result<Option<SRefSchemas>, Error> {
         if self.0.starts_with(SCHEMAS_PREFIX) {
             Ok(Some(self.0.as_str()[SCHEMAS_PREFIX.len()..].parse()?))
@@ -100,6 +172,15 @@ impl std::str::FromStr for SRef {
     }
 }
 
+impl Serialize for SRef {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.0)
+    }
+}
+
 impl<'de> Deserialize<'de> for SRef {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
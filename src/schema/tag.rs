@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT
+//
+// OpenAPI Schema
+// Tag Object
+//
+
+use crate::schema::extensions::Extensions;
+use crate::schema::external_doc::ExternalDoc;
+use crate::schema::ApiDocTag;
+use crate::typing::TaggedString;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Tag {
+    pub name: ApiDocTag,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Description>,
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDoc>,
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+pub type Description = TaggedString<TagDescriptionTag>;
+pub enum TagDescriptionTag {}
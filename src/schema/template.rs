@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT
+//
+// Templates with names in curly braces
+//
+// Path templates (/pets/{id}), server URLs (https://{env}.example.com)
+// and strings with runtime expressions ({$request.body#/url}/cancel)
+// are split into the same tokens. Meaning of the names is up to the
+// users of the template.
+//
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    Literal(&'a str),
+    // Content of the curly braces, may be empty
+    Name(&'a str),
+}
+
+// Opening curly brace is not closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnclosedBrace;
+
+pub fn tokenize(s: &str) -> Result<Vec<Token<'_>>, UnclosedBrace> {
+    let mut result = vec![];
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            result.push(Token::Literal(&rest[..start]));
+        }
+        let end = rest[start..].find('}').ok_or(UnclosedBrace)?;
+        result.push(Token::Name(&rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        result.push(Token::Literal(rest));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            tokenize("/pets/{petId}/photos/{name}.{ext}"),
+            Ok(vec![
+                Token::Literal("/pets/"),
+                Token::Name("petId"),
+                Token::Literal("/photos/"),
+                Token::Name("name"),
+                Token::Literal("."),
+                Token::Name("ext"),
+            ])
+        );
+        assert_eq!(tokenize("{}"), Ok(vec![Token::Name("")]));
+        assert_eq!(tokenize(""), Ok(vec![]));
+        assert_eq!(tokenize("/pets/{id"), Err(UnclosedBrace));
+    }
+}
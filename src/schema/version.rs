@@ -5,7 +5,7 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
 #[derive(PartialEq, Eq, Debug)]
@@ -58,6 +58,21 @@ impl std::fmt::Display for Error {
     }
 }
 
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_str(self)
+    }
+}
+
 impl<'de> Deserialize<'de> for Version {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
@@ -102,6 +117,12 @@ mod test {
         );
     }
 
+    #[test]
+    fn version_to_string() {
+        let version: Version = "3.0.12".parse().unwrap();
+        assert_eq!(version.to_string(), "3.0.12");
+    }
+
     #[test]
     fn version_invalid_major_num_format() {
         let parse_result: Result<Version, _> = "a.1.2".parse();
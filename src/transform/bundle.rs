@@ -0,0 +1,434 @@
+// SPDX-License-Identifier: MIT
+//
+// Bundling of multi-file openapi specification into single
+// self-contained document.
+//
+// All external references ($ref: 'file.yaml#/pointer') are copied
+// into the components of the root document and references are
+// rewritten to local pointers. Identical objects are deduplicated
+// and name clashes are resolved deterministically (in order of
+// document traversal).
+//
+
+use crate::schema;
+use crate::schema::load;
+use crate::transform;
+use crate::transform::kind;
+use crate::transform::kind::Child;
+use crate::transform::kind::Kind;
+use crate::transform::kind::SECTIONS;
+use crate::transform::pointer;
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+const MAX_DEPTH: u32 = 1024;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, std::io::Error),
+    Load(PathBuf, load::Error),
+    PointerNotFound(PathBuf, String),
+    MaxDepthReached(u32),
+    Schema(serde_yaml::Error),
+}
+
+// Bundle specification starting from the root document.
+pub fn bundle(root: &Path) -> Result<Value, Error> {
+    let root = root
+        .canonicalize()
+        .map_err(|err| Error::Io(root.to_path_buf(), err))?;
+    let mut bundler = Bundler::new(root.clone());
+    let mut doc = bundler.load(&root)?.clone();
+    bundler.collect_existing(&doc);
+    bundler.walk(&mut doc, Kind::Document, &root, 0)?;
+    bundler.merge_components(&mut doc);
+    bundler.resolve_aliases(&mut doc);
+    Ok(doc)
+}
+
+// Bundle specification and check that result is valid description.
+pub fn bundle_description(root: &Path) -> Result<(Value, schema::Description), Error> {
+    let doc = bundle(root)?;
+    let description = transform::parse_description(&doc).map_err(Error::Schema)?;
+    Ok((doc, description))
+}
+
+enum Resolved {
+    // Reference is rewritten to new value
+    Rewrite(String),
+    // Reference is replaced by the referenced object
+    Inline(Value),
+}
+
+type Section = indexmap::IndexMap<String, Value>;
+
+struct Bundler {
+    root: PathBuf,
+    documents: HashMap<PathBuf, Value>,
+    // (file, pointer) => local reference
+    targets: HashMap<(PathBuf, String), String>,
+    // Components of the root document
+    existing: indexmap::IndexMap<&'static str, Section>,
+    // Components collected from external documents
+    bundled: indexmap::IndexMap<&'static str, Section>,
+    // Local references of the deduplicated objects
+    aliases: HashMap<String, String>,
+}
+
+impl Bundler {
+    fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            documents: HashMap::default(),
+            targets: HashMap::default(),
+            existing: indexmap::IndexMap::default(),
+            bundled: indexmap::IndexMap::default(),
+            aliases: HashMap::default(),
+        }
+    }
+
+    fn load(&mut self, path: &Path) -> Result<&Value, Error> {
+        if !self.documents.contains_key(path) {
+            let doc =
+                load::value_from_path(path).map_err(|err| Error::Load(path.to_path_buf(), err))?;
+            self.documents.insert(path.to_path_buf(), doc);
+        }
+        Ok(&self.documents[path])
+    }
+
+    fn collect_existing(&mut self, doc: &Value) {
+        if let Some(Value::Mapping(components)) = doc.get("components") {
+            for (section, items) in components.iter() {
+                let section = section
+                    .as_str()
+                    .and_then(|s| SECTIONS.iter().find(|v| **v == s).copied());
+                if let (Some(section), Value::Mapping(items)) = (section, items) {
+                    self.existing.insert(
+                        section,
+                        items
+                            .iter()
+                            .filter_map(|(k, v)| k.as_str().map(|k| (k.to_string(), v.clone())))
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn walk(&mut self, v: &mut Value, kind: Kind, base: &Path, depth: u32) -> Result<(), Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::MaxDepthReached(depth));
+        }
+        let Value::Mapping(m) = v else {
+            return Ok(());
+        };
+        if let Some(Value::String(r)) = m.get("$ref") {
+            let r = r.clone();
+            match self.reference(&r, kind, base, depth)? {
+                Resolved::Rewrite(r) => {
+                    m.insert("$ref".into(), Value::String(r));
+                }
+                Resolved::Inline(target) => {
+                    // Sibling fields override fields of referenced object
+                    let mut siblings = std::mem::take(m);
+                    siblings.remove("$ref");
+                    *v = target;
+                    if let Value::Mapping(m) = v {
+                        m.extend(siblings);
+                    }
+                }
+            }
+            return Ok(());
+        }
+        for (key, child) in m.iter_mut() {
+            let Some(key) = kind::key_str(key) else {
+                continue;
+            };
+            match kind.child(&key) {
+                Child::One(kind) => self.walk(child, kind, base, depth + 1)?,
+                Child::Map(kind) => {
+                    if let Value::Mapping(items) = child {
+                        for (_, item) in items.iter_mut() {
+                            self.walk(item, kind, base, depth + 1)?;
+                        }
+                    }
+                }
+                Child::Seq(kind) => {
+                    if let Value::Sequence(items) = child {
+                        for item in items.iter_mut() {
+                            self.walk(item, kind, base, depth + 1)?;
+                        }
+                    }
+                }
+                Child::RefStrings(kind) => {
+                    if let Value::Mapping(items) = child {
+                        for (_, item) in items.iter_mut() {
+                            if let Value::String(r) = item {
+                                if let Resolved::Rewrite(r) =
+                                    self.reference(&r.clone(), kind, base, depth)?
+                                {
+                                    *item = Value::String(r);
+                                }
+                            }
+                        }
+                    }
+                }
+                Child::Skip => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn reference(
+        &mut self,
+        r: &str,
+        kind: Kind,
+        base: &Path,
+        depth: u32,
+    ) -> Result<Resolved, Error> {
+        let (file, fragment) = r.split_once('#').unwrap_or((r, ""));
+        let target_file = if file.is_empty() {
+            base.to_path_buf()
+        } else {
+            let path = base.parent().unwrap_or(Path::new(".")).join(file);
+            path.canonicalize().map_err(|err| Error::Io(path, err))?
+        };
+        if target_file == self.root {
+            // Reference to the root document is always local
+            return Ok(Resolved::Rewrite(format!("#{fragment}")));
+        }
+        let key = (target_file.clone(), fragment.to_string());
+        if let Some(local) = self.targets.get(&key) {
+            return Ok(Resolved::Rewrite(local.clone()));
+        }
+        let mut target = pointer::resolve(self.load(&target_file)?, fragment)
+            .cloned()
+            .ok_or_else(|| Error::PointerNotFound(target_file.clone(), fragment.to_string()))?;
+        let Some(section) = kind.section() else {
+            self.walk(&mut target, kind, &target_file, depth + 1)?;
+            return Ok(Resolved::Inline(target));
+        };
+        let name = self.choose_name(section, &target_file, fragment);
+        let local = local_ref(section, &name);
+        self.targets.insert(key.clone(), local.clone());
+        // Reserve name before traversal because object may refer to itself
+        self.bundled
+            .entry(section)
+            .or_default()
+            .insert(name.clone(), Value::Null);
+        self.walk(&mut target, kind, &target_file, depth + 1)?;
+        match self.find_identical(section, &name, &target) {
+            Some(dup) => {
+                self.bundled[section].shift_remove(&name);
+                let dup_local = local_ref(section, &dup);
+                self.targets.insert(key, dup_local.clone());
+                self.aliases.insert(local, dup_local.clone());
+                Ok(Resolved::Rewrite(dup_local))
+            }
+            None => {
+                self.bundled[section].insert(name, target);
+                Ok(Resolved::Rewrite(local))
+            }
+        }
+    }
+
+    fn find_identical(&self, section: &str, name: &str, v: &Value) -> Option<String> {
+        [&self.existing, &self.bundled]
+            .into_iter()
+            .filter_map(|components| components.get(section))
+            .flat_map(|items| items.iter())
+            .find(|(n, item)| n.as_str() != name && *item == v)
+            .map(|(n, _)| n.clone())
+    }
+
+    fn is_taken(&self, section: &str, name: &str) -> bool {
+        [&self.existing, &self.bundled]
+            .into_iter()
+            .filter_map(|components| components.get(section))
+            .any(|items| items.contains_key(name))
+    }
+
+    // Name of the bundled object is last token of the pointer or name
+    // of the file if whole file is referenced. If name is taken then
+    // file name is used as prefix and after that numerical suffix is
+    // added.
+    fn choose_name(&self, section: &str, file: &Path, fragment: &str) -> String {
+        let stem = sanitize(
+            file.file_stem()
+                .and_then(|v| v.to_str())
+                .unwrap_or("external"),
+        );
+        let name = pointer::tokens(fragment)
+            .last()
+            .map(|v| sanitize(v))
+            .unwrap_or_else(|| stem.clone());
+        let prefixed = format!("{stem}_{name}");
+        std::iter::once(name.clone())
+            .chain((name != stem).then_some(prefixed))
+            .chain((2..).map(|n| format!("{name}_{n}")))
+            .find(|candidate| !self.is_taken(section, candidate))
+            .unwrap_or(name)
+    }
+
+    fn merge_components(&mut self, doc: &mut Value) {
+        if self.bundled.values().all(|items| items.is_empty()) {
+            return;
+        }
+        let Value::Mapping(root) = doc else {
+            return;
+        };
+        let components = root
+            .entry("components".into())
+            .or_insert_with(|| Value::Mapping(Mapping::default()));
+        let Value::Mapping(components) = components else {
+            return;
+        };
+        for (section, items) in std::mem::take(&mut self.bundled) {
+            if items.is_empty() {
+                continue;
+            }
+            let target = components
+                .entry(section.into())
+                .or_insert_with(|| Value::Mapping(Mapping::default()));
+            if let Value::Mapping(target) = target {
+                target.extend(items.into_iter().map(|(k, v)| (Value::String(k), v)));
+            }
+        }
+    }
+
+    // Rewrite references to deduplicated objects. This is needed
+    // for references those have been created before deduplication
+    // (self-references of the object).
+    fn resolve_aliases(&self, v: &mut Value) {
+        match v {
+            Value::Mapping(m) => {
+                for (key, child) in m.iter_mut() {
+                    match (key.as_str(), &child) {
+                        (Some("$ref"), Value::String(r)) => {
+                            if let Some(alias) = self.aliases.get(r) {
+                                *child = Value::String(alias.clone());
+                            }
+                        }
+                        (Some("mapping"), Value::Mapping(_)) => {
+                            if let Value::Mapping(items) = child {
+                                for (_, item) in items.iter_mut() {
+                                    let alias = item.as_str().and_then(|r| self.aliases.get(r));
+                                    if let Some(alias) = alias {
+                                        *item = Value::String(alias.clone());
+                                    }
+                                }
+                            }
+                        }
+                        _ => self.resolve_aliases(child),
+                    }
+                }
+            }
+            Value::Sequence(s) => s.iter_mut().for_each(|v| self.resolve_aliases(v)),
+            _ => {}
+        }
+    }
+}
+
+fn local_ref(section: &str, name: &str) -> String {
+    format!("#/components/{section}/{}", pointer::escape(name))
+}
+
+// Component names must match ^[a-zA-Z0-9\.\-_]+$
+fn sanitize(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::Load(path, err) => write!(f, "{}: {err}", path.display()),
+            Self::PointerNotFound(path, pointer) => {
+                write!(f, "{}: pointer not found: #{pointer}", path.display())
+            }
+            Self::MaxDepthReached(depth) => write!(f, "maximum depth reached: {depth}"),
+            Self::Schema(err) => write!(f, "bundled document is not valid: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn vector(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test/test-vectors/bundle")
+            .join(name)
+    }
+
+    #[test]
+    fn bundle_external_references() {
+        let (doc, description) = bundle_description(&vector("openapi.yaml")).unwrap();
+        let schemas = description.components.unwrap().schemas.unwrap();
+        let names = schemas.keys().map(|v| format!("{v:?}")).collect::<Vec<_>>();
+        // Error is defined in root and identical one in common.yaml.
+        // Root Pet clashes with external pets.yaml#/Pet.
+        assert_eq!(
+            names,
+            [
+                "\"Error\"",
+                "\"Pet\"",
+                "\"pets_Pet\"",
+                "\"Tag\"",
+                "\"Owner\""
+            ]
+        );
+        let get_ref = pointer::resolve(
+            &doc,
+            "/paths/~1pets/get/responses/200/content/application~1json/schema/items/$ref",
+        );
+        assert_eq!(
+            get_ref.and_then(|v| v.as_str()),
+            Some("#/components/schemas/pets_Pet")
+        );
+        let err_ref = pointer::resolve(&doc, "/paths/~1pets/get/responses/default/$ref");
+        assert_eq!(
+            err_ref.and_then(|v| v.as_str()),
+            Some("#/components/responses/ErrorResponse")
+        );
+        let error_schema = pointer::resolve(
+            &doc,
+            "/components/responses/ErrorResponse/content/application~1json/schema/$ref",
+        );
+        assert_eq!(
+            error_schema.and_then(|v| v.as_str()),
+            Some("#/components/schemas/Error")
+        );
+    }
+
+    #[test]
+    fn bundle_inlines_path_items() {
+        let doc = bundle(&vector("openapi.yaml")).unwrap();
+        let owner_ref = pointer::resolve(
+            &doc,
+            "/paths/~1owners/get/responses/200/content/application~1json/schema/$ref",
+        );
+        assert_eq!(
+            owner_ref.and_then(|v| v.as_str()),
+            Some("#/components/schemas/Owner")
+        );
+        // Owner refers to itself
+        let friend_ref = pointer::resolve(&doc, "/components/schemas/Owner/properties/friend/$ref");
+        assert_eq!(
+            friend_ref.and_then(|v| v.as_str()),
+            Some("#/components/schemas/Owner")
+        );
+    }
+}
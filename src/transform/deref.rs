@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: MIT
+//
+// Full dereferencing of openapi description.
+//
+// Every Reference object (schemas, parameters, request bodies,
+// responses, headers, path items, ...) is replaced by a copy of the
+// referenced object so the result does not contain $ref at all.
+// Only local references are supported so multi-file specifications
+// must be bundled first (see transform::bundle).
+//
+
+use crate::schema;
+use crate::transform;
+use crate::transform::kind;
+use crate::transform::kind::Child;
+use crate::transform::kind::Kind;
+use crate::transform::pointer;
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+
+const MAX_DEPTH: u32 = 1024;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    // How many times recursive reference is expanded inside
+    // itself. When limit is reached schema reference is replaced by
+    // empty object (any value) and other references are errors. If
+    // None then recursive reference is an error.
+    pub recursion_limit: Option<u32>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    ExternalReference(String),
+    PointerNotFound(String),
+    RecursiveReference(String),
+    MaxDepthReached(u32),
+    Schema(serde_yaml::Error),
+}
+
+pub fn deref(doc: &Value, options: &Options) -> Result<Value, Error> {
+    let deref = Deref {
+        doc,
+        options,
+        stack: vec![],
+    };
+    deref.expand(doc, Kind::Document, 0)
+}
+
+// Dereference document and check that result is valid description.
+pub fn deref_description(
+    doc: &Value,
+    options: &Options,
+) -> Result<(Value, schema::Description), Error> {
+    let expanded = deref(doc, options)?;
+    let description = transform::parse_description(&expanded).map_err(Error::Schema)?;
+    Ok((expanded, description))
+}
+
+struct Deref<'a> {
+    doc: &'a Value,
+    options: &'a Options,
+    // References those are currently expanded
+    stack: Vec<&'a str>,
+}
+
+impl<'a> Deref<'a> {
+    fn expand(&self, v: &'a Value, kind: Kind, depth: u32) -> Result<Value, Error> {
+        if depth > MAX_DEPTH {
+            return Err(Error::MaxDepthReached(depth));
+        }
+        let Value::Mapping(m) = v else {
+            return Ok(v.clone());
+        };
+        if let Some(Value::String(r)) = m.get("$ref") {
+            return self.expand_ref(r, m, kind, depth);
+        }
+        let mut result = Mapping::with_capacity(m.len());
+        for (key, child) in m.iter() {
+            let expanded = match kind::key_str(key).map(|key| kind.child(&key)) {
+                Some(Child::One(kind)) => self.expand(child, kind, depth + 1)?,
+                Some(Child::Map(kind)) => match child {
+                    Value::Mapping(items) => Value::Mapping(
+                        items
+                            .iter()
+                            .map(|(k, item)| Ok((k.clone(), self.expand(item, kind, depth + 1)?)))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    _ => child.clone(),
+                },
+                Some(Child::Seq(kind)) => match child {
+                    Value::Sequence(items) => Value::Sequence(
+                        items
+                            .iter()
+                            .map(|item| self.expand(item, kind, depth + 1))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    _ => child.clone(),
+                },
+                // Discriminator mapping values are not Reference objects
+                Some(Child::RefStrings(_)) | Some(Child::Skip) | None => child.clone(),
+            };
+            result.insert(key.clone(), expanded);
+        }
+        Ok(Value::Mapping(result))
+    }
+
+    fn expand_ref(
+        &self,
+        r: &'a str,
+        m: &'a Mapping,
+        kind: Kind,
+        depth: u32,
+    ) -> Result<Value, Error> {
+        let fragment = r
+            .strip_prefix('#')
+            .ok_or_else(|| Error::ExternalReference(r.into()))?;
+        let recursion = self.stack.iter().filter(|v| **v == r).count() as u32;
+        if recursion > 0 {
+            // Empty object is any value only if it is a schema
+            match self.options.recursion_limit {
+                Some(limit) if recursion <= limit => {}
+                Some(_) if kind == Kind::Schema => return Ok(Value::Mapping(Mapping::default())),
+                _ => return Err(Error::RecursiveReference(r.into())),
+            }
+        }
+        let target =
+            pointer::resolve(self.doc, fragment).ok_or_else(|| Error::PointerNotFound(r.into()))?;
+        let mut stack = self.stack.clone();
+        stack.push(r);
+        let nested = Deref {
+            doc: self.doc,
+            options: self.options,
+            stack,
+        };
+        let mut expanded = nested.expand(target, kind, depth + 1)?;
+        // Sibling fields (e.g. summary / description) override fields
+        // of the referenced object
+        if let Value::Mapping(expanded) = &mut expanded {
+            for (key, sibling) in m.iter().filter(|(k, _)| k.as_str() != Some("$ref")) {
+                expanded.insert(key.clone(), self.expand(sibling, kind, depth + 1)?);
+            }
+        }
+        Ok(expanded)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExternalReference(r) => {
+                write!(f, "external reference must be bundled first: {r}")
+            }
+            Self::PointerNotFound(r) => write!(f, "reference not found: {r}"),
+            Self::RecursiveReference(r) => write!(f, "recursive reference: {r}"),
+            Self::MaxDepthReached(depth) => write!(f, "maximum depth reached: {depth}"),
+            Self::Schema(err) => write!(f, "dereferenced document is not valid: {err}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Dereference
+  version: 0.0.1
+paths:
+  /pets/{id}:
+    parameters:
+      - $ref: '#/components/parameters/PetId'
+    get:
+      responses:
+        200:
+          $ref: '#/components/responses/Pet'
+components:
+  parameters:
+    PetId:
+      name: id
+      in: path
+      required: true
+      schema:
+        type: string
+  responses:
+    Pet:
+      description: Pet
+      content:
+        application/json:
+          schema:
+            $ref: '#/components/schemas/Pet'
+          example:
+            $ref: 'not a reference'
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+        parent:
+          $ref: '#/components/schemas/Pet'
+"#;
+
+    fn contains_ref(v: &Value) -> bool {
+        match v {
+            Value::Mapping(m) => m
+                .iter()
+                .any(|(k, v)| k.as_str() == Some("$ref") || contains_ref(v)),
+            Value::Sequence(s) => s.iter().any(contains_ref),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn recursive_reference_is_error() {
+        let doc: Value = serde_yaml::from_str(SPEC).unwrap();
+        let result = deref(&doc, &Options::default());
+        assert!(matches!(result, Err(Error::RecursiveReference(_))));
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let doc: Value = serde_yaml::from_str(SPEC).unwrap();
+        let options = Options {
+            recursion_limit: Some(1),
+        };
+        let (expanded, _) = deref_description(&doc, &options).unwrap();
+        let media = "/paths/~1pets~1{id}/get/responses/200/content/application~1json";
+        let schema = pointer::resolve(&expanded, &format!("{media}/schema")).unwrap();
+        assert!(!contains_ref(schema));
+        let parent = pointer::resolve(schema, "/properties/parent/properties/name");
+        assert!(parent.is_some());
+        let cut = pointer::resolve(schema, "/properties/parent/properties/parent");
+        assert_eq!(cut, Some(&Value::Mapping(Mapping::default())));
+        // Example values are not references
+        let example = pointer::resolve(&expanded, &format!("{media}/example/$ref"));
+        assert_eq!(example.and_then(|v| v.as_str()), Some("not a reference"));
+        let param = pointer::resolve(&expanded, "/paths/~1pets~1{id}/parameters/0/name");
+        assert_eq!(param.and_then(|v| v.as_str()), Some("id"));
+    }
+
+    #[test]
+    fn recursion_limit_of_path_item() {
+        let spec = r#"
+openapi: 3.1.0
+info:
+  title: Recursive path item
+  version: 0.0.1
+paths:
+  /pets:
+    $ref: '#/components/pathItems/Pets'
+components:
+  pathItems:
+    Pets:
+      $ref: '#/components/pathItems/Pets'
+"#;
+        let doc: Value = serde_yaml::from_str(spec).unwrap();
+        let options = Options {
+            recursion_limit: Some(1),
+        };
+        let result = deref(&doc, &options);
+        assert!(matches!(result, Err(Error::RecursiveReference(_))));
+    }
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT
+//
+// Kinds of the objects of raw openapi document tree
+//
+
+use serde_yaml::Value;
+
+// Kind of the object in the document. Defines where referenced
+// object is placed in components and how to traverse its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Document,
+    Components,
+    PathItem,
+    Operation,
+    Parameter,
+    RequestBody,
+    Responses,
+    Response,
+    Header,
+    MediaType,
+    Encoding,
+    Schema,
+    Discriminator,
+    Example,
+    Link,
+    Callback,
+    SecurityScheme,
+}
+
+pub enum Child {
+    One(Kind),
+    Map(Kind),
+    Seq(Kind),
+    // Map of strings those are references (discriminator mapping)
+    RefStrings(Kind),
+    Skip,
+}
+
+impl Kind {
+    // Section of the components where object of this kind is placed.
+    // Path items are always inlined.
+    pub fn section(&self) -> Option<&'static str> {
+        match self {
+            Self::Schema => Some("schemas"),
+            Self::Response => Some("responses"),
+            Self::Parameter => Some("parameters"),
+            Self::Example => Some("examples"),
+            Self::RequestBody => Some("requestBodies"),
+            Self::Header => Some("headers"),
+            Self::SecurityScheme => Some("securitySchemes"),
+            Self::Link => Some("links"),
+            Self::Callback => Some("callbacks"),
+            _ => None,
+        }
+    }
+
+    pub fn child(&self, key: &str) -> Child {
+        if key.starts_with("x-") {
+            return Child::Skip;
+        }
+        match (self, key) {
+            (Self::Document, "paths" | "webhooks") => Child::Map(Self::PathItem),
+            (Self::Document, "components") => Child::One(Self::Components),
+            (Self::Components, "schemas") => Child::Map(Self::Schema),
+            (Self::Components, "responses") => Child::Map(Self::Response),
+            (Self::Components, "parameters") => Child::Map(Self::Parameter),
+            (Self::Components, "examples") => Child::Map(Self::Example),
+            (Self::Components, "requestBodies") => Child::Map(Self::RequestBody),
+            (Self::Components, "headers") => Child::Map(Self::Header),
+            (Self::Components, "securitySchemes") => Child::Map(Self::SecurityScheme),
+            (Self::Components, "links") => Child::Map(Self::Link),
+            (Self::Components, "callbacks") => Child::Map(Self::Callback),
+            (Self::Components, "pathItems") => Child::Map(Self::PathItem),
+            (
+                Self::PathItem,
+                "get" | "put" | "post" | "delete" | "options" | "head" | "patch" | "trace",
+            ) => Child::One(Self::Operation),
+            (Self::PathItem | Self::Operation, "parameters") => Child::Seq(Self::Parameter),
+            (Self::Operation, "requestBody") => Child::One(Self::RequestBody),
+            (Self::Operation, "responses") => Child::One(Self::Responses),
+            (Self::Operation, "callbacks") => Child::Map(Self::Callback),
+            (Self::Responses, _) => Child::One(Self::Response),
+            (Self::Callback, _) => Child::One(Self::PathItem),
+            (Self::Parameter | Self::Header | Self::MediaType, "schema") => {
+                Child::One(Self::Schema)
+            }
+            (Self::Parameter | Self::Header | Self::MediaType, "examples") => {
+                Child::Map(Self::Example)
+            }
+            (Self::Parameter | Self::Header | Self::RequestBody | Self::Response, "content") => {
+                Child::Map(Self::MediaType)
+            }
+            (Self::Response | Self::Encoding, "headers") => Child::Map(Self::Header),
+            (Self::Response, "links") => Child::Map(Self::Link),
+            (Self::MediaType, "encoding") => Child::Map(Self::Encoding),
+            (
+                Self::Schema,
+                "properties" | "patternProperties" | "$defs" | "definitions" | "dependentSchemas",
+            ) => Child::Map(Self::Schema),
+            (
+                Self::Schema,
+                "items"
+                | "additionalProperties"
+                | "additionalItems"
+                | "not"
+                | "propertyNames"
+                | "contains"
+                | "if"
+                | "then"
+                | "else"
+                | "unevaluatedItems"
+                | "unevaluatedProperties",
+            ) => Child::One(Self::Schema),
+            (Self::Schema, "allOf" | "anyOf" | "oneOf" | "prefixItems") => Child::Seq(Self::Schema),
+            (Self::Schema, "discriminator") => Child::One(Self::Discriminator),
+            (Self::Discriminator, "mapping") => Child::RefStrings(Self::Schema),
+            _ => Child::Skip,
+        }
+    }
+}
+
+pub const SECTIONS: [&str; 10] = [
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "pathItems",
+];
+
+// Key of the object as string. Unquoted HTTP status codes are
+// numbers in the document tree.
+pub fn key_str(key: &Value) -> Option<String> {
+    match key {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: MIT
+//
+// Transformations of the raw openapi document tree.
+//
+// Transformations work on serde_yaml::Value so that every part of
+// the document (including parts that are not modeled by
+// crate::schema) is preserved in the output.
+//
+
+pub mod bundle;
+pub mod deref;
+pub mod kind;
+pub mod pointer;
+
+use crate::schema;
+use crate::schema::load::Format;
+use serde_yaml::Value;
+
+#[derive(Debug)]
+pub enum Error {
+    Yaml(serde_yaml::Error),
+    Json(serde_json::Error),
+}
+
+// Parse transformed document tree. Document is parsed from text
+// because keys like unquoted HTTP status codes are numbers in the
+// document tree.
+pub fn parse_description(v: &Value) -> Result<schema::Description, serde_yaml::Error> {
+    serde_yaml::to_string(v).and_then(|text| serde_yaml::from_str(&text))
+}
+
+// Serialize document tree. Output format is usually detected by file
+// extension (see Format::from_path) with YAML as default.
+pub fn serialize(v: &Value, format: Format) -> Result<String, Error> {
+    match format {
+        Format::Yaml => serde_yaml::to_string(v).map_err(Error::Yaml),
+        Format::Json => serde_json::to_string_pretty(v).map_err(Error::Json),
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Yaml(err) => write!(f, "YAML serialization error: {err}"),
+            Self::Json(err) => write!(f, "JSON serialization error: {err}"),
+        }
+    }
+}
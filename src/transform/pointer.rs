@@ -0,0 +1,112 @@
+// SPDX-License-Identifier: MIT
+//
+// JSON Pointer (RFC 6901) over raw document tree
+//
+
+use serde_yaml::Value;
+
+// Escape single reference token: '~' => '~0', '/' => '~1'
+pub fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+// Unescape single reference token: '~1' => '/', '~0' => '~'
+pub fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+// Split pointer to the list of unescaped reference tokens. Pointer is
+// taken from URI fragment so it may be percent-encoded.
+pub fn tokens(pointer: &str) -> Vec<String> {
+    let pointer = percent_decode(pointer);
+    if pointer.is_empty() {
+        vec![]
+    } else {
+        pointer
+            .trim_start_matches('/')
+            .split('/')
+            .map(unescape)
+            .collect()
+    }
+}
+
+// Build pointer from the list of unescaped reference tokens.
+pub fn join<'a>(tokens: impl IntoIterator<Item = &'a str>) -> String {
+    tokens
+        .into_iter()
+        .map(|t| format!("/{}", escape(t)))
+        .collect()
+}
+
+pub fn resolve<'v>(v: &'v Value, pointer: &str) -> Option<&'v Value> {
+    tokens(pointer).iter().try_fold(v, |v, token| match v {
+        Value::Mapping(m) => m.get(token.as_str()).or_else(|| {
+            // Keys like HTTP status codes may be parsed as numbers
+            token
+                .parse::<u64>()
+                .ok()
+                .and_then(|n| m.get(Value::Number(n.into())))
+        }),
+        Value::Sequence(s) => token.parse::<usize>().ok().and_then(|i| s.get(i)),
+        _ => None,
+    })
+}
+
+// Decode percent-encoded octets. Invalid sequences are kept as is.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = if bytes[i] == b'%' {
+            s.get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        } else {
+            None
+        };
+        match decoded {
+            Some(b) => {
+                result.push(b);
+                i += 3;
+            }
+            None => {
+                result.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn escape_unescape() {
+        assert_eq!(escape("/pets/{id}"), "~1pets~1{id}");
+        assert_eq!(unescape("~1pets~1{id}"), "/pets/{id}");
+        assert_eq!(unescape("~01"), "~1");
+    }
+
+    #[test]
+    fn pointer_tokens() {
+        assert!(tokens("").is_empty());
+        assert_eq!(
+            tokens("/components/schemas/Pet"),
+            ["components", "schemas", "Pet"]
+        );
+        assert_eq!(tokens("/paths/~1pets%7Bid%7D"), ["paths", "/pets{id}"]);
+    }
+
+    #[test]
+    fn resolve_in_document() {
+        let doc: Value = serde_yaml::from_str(
+            "paths:\n  /pets:\n    get:\n      responses:\n        200:\n          description: ok\n",
+        )
+        .unwrap();
+        let resp = resolve(&doc, "/paths/~1pets/get/responses/200/description");
+        assert_eq!(resp.and_then(|v| v.as_str()), Some("ok"));
+        assert!(resolve(&doc, "/paths/~1cats").is_none());
+    }
+}
@@ -5,16 +5,25 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct AlwaysFalse {}
 
 pub enum Error {
     NotFalse,
 }
 
+impl Serialize for AlwaysFalse {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_bool(false)
+    }
+}
+
 impl<'de> Deserialize<'de> for AlwaysFalse {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
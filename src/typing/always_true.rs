@@ -5,16 +5,25 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct AlwaysTrue {}
 
 pub enum Error {
     NotTrue,
 }
 
+impl Serialize for AlwaysTrue {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_bool(true)
+    }
+}
+
 impl<'de> Deserialize<'de> for AlwaysTrue {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
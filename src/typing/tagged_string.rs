@@ -5,7 +5,7 @@
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
 pub struct TaggedString<Tag> {
@@ -20,6 +20,10 @@ impl<Tag> TaggedString<Tag> {
             _marker: std::marker::PhantomData,
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.v
+    }
 }
 
 impl<Tag> PartialEq for TaggedString<Tag> {
@@ -51,6 +55,15 @@ impl<Tag> Clone for TaggedString<Tag> {
     }
 }
 
+impl<Tag> Serialize for TaggedString<Tag> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.v)
+    }
+}
+
 impl<'de, Tag> Deserialize<'de> for TaggedString<Tag> {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
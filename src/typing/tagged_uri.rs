@@ -2,10 +2,13 @@
 //
 // Strong-typed URL
 //
+// Value is checked to be URI but kept as written (uriparse would
+// normalize e.g. https://example.com to https://example.com/).
+//
 
 use serde::{
     de::{self, Visitor},
-    Deserialize, Deserializer,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
 pub struct TaggedURI<Tag> {
@@ -14,20 +17,34 @@ pub struct TaggedURI<Tag> {
 }
 
 impl<Tag> TaggedURI<Tag> {
-    fn from_uri(uri: uriparse::URI<'_>) -> Self {
-        Self {
-            v: uri.to_string(),
-            _marker: std::marker::PhantomData,
-        }
+    pub fn as_str(&self) -> &str {
+        &self.v
+    }
+}
+
+impl<Tag> PartialEq for TaggedURI<Tag> {
+    fn eq(&self, other: &Self) -> bool {
+        self.v.eq(&other.v)
     }
 }
 
+impl<Tag> Eq for TaggedURI<Tag> {}
+
 impl<Tag> std::fmt::Debug for TaggedURI<Tag> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.v.fmt(f)
     }
 }
 
+impl<Tag> Serialize for TaggedURI<Tag> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.v)
+    }
+}
+
 impl<'de, Tag> Deserialize<'de> for TaggedURI<Tag> {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
@@ -48,8 +65,11 @@ impl<'de, Tag> Deserialize<'de> for TaggedURI<Tag> {
             where
                 E: de::Error,
             {
-                let uri = uriparse::URI::try_from(value).map_err(de::Error::custom)?;
-                Ok(TaggedURI::<Tag>::from_uri(uri))
+                uriparse::URI::try_from(value).map_err(de::Error::custom)?;
+                Ok(TaggedURI::<Tag> {
+                    v: value.to_string(),
+                    _marker: std::marker::PhantomData,
+                })
             }
         }
 
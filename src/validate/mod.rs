@@ -0,0 +1,633 @@
+// SPDX-License-Identifier: MIT
+//
+// Validation of JSON instances against compiled schemas.
+//
+// Every error refers to the place inside the instance and to the
+// schema keyword inside description that is not satisfied (both are
+// JSON pointers). Keyword "pattern" is not checked.
+//
+
+use crate::compile::constraints::Bound;
+use crate::compile::constraints::Constraints;
+use crate::compile::data_type::CompiledAdditionalProperties;
+use crate::compile::data_type::CompiledObject;
+use crate::compile::data_type::CompiledType;
+use crate::compile::data_type::DataType;
+use crate::compile::data_type::NormalCompiledType;
+use crate::compile::data_type::NullableCompiledType;
+use crate::compile::data_type::TypeOrSchemaRef;
+use crate::compile::operation::request_body::RequestBodyOrReference;
+use crate::compile::operation::response_body::ResponseBodyOrReference;
+use crate::compile::operation::Operation;
+use crate::compile::Compiled;
+use crate::compile::Schemas;
+use crate::schema::data_type::numerical;
+use crate::schema::discriminator::Discriminator;
+use crate::schema::http_status_code::HttpStatusCode;
+use crate::schema::sref::SRefSchemas;
+use crate::schema::sref::SRefSchemasObjectName;
+use crate::transform::pointer;
+use serde_json::Value;
+
+const MAX_DEPTH: u32 = 1024;
+const SCHEMAS: &str = "/components/schemas";
+const JSON_SCHEMA: &str = "/content/application~1json/schema";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Error {
+    // JSON pointer inside the instance
+    pub instance: String,
+    // JSON pointer to the schema keyword inside description
+    pub schema: String,
+    pub kind: ErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorKind {
+    // Expected type
+    Type(&'static str),
+    Enum(String),
+    Required(String),
+    AdditionalProperty(String),
+    MinLength(u64),
+    MaxLength(u64),
+    Minimum(Bound),
+    Maximum(Bound),
+    MultipleOf(f64),
+    // Integer doesn't fit into format
+    Format(&'static str),
+    MinItems(u64),
+    MaxItems(u64),
+    UniqueItems,
+    MinProperties(u64),
+    MaxProperties(u64),
+    // Number of matched schemas of oneOf
+    OneOf(usize),
+    AnyOf,
+    // Discriminator property is missing or is not a string
+    DiscriminatorProperty(String),
+    // Discriminator value doesn't refer to any schema
+    DiscriminatorValue(String),
+    // Schema selected by discriminator is not one of the alternatives
+    DiscriminatorSchema(SRefSchemasObjectName),
+    SchemaNotFound(SRefSchemasObjectName),
+    MaxDepthReached,
+}
+
+pub struct Validator<'a, 'b> {
+    schemas: &'b Schemas<'a>,
+}
+
+// Validate request body of the operation. Result is None if the body
+// has no JSON schema.
+pub fn request_body(compiled: &Compiled, op: &Operation, v: &Value) -> Option<Vec<Error>> {
+    let (at, body) = match op.request_body_or_ref.as_ref()? {
+        RequestBodyOrReference::Body(body) => (format!("{}/requestBody", op.pointer()), body),
+        RequestBodyOrReference::Reference(sref) => (
+            pointer::join(["components", "requestBodies", sref.as_str()]),
+            compiled.request_bodies.get(sref)?,
+        ),
+    };
+    let t = body.json_type_or_ref.as_ref()?;
+    Some(Validator::new(&compiled.schemas).validate(v, t, &format!("{at}{JSON_SCHEMA}")))
+}
+
+// Validate response body of the operation for the status code. Exact
+// code is preferred to the pattern (e.g. 2XX) which is preferred to
+// default response. Result is None if the response has no JSON schema.
+pub fn response_body(
+    compiled: &Compiled,
+    op: &Operation,
+    code: u16,
+    v: &Value,
+) -> Option<Vec<Error>> {
+    let responses = &op.request_responses;
+    let found = |specific: bool| {
+        responses
+            .codes
+            .iter()
+            .find(|(c, _)| matches!(c, HttpStatusCode::Specific(_)) == specific && c.matches(code))
+    };
+    let (at, resp) = match found(true).or_else(|| found(false)) {
+        Some((c, resp)) => (format!("{}/responses/{c}", op.pointer()), resp),
+        None => (
+            format!("{}/responses/default", op.pointer()),
+            responses.default.as_ref()?,
+        ),
+    };
+    let (at, body) = match resp {
+        ResponseBodyOrReference::Body(body) => (at, body),
+        ResponseBodyOrReference::Reference(sref) => (
+            pointer::join(["components", "responses", sref.as_str()]),
+            compiled.response_bodies.get(sref)?,
+        ),
+    };
+    let t = body.json_type_or_ref.as_ref()?;
+    Some(Validator::new(&compiled.schemas).validate(v, t, &format!("{at}{JSON_SCHEMA}")))
+}
+
+impl<'a, 'b> Validator<'a, 'b> {
+    pub fn new(schemas: &'b Schemas<'a>) -> Self {
+        Self { schemas }
+    }
+
+    // Schema is JSON pointer to the data type inside description
+    pub fn validate(&self, v: &Value, t: &TypeOrSchemaRef<'a>, schema: &str) -> Vec<Error> {
+        let mut errors = vec![];
+        let at = At {
+            instance: String::new(),
+            schema: schema.into(),
+        };
+        self.type_or_ref(v, t, &at, &mut errors, 0);
+        errors
+    }
+
+    fn type_or_ref(
+        &self,
+        v: &Value,
+        t: &TypeOrSchemaRef<'a>,
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        match t {
+            TypeOrSchemaRef::DataType(dt) => self.data_type(v, dt, at, errors, depth),
+            TypeOrSchemaRef::Reference(name) => self.reference(v, name, at, errors, depth),
+        }
+    }
+
+    fn reference(
+        &self,
+        v: &Value,
+        name: &SRefSchemasObjectName,
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        match self.schemas.get(name) {
+            Some(dt) => {
+                let at = At {
+                    instance: at.instance.clone(),
+                    schema: format!("{SCHEMAS}/{}", pointer::escape(name.as_str())),
+                };
+                self.data_type(v, dt, &at, errors, depth + 1)
+            }
+            None => errors.push(at.error(ErrorKind::SchemaNotFound(name.clone()))),
+        }
+    }
+
+    fn data_type(
+        &self,
+        v: &Value,
+        dt: &DataType<'a>,
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        if depth > MAX_DEPTH {
+            return errors.push(at.error(ErrorKind::MaxDepthReached));
+        }
+        let matches = |i: usize, keyword: &str, t: &TypeOrSchemaRef<'a>| {
+            let mut errors = vec![];
+            self.type_or_ref(v, t, &at.schema(keyword).index(i), &mut errors, depth + 1);
+            errors.is_empty()
+        };
+        match dt {
+            DataType::ActualType(t) => {
+                self.actual_type(v, &t.compiled_type, &t.constraints, at, errors, depth)
+            }
+            DataType::AllOf(t) => {
+                for (i, t) in t.all_of.iter().enumerate() {
+                    let at = at.schema("allOf").index(i);
+                    self.type_or_ref(v, t, &at, errors, depth + 1);
+                }
+            }
+            DataType::OneOf(t) => match t.discriminator {
+                Some(d) => self.discriminator(v, d, &t.one_of, at, errors, depth),
+                None => {
+                    let n = t
+                        .one_of
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, t)| matches(*i, "oneOf", t))
+                        .count();
+                    if n != 1 {
+                        errors.push(at.schema("oneOf").error(ErrorKind::OneOf(n)));
+                    }
+                }
+            },
+            DataType::AnyOf(t) => match t.discriminator {
+                Some(d) => self.discriminator(v, d, &t.any_of, at, errors, depth),
+                None => {
+                    if !t
+                        .any_of
+                        .iter()
+                        .enumerate()
+                        .any(|(i, t)| matches(i, "anyOf", t))
+                    {
+                        errors.push(at.schema("anyOf").error(ErrorKind::AnyOf));
+                    }
+                }
+            },
+        }
+    }
+
+    // Only the schema selected by discriminator is validated. It must
+    // be one of the alternatives.
+    fn discriminator(
+        &self,
+        v: &Value,
+        d: &Discriminator,
+        alternatives: &[TypeOrSchemaRef<'a>],
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        let property = d.property_name.as_str();
+        let at_d = at.schema("discriminator");
+        let Some(value) = v.get(property).and_then(Value::as_str) else {
+            return errors.push(at_d.error(ErrorKind::DiscriminatorProperty(property.into())));
+        };
+        let mapped = d
+            .mapping
+            .iter()
+            .flatten()
+            .find(|(k, _)| k.as_str() == value);
+        let name = match mapped {
+            Some((_, sref)) => match sref.schemas_sref() {
+                Ok(Some(SRefSchemas::Normal(name))) => name,
+                _ => return errors.push(at_d.error(ErrorKind::DiscriminatorValue(value.into()))),
+            },
+            // Implicit mapping to the schema with the same name
+            None if self
+                .schemas
+                .contains_key(&SRefSchemasObjectName::new(value.into())) =>
+            {
+                SRefSchemasObjectName::new(value.into())
+            }
+            None => return errors.push(at_d.error(ErrorKind::DiscriminatorValue(value.into()))),
+        };
+        if !alternatives
+            .iter()
+            .any(|t| matches!(t, TypeOrSchemaRef::Reference(n) if *n == name))
+        {
+            return errors.push(at_d.error(ErrorKind::DiscriminatorSchema(name)));
+        }
+        self.reference(v, &name, at, errors, depth + 1)
+    }
+
+    fn actual_type(
+        &self,
+        v: &Value,
+        t: &CompiledType<'a>,
+        c: &Constraints,
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        let kind = match t {
+            CompiledType::Nullable(_) if v.is_null() => return,
+            CompiledType::Nullable(t) => match t {
+                NullableCompiledType::Null => Kind::Null,
+                NullableCompiledType::Boolean(_) => Kind::Boolean,
+                NullableCompiledType::Integer(t) => Kind::Integer(nullable_int32(t)),
+                NullableCompiledType::Number(_) => Kind::Number,
+                NullableCompiledType::String(s) => Kind::String(s.enumeration.as_deref()),
+                NullableCompiledType::Object(obj) => Kind::Object(obj),
+                NullableCompiledType::Array(arr) => Kind::Array(&arr.items),
+            },
+            CompiledType::Normal(t) => match t {
+                NormalCompiledType::Boolean(_) => Kind::Boolean,
+                NormalCompiledType::Integer(t) => Kind::Integer(int32(t)),
+                NormalCompiledType::Number(_) => Kind::Number,
+                NormalCompiledType::String(s) => Kind::String(s.enumeration.as_deref()),
+                NormalCompiledType::Object(obj) => Kind::Object(obj),
+                NormalCompiledType::Array(arr) => Kind::Array(&arr.items),
+            },
+        };
+        let mut error = |keyword: &str, kind| errors.push(at.schema(keyword).error(kind));
+        match kind {
+            Kind::Null if !v.is_null() => error("type", ErrorKind::Type("null")),
+            Kind::Boolean if !v.is_boolean() => error("type", ErrorKind::Type("boolean")),
+            Kind::Integer(is_int32) => match integer(v) {
+                None => error("type", ErrorKind::Type("integer")),
+                Some(n) => {
+                    if is_int32 && i32::try_from(n).is_err() {
+                        error("format", ErrorKind::Format("int32"));
+                    }
+                    number(v, c, error)
+                }
+            },
+            Kind::Number if !v.is_number() => error("type", ErrorKind::Type("number")),
+            Kind::Number => number(v, c, error),
+            Kind::String(values) => match v.as_str() {
+                None => error("type", ErrorKind::Type("string")),
+                Some(s) => {
+                    if values.is_some_and(|vs| !vs.iter().flatten().any(|m| m == s)) {
+                        error("enum", ErrorKind::Enum(s.into()));
+                    }
+                    let len = s.chars().count() as u64;
+                    if let Some(min) = c.min_length.filter(|min| len < *min) {
+                        error("minLength", ErrorKind::MinLength(min));
+                    }
+                    if let Some(max) = c.max_length.filter(|max| len > *max) {
+                        error("maxLength", ErrorKind::MaxLength(max));
+                    }
+                }
+            },
+            Kind::Object(obj) => match v.as_object() {
+                None => error("type", ErrorKind::Type("object")),
+                Some(m) => self.object(m, obj, c, at, errors, depth),
+            },
+            Kind::Array(items) => match v.as_array() {
+                None => error("type", ErrorKind::Type("array")),
+                Some(s) => {
+                    let len = s.len() as u64;
+                    if let Some(min) = c.min_items.filter(|min| len < *min) {
+                        error("minItems", ErrorKind::MinItems(min));
+                    }
+                    if let Some(max) = c.max_items.filter(|max| len > *max) {
+                        error("maxItems", ErrorKind::MaxItems(max));
+                    }
+                    let unique = || s.iter().enumerate().all(|(i, v)| !s[..i].contains(v));
+                    if c.unique_items && !unique() {
+                        error("uniqueItems", ErrorKind::UniqueItems);
+                    }
+                    let at_items = at.schema("items");
+                    for (i, v) in s.iter().enumerate() {
+                        let at = at_items.instance(&i.to_string());
+                        self.type_or_ref(v, items, &at, errors, depth + 1);
+                    }
+                }
+            },
+            Kind::Null | Kind::Boolean => {}
+        }
+    }
+
+    fn object(
+        &self,
+        m: &serde_json::Map<String, Value>,
+        obj: &CompiledObject<'a>,
+        c: &Constraints,
+        at: &At,
+        errors: &mut Vec<Error>,
+        depth: u32,
+    ) {
+        let len = m.len() as u64;
+        if let Some(min) = c.min_properties.filter(|min| len < *min) {
+            errors.push(
+                at.schema("minProperties")
+                    .error(ErrorKind::MinProperties(min)),
+            );
+        }
+        if let Some(max) = c.max_properties.filter(|max| len > *max) {
+            errors.push(
+                at.schema("maxProperties")
+                    .error(ErrorKind::MaxProperties(max)),
+            );
+        }
+        for name in obj.required.iter() {
+            if !m.contains_key(name.as_str()) {
+                let kind = ErrorKind::Required(name.as_str().into());
+                errors.push(at.schema("required").error(kind));
+            }
+        }
+        for (name, t) in obj.properties.iter() {
+            if let Some(v) = m.get(name.as_str()) {
+                let at = at
+                    .schema("properties")
+                    .schema(name.as_str())
+                    .instance(name.as_str());
+                self.type_or_ref(v, t, &at, errors, depth + 1);
+            }
+        }
+        let defined = |name: &str| obj.properties.keys().any(|p| p.as_str() == name);
+        for (name, v) in m.iter().filter(|(name, _)| !defined(name)) {
+            match &obj.additional_properties {
+                None | Some(CompiledAdditionalProperties::Bool(true)) => {}
+                Some(CompiledAdditionalProperties::Bool(false)) => {
+                    let kind = ErrorKind::AdditionalProperty(name.clone());
+                    errors.push(at.schema("additionalProperties").error(kind));
+                }
+                Some(CompiledAdditionalProperties::Schema(t)) => {
+                    let at = at.schema("additionalProperties").instance(name);
+                    self.type_or_ref(v, t, &at, errors, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+// Value is checked to be a number already
+fn number(v: &Value, c: &Constraints, mut error: impl FnMut(&str, ErrorKind)) {
+    let Some(n) = v.as_f64() else {
+        return;
+    };
+    if let Some(min) = c.minimum {
+        if n < min.value || (min.exclusive && n == min.value) {
+            error("minimum", ErrorKind::Minimum(min));
+        }
+    }
+    if let Some(max) = c.maximum {
+        if n > max.value || (max.exclusive && n == max.value) {
+            error("maximum", ErrorKind::Maximum(max));
+        }
+    }
+    if let Some(multiple_of) = c.multiple_of {
+        let q = n / multiple_of;
+        if (q - q.round()).abs() > f64::EPSILON * q.abs().max(1.0) {
+            error("multipleOf", ErrorKind::MultipleOf(multiple_of));
+        }
+    }
+}
+
+// Numbers with zero fractional part are integers too
+fn integer(v: &Value) -> Option<i128> {
+    v.as_i64()
+        .map(i128::from)
+        .or_else(|| v.as_u64().map(i128::from))
+        .or_else(|| {
+            v.as_f64()
+                .filter(|f| f.fract() == 0.0 && f.is_finite())
+                .map(|f| f as i128)
+        })
+}
+
+fn int32(t: &numerical::IntegerType) -> bool {
+    matches!(
+        t,
+        numerical::IntegerType::WithFormat(numerical::IntegerWithFormat::Int32(_))
+    )
+}
+
+fn nullable_int32(t: &numerical::NullableIntegerType) -> bool {
+    matches!(
+        t,
+        numerical::NullableIntegerType::WithFormat(numerical::NullableIntegerWithFormat::Int32(_))
+    )
+}
+
+enum Kind<'a, 'b> {
+    Null,
+    Boolean,
+    // Format is int32
+    Integer(bool),
+    Number,
+    String(Option<&'b [Option<String>]>),
+    Object(&'b CompiledObject<'a>),
+    Array(&'b TypeOrSchemaRef<'a>),
+}
+
+// Current position inside instance and description
+#[derive(Clone)]
+struct At {
+    instance: String,
+    schema: String,
+}
+
+impl At {
+    fn instance(&self, token: &str) -> Self {
+        Self {
+            instance: format!("{}/{}", self.instance, pointer::escape(token)),
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn schema(&self, token: &str) -> Self {
+        Self {
+            instance: self.instance.clone(),
+            schema: format!("{}/{}", self.schema, pointer::escape(token)),
+        }
+    }
+
+    fn index(&self, i: usize) -> Self {
+        self.schema(&i.to_string())
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            instance: self.instance.clone(),
+            schema: self.schema.clone(),
+            kind,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.instance.is_empty() {
+            write!(f, "{}: ", self.instance)?;
+        }
+        write!(f, "{} (schema {})", self.kind, self.schema)
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bound = |b: &Bound| if b.exclusive { " (exclusive)" } else { "" };
+        match self {
+            Self::Type(t) => write!(f, "expected {t}"),
+            Self::Enum(v) => write!(f, "value is not in enum: {v}"),
+            Self::Required(name) => write!(f, "required property is missing: {name}"),
+            Self::AdditionalProperty(name) => write!(f, "property is not allowed: {name}"),
+            Self::MinLength(v) => write!(f, "string is shorter than {v}"),
+            Self::MaxLength(v) => write!(f, "string is longer than {v}"),
+            Self::Minimum(b) => write!(f, "value is less than minimum {}{}", b.value, bound(b)),
+            Self::Maximum(b) => {
+                write!(f, "value is greater than maximum {}{}", b.value, bound(b))
+            }
+            Self::MultipleOf(v) => write!(f, "value is not multiple of {v}"),
+            Self::Format(format) => write!(f, "value doesn't fit into {format}"),
+            Self::MinItems(v) => write!(f, "array has less than {v} items"),
+            Self::MaxItems(v) => write!(f, "array has more than {v} items"),
+            Self::UniqueItems => write!(f, "array items are not unique"),
+            Self::MinProperties(v) => write!(f, "object has less than {v} properties"),
+            Self::MaxProperties(v) => write!(f, "object has more than {v} properties"),
+            Self::OneOf(n) => write!(f, "matches {n} schemas of oneOf"),
+            Self::AnyOf => write!(f, "matches no schema of anyOf"),
+            Self::DiscriminatorProperty(name) => {
+                write!(
+                    f,
+                    "discriminator property is missing or not a string: {name}"
+                )
+            }
+            Self::DiscriminatorValue(v) => write!(f, "unknown discriminator value: {v}"),
+            Self::DiscriminatorSchema(name) => write!(
+                f,
+                "schema selected by discriminator is not an alternative: {}",
+                name.as_str()
+            ),
+            Self::SchemaNotFound(name) => write!(f, "schema not found: {}", name.as_str()),
+            Self::MaxDepthReached => write!(f, "maximum depth reached"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::compile::compile;
+    use crate::schema::Description;
+    use serde_json::json;
+
+    #[test]
+    fn validate_payloads() {
+        let spec = include_str!("../../test/test-vectors/validate.yaml");
+        let description: Description = serde_yaml::from_str(spec).unwrap();
+        let compiled = compile(&description).unwrap();
+        let op = &compiled.operations[0];
+        let request = |v: Value| -> Vec<String> {
+            let errors = request_body(&compiled, op, &v).unwrap();
+            errors.iter().map(|err| err.to_string()).collect()
+        };
+
+        assert!(request(json!({"kind": "Cat", "name": "Tom", "lives": 9})).is_empty());
+        assert_eq!(
+            request(json!({"kind": "Cat", "name": "", "lives": 10, "age": 3})),
+            [
+                "/name: string is shorter than 1 (schema /components/schemas/Cat/properties/name/minLength)",
+                "/lives: value is greater than maximum 9 (schema /components/schemas/Cat/properties/lives/maximum)",
+                "property is not allowed: age (schema /components/schemas/Cat/additionalProperties)",
+            ]
+        );
+        assert_eq!(
+            request(json!({"kind": "Cat", "lives": 3_000_000_000u64})),
+            [
+                "required property is missing: name (schema /components/schemas/Cat/required)",
+                "/lives: value doesn't fit into int32 (schema /components/schemas/Cat/properties/lives/format)",
+                "/lives: value is greater than maximum 9 (schema /components/schemas/Cat/properties/lives/maximum)",
+            ]
+        );
+        assert!(request(json!({"kind": "dog", "owner": null, "weight": 7.5, "age": 3})).is_empty());
+        assert_eq!(
+            request(json!({"kind": "dog", "size": "huge", "tags": ["a", "a", "b"], "weight": 7.2, "age": "old"})),
+            [
+                "/size: value is not in enum: huge (schema /components/schemas/Dog/properties/size/enum)",
+                "/tags: array has more than 2 items (schema /components/schemas/Dog/properties/tags/maxItems)",
+                "/tags: array items are not unique (schema /components/schemas/Dog/properties/tags/uniqueItems)",
+                "/weight: value is not multiple of 0.5 (schema /components/schemas/Dog/properties/weight/multipleOf)",
+                "/age: expected integer (schema /components/schemas/Dog/additionalProperties/type)",
+            ]
+        );
+        assert_eq!(
+            request(json!({"kind": "Bird"})),
+            ["unknown discriminator value: Bird (schema /components/schemas/Pet/discriminator)"]
+        );
+        assert_eq!(
+            request(json!({"kind": "Error"})),
+            ["schema selected by discriminator is not an alternative: Error (schema /components/schemas/Pet/discriminator)"]
+        );
+
+        let response =
+            |code, v: Value| -> Vec<Error> { response_body(&compiled, op, code, &v).unwrap() };
+        assert!(response(404, json!("not found")).is_empty());
+        let errors = response(400, json!({"code": 1}));
+        assert_eq!(errors[0].kind, ErrorKind::AnyOf);
+        assert_eq!(errors[0].schema, "/components/schemas/Error/anyOf");
+        assert_eq!(
+            response(201, json!([]))[0].kind,
+            ErrorKind::DiscriminatorProperty("kind".into())
+        );
+        assert!(response_body(&compiled, op, 500, &json!(null)).is_none());
+    }
+}
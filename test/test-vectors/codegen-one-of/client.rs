@@ -0,0 +1,232 @@
+// Generated by openapirs. Do not edit.
+
+#![allow(clippy::all, deprecated, dead_code, unused_imports)]
+
+use super::operations::*;
+use super::types::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+pub struct Client<T> {
+    base_url: String,
+    transport: T,
+}
+
+impl<T: Transport> Client<T> {
+    pub fn new(base_url: impl Into<String>, transport: T) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            base_url,
+            transport,
+        }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// POST /test
+    pub async fn post_test(&self, body: &PostTestRequestBody) -> Result<PostTestResponse, Error<T::Error>> {
+        let url = format!("{}/test", self.base_url);
+        let mut request_headers: Vec<(String, String)> = vec![];
+        request_headers.push(("Content-Type".into(), "application/json".into()));
+        let request_body = Some(serde_json::to_vec(body).map_err(Error::Serialize)?);
+        let request = Request {
+            method: "POST",
+            url,
+            headers: request_headers,
+            body: request_body,
+        };
+        let response = self.transport.send(request).await.map_err(Error::Transport)?;
+        match response.status {
+            _ => Err(Error::UnexpectedStatus(response)),
+        }
+    }
+}
+
+// Request to be sent by transport. URL includes base URL of the
+// client, path and query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+// Sender of requests, e.g. reqwest or hyper client or test double
+pub trait Transport {
+    type Error;
+
+    fn send(
+        &self,
+        request: Request,
+    ) -> impl std::future::Future<Output = Result<Response, Self::Error>> + Send;
+}
+
+#[derive(Debug)]
+pub enum Error<E> {
+    Transport(E),
+    // Parameter or request body can't be serialized
+    Serialize(serde_json::Error),
+    // Response body doesn't match the type of the response
+    Deserialize(u16, serde_json::Error),
+    // Status is not described by the operation
+    UnexpectedStatus(Response),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Transport(err) => write!(f, "transport error: {err}"),
+            Self::Serialize(err) => write!(f, "serialization error: {err}"),
+            Self::Deserialize(status, err) => write!(f, "response {status}: {err}"),
+            Self::UnexpectedStatus(response) => {
+                write!(f, "unexpected response status {}", response.status)
+            }
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for Error<E> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+fn value<E>(v: &impl Serialize) -> Result<Value, Error<E>> {
+    serde_json::to_value(v).map_err(Error::Serialize)
+}
+
+// Value of the parameter with JSON content
+fn json_value<E>(v: &impl Serialize) -> Result<Value, Error<E>> {
+    match value(v)? {
+        Value::Null => Ok(Value::Null),
+        v => Ok(Value::String(v.to_string())),
+    }
+}
+
+// Absent parameters are null or empty arrays (optional arrays default
+// to empty)
+fn is_absent(v: &Value) -> bool {
+    v.is_null() || v.as_array().is_some_and(Vec::is_empty)
+}
+
+fn scalar(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+// Percent-encode all but unreserved characters
+fn encode(s: &str) -> String {
+    let mut result = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                result.push(b as char)
+            }
+            b => result.push_str(&format!("%{b:02X}")),
+        }
+    }
+    result
+}
+
+// Path (simple, label or matrix style) or header (simple style)
+// parameter
+fn simple(name: &str, style: Style, explode: bool, v: &Value, enc: fn(&str) -> String) -> String {
+    let (prefix, separator) = match style {
+        Style::Label => (".".to_string(), if explode { "." } else { "," }),
+        Style::Matrix => (format!(";{name}="), if explode { ";" } else { "," }),
+        _ => (String::new(), ","),
+    };
+    match v {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(|v| enc(&scalar(v))).collect();
+            match style {
+                Style::Matrix if explode => items.iter().map(|v| format!(";{name}={v}")).collect(),
+                _ => prefix + &items.join(separator),
+            }
+        }
+        Value::Object(map) => {
+            let pairs = map.iter().map(|(k, v)| (enc(k), enc(&scalar(v))));
+            match style {
+                Style::Matrix if explode => pairs.map(|(k, v)| format!(";{k}={v}")).collect(),
+                _ if explode => {
+                    let pairs: Vec<String> = pairs.map(|(k, v)| format!("{k}={v}")).collect();
+                    prefix + &pairs.join(separator)
+                }
+                _ => {
+                    let items: Vec<String> = pairs.flat_map(|(k, v)| [k, v]).collect();
+                    prefix + &items.join(",")
+                }
+            }
+        }
+        v => prefix + &enc(&scalar(v)),
+    }
+}
+
+// Query (form, spaceDelimited, pipeDelimited or deepObject style) or
+// cookie (form style) parameter as name and value pairs. Names and
+// items are percent-encoded, delimiters of the items are not.
+fn pairs(name: &str, style: Style, explode: bool, v: &Value) -> Vec<(String, String)> {
+    let pair = |k: &str, v: &Value| (encode(k), encode(&scalar(v)));
+    match v {
+        v if is_absent(v) => vec![],
+        Value::Array(items) if explode && style == Style::Form => {
+            items.iter().map(|v| pair(name, v)).collect()
+        }
+        Value::Array(items) => {
+            let separator = match style {
+                Style::SpaceDelimited => "%20",
+                Style::PipeDelimited => "|",
+                _ => ",",
+            };
+            let items: Vec<String> = items.iter().map(|v| encode(&scalar(v))).collect();
+            vec![(encode(name), items.join(separator))]
+        }
+        Value::Object(map) if style == Style::DeepObject => map
+            .iter()
+            .map(|(k, v)| pair(&format!("{name}[{k}]"), v))
+            .collect(),
+        Value::Object(map) if explode => map.iter().map(|(k, v)| pair(k, v)).collect(),
+        Value::Object(map) => {
+            let items: Vec<String> = map
+                .iter()
+                .flat_map(|(k, v)| [encode(k), encode(&scalar(v))])
+                .collect();
+            vec![(encode(name), items.join(","))]
+        }
+        v => vec![pair(name, v)],
+    }
+}
+
+// Query of the encoded pairs
+fn query_string(pairs: &[(String, String)]) -> String {
+    let pairs: Vec<String> = pairs.iter().map(|(k, v)| format!("{k}={v}")).collect();
+    match pairs.is_empty() {
+        true => String::new(),
+        false => format!("?{}", pairs.join("&")),
+    }
+}
+
+fn json<T: DeserializeOwned, E>(response: &Response) -> Result<T, Error<E>> {
+    serde_json::from_slice(&response.body).map_err(|err| Error::Deserialize(response.status, err))
+}
@@ -0,0 +1,5 @@
+// Generated by openapirs. Do not edit.
+
+pub mod client;
+pub mod operations;
+pub mod types;
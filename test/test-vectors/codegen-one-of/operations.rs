@@ -0,0 +1,16 @@
+// Generated by openapirs. Do not edit.
+
+#![allow(unused_imports)]
+
+use super::types::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PostTestResponse {
+}
+
+impl PostTestResponse {
+    pub fn is_success(&self) -> bool {
+        match *self {
+        }
+    }
+}
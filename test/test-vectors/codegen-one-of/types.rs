@@ -0,0 +1,23 @@
+// Generated by openapirs. Do not edit.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PostTestRequestBody {
+    PostTestRequestBodyVariant1(PostTestRequestBodyVariant1),
+    PostTestRequestBodyVariant2(PostTestRequestBodyVariant2),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostTestRequestBodyVariant1 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prop1: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostTestRequestBodyVariant2 {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prop2: Option<String>,
+}
@@ -0,0 +1,584 @@
+// Generated by openapirs. Do not edit.
+
+#![allow(clippy::all, deprecated, dead_code, unused_imports, unused_variables)]
+
+use super::operations::*;
+use super::types::*;
+use axum::body::Bytes;
+use axum::extract::Path;
+use axum::http::header::CONTENT_TYPE;
+use axum::http::header::COOKIE;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::Uri;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetFileRequest {
+    pub path: GetFilePath,
+}
+
+impl IntoResponse for GetFileResponse {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Ok200(body) => json_response(200, &body),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetTypedFileRequest {
+    pub path: GetTypedFilePath,
+}
+
+impl IntoResponse for GetTypedFileResponse {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Ok200(body) => json_response(200, &body),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetCsvFileRequest {
+    pub path: GetCsvFilePath,
+}
+
+impl IntoResponse for GetCsvFileResponse {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Ok200(body) => json_response(200, &body),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PutCsvFileRequest {
+    pub path: PutCsvFilePath,
+}
+
+impl IntoResponse for PutCsvFileResponse {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Ok200(body) => json_response(200, &body),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetLatestFileRequest {
+}
+
+impl IntoResponse for GetLatestFileResponse {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Ok200(body) => json_response(200, &body),
+        }
+    }
+}
+
+pub trait Api: Send + Sync + 'static {
+    /// GET /files/{name}
+    fn get_file(&self, request: GetFileRequest) -> impl Future<Output = GetFileResponse> + Send;
+
+    /// GET /files/{name}.{ext}
+    fn get_typed_file(&self, request: GetTypedFileRequest) -> impl Future<Output = GetTypedFileResponse> + Send;
+
+    /// GET /files/{name}.csv
+    fn get_csv_file(&self, request: GetCsvFileRequest) -> impl Future<Output = GetCsvFileResponse> + Send;
+
+    /// PUT /files/{name}.csv
+    fn put_csv_file(&self, request: PutCsvFileRequest) -> impl Future<Output = PutCsvFileResponse> + Send;
+
+    /// GET /files/latest
+    fn get_latest_file(&self, request: GetLatestFileRequest) -> impl Future<Output = GetLatestFileResponse> + Send;
+}
+
+// Router serving operations of the service
+pub fn router<S: Api>(service: S) -> axum::Router {
+    let service = Arc::new(service);
+    axum::Router::new()
+        .route(
+            "/files/{p2}",
+            axum::routing::get({
+                let service = service.clone();
+                move |Path(params): Path<HashMap<String, String>>, uri: Uri, headers: HeaderMap, body: Bytes| async move {
+                    if let Some(response) = handle_get_csv_file(&*service, &params, &uri, &headers, &body).await {
+                        return response;
+                    }
+                    if let Some(response) = handle_get_typed_file(&*service, &params, &uri, &headers, &body).await {
+                        return response;
+                    }
+                    if let Some(response) = handle_get_file(&*service, &params, &uri, &headers, &body).await {
+                        return response;
+                    }
+                    not_found()
+                }
+            })
+            .put({
+                let service = service.clone();
+                move |Path(params): Path<HashMap<String, String>>, uri: Uri, headers: HeaderMap, body: Bytes| async move {
+                    if let Some(response) = handle_put_csv_file(&*service, &params, &uri, &headers, &body).await {
+                        return response;
+                    }
+                    not_found()
+                }
+            }),
+        )
+        .route(
+            "/files/latest",
+            axum::routing::get({
+                let service = service.clone();
+                move |uri: Uri, headers: HeaderMap, body: Bytes| async move {
+                    if let Some(response) = handle_get_latest_file(&*service, &HashMap::new(), &uri, &headers, &body).await {
+                        return response;
+                    }
+                    not_found()
+                }
+            }),
+        )
+}
+
+async fn handle_get_file<S: Api>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {
+    let mut errors = vec![];
+    let v0 = check(&mut errors, "path", "name", true, simple_value(params.get("p2").map(String::as_str), "name", Style::Simple, false, &Shape::Scalar(Kind::String)));
+    let (Some(v0),) = (v0,) else {
+        return Some(bad_request(errors));
+    };
+    let request = GetFileRequest {
+        path: GetFilePath { name: v0 },
+    };
+    Some(service.get_file(request).await.into_response())
+}
+
+async fn handle_get_typed_file<S: Api>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {
+    let Some(s2) = split_segment(params.get("p2").map(String::as_str), &[Part::Param, Part::Literal("."), Part::Param]) else {
+        return None;
+    };
+    let mut errors = vec![];
+    let v0 = check(&mut errors, "path", "name", true, simple_value(s2.get(0).map(String::as_str), "name", Style::Simple, false, &Shape::Scalar(Kind::String)));
+    let v1 = check(&mut errors, "path", "ext", true, simple_value(s2.get(1).map(String::as_str), "ext", Style::Simple, false, &Shape::Scalar(Kind::String)));
+    let (Some(v0), Some(v1)) = (v0, v1) else {
+        return Some(bad_request(errors));
+    };
+    let request = GetTypedFileRequest {
+        path: GetTypedFilePath { name: v0, ext: v1 },
+    };
+    Some(service.get_typed_file(request).await.into_response())
+}
+
+async fn handle_get_csv_file<S: Api>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {
+    let Some(s2) = split_segment(params.get("p2").map(String::as_str), &[Part::Param, Part::Literal(".csv")]) else {
+        return None;
+    };
+    let mut errors = vec![];
+    let v0 = check(&mut errors, "path", "name", true, simple_value(s2.get(0).map(String::as_str), "name", Style::Simple, false, &Shape::Scalar(Kind::String)));
+    let (Some(v0),) = (v0,) else {
+        return Some(bad_request(errors));
+    };
+    let request = GetCsvFileRequest {
+        path: GetCsvFilePath { name: v0 },
+    };
+    Some(service.get_csv_file(request).await.into_response())
+}
+
+async fn handle_put_csv_file<S: Api>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {
+    let Some(s2) = split_segment(params.get("p2").map(String::as_str), &[Part::Param, Part::Literal(".csv")]) else {
+        return None;
+    };
+    let mut errors = vec![];
+    let v0 = check(&mut errors, "path", "name", true, simple_value(s2.get(0).map(String::as_str), "name", Style::Simple, false, &Shape::Scalar(Kind::String)));
+    let (Some(v0),) = (v0,) else {
+        return Some(bad_request(errors));
+    };
+    let request = PutCsvFileRequest {
+        path: PutCsvFilePath { name: v0 },
+    };
+    Some(service.put_csv_file(request).await.into_response())
+}
+
+async fn handle_get_latest_file<S: Api>(service: &S, params: &HashMap<String, String>, uri: &Uri, headers: &HeaderMap, body: &Bytes) -> Option<Response> {
+    let request = GetLatestFileRequest {
+    };
+    Some(service.get_latest_file(request).await.into_response())
+}
+
+// Invalid parameter or body of the request
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    // path, query, header, cookie or body
+    #[serde(rename = "in")]
+    pub place: &'static str,
+    pub name: &'static str,
+    pub message: String,
+}
+
+fn bad_request(errors: Vec<ValidationError>) -> Response {
+    let body = serde_json::json!({ "title": "Bad request", "errors": errors });
+    json_response(400, &body)
+}
+
+fn not_found() -> Response {
+    StatusCode::NOT_FOUND.into_response()
+}
+
+fn status(code: u16) -> StatusCode {
+    StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+fn empty_response(code: u16) -> Response {
+    status(code).into_response()
+}
+
+fn json_response(code: u16, body: &impl Serialize) -> Response {
+    match serde_json::to_vec(body) {
+        Ok(body) => (status(code), [(CONTENT_TYPE, "application/json")], body).into_response(),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
+
+fn bytes_response(code: u16, content_type: &'static str, body: Vec<u8>) -> Response {
+    (status(code), [(CONTENT_TYPE, content_type)], body).into_response()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Matrix,
+    Label,
+    Form,
+    Simple,
+    SpaceDelimited,
+    PipeDelimited,
+    DeepObject,
+}
+
+// Type of the raw value. Raw values are converted to JSON values of
+// the type and then deserialized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    // JSON if raw value is valid JSON, string otherwise
+    Json,
+}
+
+// Value of the parameter: scalar, array or object of scalars (with
+// kinds of known properties)
+enum Shape {
+    Scalar(Kind),
+    Array(Kind),
+    Object(&'static [(&'static str, Kind)]),
+}
+
+// Part of the path segment with several parameters
+enum Part {
+    Literal(&'static str),
+    Param,
+}
+
+// Deserialize value. Error is added if value is invalid or required
+// value is missing.
+// Value of the absent parameter is the default one
+fn with_default(value: Result<Value, String>, default: &str) -> Result<Value, String> {
+    match value {
+        Ok(Value::Null) => serde_json::from_str(default).map_err(|err| err.to_string()),
+        value => value,
+    }
+}
+
+fn check<T: DeserializeOwned>(
+    errors: &mut Vec<ValidationError>,
+    place: &'static str,
+    name: &'static str,
+    required: bool,
+    value: Result<Value, String>,
+) -> Option<T> {
+    let message = match value {
+        Ok(Value::Null) if required => "required".to_string(),
+        Ok(v) => match serde_json::from_value(v) {
+            Ok(v) => return Some(v),
+            Err(err) => err.to_string(),
+        },
+        Err(message) => message,
+    };
+    errors.push(ValidationError {
+        place,
+        name,
+        message,
+    });
+    None
+}
+
+fn scalar(raw: &str, kind: Kind) -> Result<Value, String> {
+    match kind {
+        Kind::String => Ok(Value::String(raw.into())),
+        Kind::Integer => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| raw.parse::<u64>().map(Value::from))
+            .map_err(|_| format!("invalid integer: {raw}")),
+        Kind::Number => raw
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("invalid number: {raw}")),
+        Kind::Boolean => raw
+            .parse::<bool>()
+            .map(Value::Bool)
+            .map_err(|_| format!("invalid boolean: {raw}")),
+        Kind::Json => Ok(serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.into()))),
+    }
+}
+
+fn object(pairs: &[(&str, &str)], fields: &[(&str, Kind)]) -> Result<Value, String> {
+    let mut object = serde_json::Map::new();
+    for (k, v) in pairs {
+        let kind = fields
+            .iter()
+            .find(|(name, _)| name == k)
+            .map_or(Kind::Json, |(_, kind)| *kind);
+        object.insert(k.to_string(), scalar(v, kind)?);
+    }
+    Ok(Value::Object(object))
+}
+
+// Value of the shape from items separated by separator. Object is
+// key=value items if exploded, alternating keys and values otherwise.
+fn shaped(raw: &str, separator: char, explode: bool, shape: &Shape) -> Result<Value, String> {
+    let items: Vec<&str> = match raw.is_empty() {
+        true => vec![],
+        false => raw.split(separator).collect(),
+    };
+    match shape {
+        Shape::Scalar(kind) => scalar(raw, *kind),
+        Shape::Array(kind) => items
+            .iter()
+            .map(|v| scalar(v, *kind))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Shape::Object(fields) => {
+            let pairs: Vec<(&str, &str)> = match explode {
+                true => items.iter().map(|v| v.split_once('=').unwrap_or((v, ""))).collect(),
+                false => items
+                    .chunks(2)
+                    .map(|v| (v[0], v.get(1).copied().unwrap_or_default()))
+                    .collect(),
+            };
+            object(&pairs, fields)
+        }
+    }
+}
+
+// Path (simple, label or matrix style) or header (simple style)
+// parameter
+fn simple_value(
+    raw: Option<&str>,
+    name: &str,
+    style: Style,
+    explode: bool,
+    shape: &Shape,
+) -> Result<Value, String> {
+    let Some(raw) = raw else {
+        return Ok(Value::Null);
+    };
+    match style {
+        Style::Label => {
+            let raw = raw.strip_prefix('.').ok_or("label style expected")?;
+            shaped(raw, if explode { '.' } else { ',' }, explode, shape)
+        }
+        Style::Matrix => {
+            let raw = raw.strip_prefix(';').ok_or("matrix style expected")?;
+            let pairs: Vec<(&str, &str)> = raw
+                .split(';')
+                .map(|v| v.split_once('=').unwrap_or((v, "")))
+                .collect();
+            match shape {
+                Shape::Object(fields) if explode => object(&pairs, fields),
+                Shape::Array(kind) if explode => pairs
+                    .iter()
+                    .filter(|(k, _)| *k == name)
+                    .map(|(_, v)| scalar(v, *kind))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Value::Array),
+                _ => match pairs.iter().find(|(k, _)| *k == name) {
+                    Some((_, v)) => shaped(v, ',', false, shape),
+                    None => Err(format!("{name} expected")),
+                },
+            }
+        }
+        _ => shaped(raw, ',', explode, shape),
+    }
+}
+
+// Query (form, spaceDelimited, pipeDelimited or deepObject style) or
+// cookie (form style) parameter
+fn form_value(
+    pairs: &[(String, String)],
+    name: &str,
+    style: Style,
+    explode: bool,
+    shape: &Shape,
+) -> Result<Value, String> {
+    let values: Vec<&str> = pairs
+        .iter()
+        .filter(|(k, _)| k == name)
+        .map(|(_, v)| v.as_str())
+        .collect();
+    match shape {
+        Shape::Object(fields) if explode || style == Style::DeepObject => {
+            let pairs: Vec<(&str, &str)> = pairs
+                .iter()
+                .filter_map(|(k, v)| {
+                    let key = match style {
+                        Style::DeepObject => k
+                            .strip_prefix(name)?
+                            .strip_prefix('[')?
+                            .strip_suffix(']')?,
+                        _ => fields.iter().find(|(f, _)| f == k)?.0,
+                    };
+                    Some((key, v.as_str()))
+                })
+                .collect();
+            match pairs.is_empty() {
+                true => Ok(Value::Null),
+                false => object(&pairs, fields),
+            }
+        }
+        Shape::Array(kind) if explode && style == Style::Form => match values.is_empty() {
+            true => Ok(Value::Null),
+            false => values
+                .iter()
+                .map(|v| scalar(v, *kind))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+        },
+        _ => {
+            let separator = match style {
+                Style::SpaceDelimited => ' ',
+                Style::PipeDelimited => '|',
+                _ => ',',
+            };
+            match values.first() {
+                Some(v) => shaped(v, separator, false, shape),
+                None => Ok(Value::Null),
+            }
+        }
+    }
+}
+
+// Value of the parameter with JSON content
+fn json_value(raw: Option<&str>) -> Result<Value, String> {
+    match raw {
+        Some(raw) => serde_json::from_str(raw).map_err(|err| err.to_string()),
+        None => Ok(Value::Null),
+    }
+}
+
+fn json_body(body: &Bytes) -> Result<Value, String> {
+    match body.is_empty() {
+        true => Ok(Value::Null),
+        false => serde_json::from_slice(body).map_err(|err| err.to_string()),
+    }
+}
+
+fn bytes_body(errors: &mut Vec<ValidationError>, body: &Bytes) -> Option<Vec<u8>> {
+    if body.is_empty() {
+        errors.push(ValidationError {
+            place: "body",
+            name: "",
+            message: "required".into(),
+        });
+        return None;
+    }
+    Some(body.to_vec())
+}
+
+fn header<'h>(headers: &'h HeaderMap, name: &str) -> Option<&'h str> {
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn first<'p>(pairs: &'p [(String, String)], name: &str) -> Option<&'p str> {
+    pairs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+}
+
+// Query value (plus is space)
+fn decode(s: &str) -> String {
+    percent_decode(&s.replace('+', " "))
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut result = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = s.get(i + 1..i + 3).and_then(|v| u8::from_str_radix(v, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                result.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&result).into_owned()
+}
+
+fn query_pairs(query: Option<&str>) -> Vec<(String, String)> {
+    query
+        .unwrap_or_default()
+        .split('&')
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            let (k, v) = v.split_once('=').unwrap_or((v, ""));
+            (decode(k), decode(v))
+        })
+        .collect()
+}
+
+fn cookie_pairs(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|v| v.trim().split_once('='))
+        .map(|(k, v)| (percent_decode(k), percent_decode(v)))
+        .collect()
+}
+
+// Values of the parameters of the path segment with several
+// parameters (e.g. {name}.{ext}). Parameters are matched greedily.
+fn split_segment(segment: Option<&str>, parts: &[Part]) -> Option<Vec<String>> {
+    let segment = segment?;
+    match parts {
+        [] => segment.is_empty().then(Vec::new),
+        [Part::Literal(s), rest @ ..] => split_segment(segment.strip_prefix(s), rest),
+        [Part::Param] => (!segment.is_empty()).then(|| vec![segment.to_string()]),
+        [Part::Param, rest @ ..] => {
+            let [Part::Literal(s), ..] = rest else {
+                return None;
+            };
+            segment
+                .rmatch_indices(s)
+                .filter(|(i, _)| *i > 0)
+                .find_map(|(i, _)| {
+                    let mut values = split_segment(Some(&segment[i..]), rest)?;
+                    values.insert(0, segment[..i].to_string());
+                    Some(values)
+                })
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Generated by openapirs. Do not edit.
+
+#![allow(deprecated)]
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// See <https://example.com/docs/tags>
+#[deprecated]
+pub type Tag = String;
+
+/// New pet
+///
+/// Pet to be added to the store.
+///
+/// Identifier is assigned by the store.
+///
+/// See [Pets guide](https://example.com/docs/pets)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewPet {
+    /// Name of the pet
+    pub name: String,
+    pub r#type: NewPetType,
+    #[deprecated]
+    #[serde(rename = "birthDate", default, skip_serializing_if = "Option::is_none")]
+    pub birth_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<Tag>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<NewPetOwner>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NewPetType {
+    #[serde(rename = "cat")]
+    Cat,
+    #[serde(rename = "dog")]
+    Dog,
+    #[serde(rename = "guinea-pig")]
+    GuineaPig,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewPetOwner {
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Details {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<i32>,
+    #[serde(rename = "self", default, skip_serializing_if = "Option::is_none")]
+    pub self_: Option<String>,
+}
+
+/// Error of the request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Problem {
+    String(String),
+    Details(Details),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pet {
+    #[serde(flatten)]
+    pub new_pet: NewPet,
+    #[serde(flatten)]
+    pub pet_variant2: PetVariant2,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PetVariant2 {
+    pub id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default = "Settings::default_theme")]
+    pub theme: SettingsTheme,
+    #[serde(rename = "pageSize", default = "Settings::default_page_size")]
+    pub page_size: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(default = "Settings::default_tags")]
+    pub tags: Vec<String>,
+    #[serde(default = "Settings::default_notifications")]
+    pub notifications: SettingsNotifications,
+}
+
+impl Settings {
+    fn default_theme() -> SettingsTheme {
+        SettingsTheme::Dark
+    }
+    fn default_page_size() -> i32 {
+        20
+    }
+    fn default_tags() -> Vec<String> {
+        vec!["new".into()]
+    }
+    fn default_notifications() -> SettingsNotifications {
+        SettingsNotifications { email: false }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            theme: Self::default_theme(),
+            page_size: Self::default_page_size(),
+            nickname: None,
+            tags: Self::default_tags(),
+            notifications: Self::default_notifications(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SettingsTheme {
+    #[serde(rename = "light")]
+    Light,
+    #[default]
+    #[serde(rename = "dark")]
+    Dark,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingsNotifications {
+    #[serde(default = "SettingsNotifications::default_email")]
+    pub email: bool,
+}
+
+impl SettingsNotifications {
+    fn default_email() -> bool {
+        true
+    }
+}
+
+impl Default for SettingsNotifications {
+    fn default() -> Self {
+        Self {
+            email: Self::default_email(),
+        }
+    }
+}